@@ -0,0 +1,198 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use crate::grid::SizedGrid;
+use crate::life::{LifeGrid, LifeGridType};
+use crate::render::Renderer;
+use crate::terminal::{self, Key, KeyReader, RawMode};
+
+
+/// Plays the generations of a run in place in the terminal.
+/// 
+/// Each frame moves the cursor home and redraws the grid, followed by a
+/// status line. Space pauses & resumes, `s` steps a generation while
+/// paused, and `q`, Escape or Ctrl-C stops the run.
+/// 
+/// When the output isn't a terminal, each frame is simply written after the
+/// last, without escape sequences or delays.
+pub struct Animation {
+    frame_time: Duration,
+    is_terminal: bool,
+    is_paused: bool,
+    keys: Option<KeyReader>,
+    raw_mode: Option<RawMode>,
+    last_frame: Option<Instant>,
+    renderer: Renderer,
+    status: (usize, usize, usize, usize),   // <--- The generation, population,
+                                            //      width & height shown.
+    rows: usize                             // <--- The number of rows drawn,
+                                            //      including the borders.
+}
+
+impl Animation {
+    /// Initialises an animation with the given number of frames per second,
+    /// showing the part of each grid given by the renderer - redrawn in place
+    /// if the output is a terminal.
+    /// 
+    /// Keys are only read if the output is a terminal, and both stdin and
+    /// stdout are too.
+    pub fn new(frames_per_second: f64, renderer: Renderer, is_terminal: bool) -> Self {
+        let raw_mode = if is_terminal && terminal::is_interactive() { RawMode::enable() } else { None };
+
+        Self {
+            frame_time: Duration::from_secs_f64(1.0 / frames_per_second),
+            is_terminal,
+            is_paused: false,
+            keys: raw_mode.as_ref().map(|_| KeyReader::new()),
+            raw_mode,
+            last_frame: None,
+            renderer,
+            status: (0, 0, 0, 0),
+            rows: 0
+        }
+    }
+
+    /// Indicates if frames are redrawn in place.
+    pub fn is_terminal(&self) -> bool {
+        self.is_terminal
+    }
+
+    /// Shows the grid of a generation, whose top-left cell is at the given
+    /// origin, after waiting for the rest of the previous frame's time - or
+    /// for a key, while paused.
+    /// 
+    /// Gives `false` if the run should stop.
+    pub fn show(&mut self, generation: usize, grid: &LifeGridType, origin: (isize, isize), w: &mut dyn Write) -> std::io::Result<bool> {
+        if !self.wait(w)? {
+            return Ok(false);
+        }
+
+        self.status = (generation, grid.get_population(), grid.get_width(), grid.get_height());
+
+        if self.is_terminal {
+            if self.last_frame.is_none() {
+                w.write_all(format!("{}{}", terminal::HIDE_CURSOR, terminal::CLEAR_SCREEN).as_bytes())?;
+            }
+            w.write_all(terminal::CURSOR_HOME.as_bytes())?;
+        }
+        else {
+            w.write_all(format!("iteration: {}\n", generation).as_bytes())?;
+        }
+
+        let frame = self.renderer.render(grid, origin);
+        self.rows = frame.get_height() + 2;
+        self.renderer.write_chars(&frame, w)?;
+
+        if self.is_terminal {
+            w.write_all(format!("{}{}{}", self.get_status(), terminal::CLEAR_LINE, terminal::CLEAR_TO_END).as_bytes())?;
+        }
+        w.flush()?;
+
+        self.last_frame = Some(Instant::now());
+        Ok(true)
+    }
+
+    /// Restores the terminal after the last frame - even if the cursor
+    /// can't be shown again.
+    pub fn finish(&mut self, w: &mut dyn Write) -> std::io::Result<()> {
+        self.keys = None;
+        self.raw_mode = None;
+
+        if self.last_frame.take().is_some() && self.is_terminal {
+            w.write_all(format!("\n{}", terminal::SHOW_CURSOR).as_bytes())?;
+            w.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Gives the status line shown below the grid.
+    fn get_status(&self) -> String {
+        let (generation, population, width, height) = self.status;
+        let state = if self.is_paused { "paused - space: resume, s: step" } else { "space: pause" };
+        let keys = if self.keys.is_some() { format!(" | {}, q: quit", state) } else { String::new() };
+
+        format!("generation {} | population {} | {}x{}{}", generation, population, width, height, keys)
+    }
+
+    /// Rewrites the status line, which is below the grid and its borders.
+    fn update_status(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        let row = self.rows + 1;
+        w.write_all(format!("{}{}{}", terminal::move_cursor(row, 1), self.get_status(), terminal::CLEAR_LINE).as_bytes())?;
+        w.flush()
+    }
+
+    /// Waits until the next frame is due, handling keys meanwhile.
+    /// 
+    /// Gives `false` if the run should stop.
+    fn wait(&mut self, w: &mut dyn Write) -> std::io::Result<bool> {
+        let last_frame = match self.last_frame {
+            Some(last_frame) => last_frame,
+            None => return Ok(true)
+        };
+
+        if !self.is_terminal {
+            return Ok(true);
+        }
+
+        loop {
+            let key = match &self.keys {
+                Some(keys) if self.is_paused => keys.read(),
+                Some(keys) => match self.frame_time.checked_sub(last_frame.elapsed()) {
+                    Some(remaining) => keys.read_timeout(remaining),
+                    None => return Ok(true)
+                },
+                None => {
+                    std::thread::sleep(self.frame_time.saturating_sub(last_frame.elapsed()));
+                    return Ok(true);
+                }
+            };
+
+            match key {
+                Some(Key::Char(' ')) => {
+                    self.is_paused = !self.is_paused;
+                    self.update_status(w)?;
+                },
+                Some(Key::Char('s')) if self.is_paused => return Ok(true),
+                Some(Key::Char('q') | Key::Escape | Key::CtrlC) => return Ok(false),
+                Some(_) => {},
+                None if self.is_paused => return Ok(false),
+                None => return Ok(true)
+            }
+        }
+    }
+}
+
+impl Drop for Animation {
+    fn drop(&mut self) {
+        if self.is_terminal && self.last_frame.is_some() {
+            std::io::stdout().write_all(terminal::SHOW_CURSOR.as_bytes()).ok();
+        }
+    }
+}
@@ -0,0 +1,497 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use crate::grid::{BoundingBox, Grid, SimpleGrid, SizedGrid, SparseGrid};
+use crate::life::{LifeGrid, LifeGridType, LifeState};
+use crate::rule::{LifeRule, Rule};
+use crate::universe::Universe;
+
+
+/// A cell that isn't 'dead' - its position and its state number.
+pub type ObjectCell = (isize, isize, u8);
+
+/// Provides the cells of a grid that aren't 'dead', so that they can be
+/// separated into objects.
+pub trait OccupiedCells {
+    fn get_occupied_cells(&self) -> Vec<ObjectCell>;
+}
+
+impl OccupiedCells for LifeGridType {
+    fn get_occupied_cells(&self) -> Vec<ObjectCell> {
+        self.iter()
+            .filter(|cell| cell.get() != &LifeState::DEAD)
+            .map(|cell| (cell.get_x() as isize, cell.get_y() as isize, cell.get().get()))
+            .collect()
+    }
+}
+
+impl OccupiedCells for SparseGrid<LifeState> {
+    fn get_occupied_cells(&self) -> Vec<ObjectCell> {
+        self.iter()
+            .filter(|cell| cell.get() != &LifeState::DEAD)
+            .map(|cell| (cell.get_x() as isize, cell.get_y() as isize, cell.get().get()))
+            .collect()
+    }
+}
+
+impl OccupiedCells for Universe {
+    fn get_occupied_cells(&self) -> Vec<ObjectCell> {
+        let (x, y) = self.get_origin();
+        self.get_grid()
+            .get_occupied_cells()
+            .into_iter()
+            .map(|(cell_x, cell_y, state)| (x + cell_x, y + cell_y, state))
+            .collect()
+    }
+}
+
+
+//---------------------------------------------------------------------------//
+
+
+/// A rotation or reflection of a position.
+type Transform = fn(isize, isize) -> (isize, isize);
+
+/// A group of cells, separated from the other cells of a grid.
+/// 
+/// See `separate()`.
+pub struct Object {
+    cells: Vec<ObjectCell>
+}
+
+impl Object {
+    /// Initialises an object from its cells.
+    pub fn from_cells(cells: Vec<ObjectCell>) -> Self {
+        Self { cells }
+    }
+
+    /// Initialises an object from the cells of a grid that aren't 'dead'.
+    pub fn from_grid(grid: &LifeGridType) -> Self {
+        Self::from_cells(grid.get_occupied_cells())
+    }
+
+    /// Gives the cells of the object.
+    pub fn get_cells(&self) -> &[ObjectCell] {
+        &self.cells
+    }
+
+    /// Gives the number of 'live' cells of the object.
+    pub fn get_population(&self) -> usize {
+        self.cells.iter().filter(|(_, _, state)| *state == LifeState::LIVE.get()).count()
+    }
+
+    /// Gives the smallest region containing the object.
+    pub fn get_bounding_box(&self) -> Option<BoundingBox> {
+        let left = self.cells.iter().map(|(x, _, _)| *x).min()?;
+        let top = self.cells.iter().map(|(_, y, _)| *y).min()?;
+        let right = self.cells.iter().map(|(x, _, _)| *x).max()?;
+        let bottom = self.cells.iter().map(|(_, y, _)| *y).max()?;
+
+        Some(BoundingBox::new(left, top, (right - left + 1) as usize, (bottom - top + 1) as usize))
+    }
+
+    /// Gives the cells of the object in a form that is the same wherever
+    /// the object is, and however it is rotated or reflected.
+    /// 
+    /// Each of the 8 rotations & reflections of the cells is moved so that
+    /// its bounding box starts at (0,0) and is sorted, and the least of
+    /// them is the canonical form.
+    pub fn get_canonical(&self) -> Vec<ObjectCell> {
+        const TRANSFORMS: [Transform; 8] = [
+            |x, y| (x, y),
+            |x, y| (-y, x),
+            |x, y| (-x, -y),
+            |x, y| (y, -x),
+            |x, y| (-x, y),
+            |x, y| (y, x),
+            |x, y| (x, -y),
+            |x, y| (-y, -x)
+        ];
+
+        TRANSFORMS.iter()
+            .map(|transform| {
+                let mut cells: Vec<ObjectCell> = self.cells.iter()
+                    .map(|(x, y, state)| {
+                        let (x, y) = transform(*x, *y);
+                        (x, y, *state)
+                    })
+                    .collect();
+                
+                let left = cells.iter().map(|(x, _, _)| *x).min().unwrap_or(0);
+                let top = cells.iter().map(|(_, y, _)| *y).min().unwrap_or(0);
+                for cell in cells.iter_mut() {
+                    *cell = (cell.0 - left, cell.1 - top, cell.2);
+                }
+
+                // Sort by row, then column...
+                cells.sort_by_key(|(x, y, state)| (*y, *x, *state));
+                cells
+            })
+            .min_by_key(|cells| cells.iter().map(|(x, y, state)| (*y, *x, *state)).collect::<Vec<_>>())
+            .unwrap_or_default()
+    }
+
+    /// Gives a code for the object's shape that is the same wherever the
+    /// object is, and however it is rotated or reflected.
+    /// 
+    /// The code is the rows of the canonical form (see `get_canonical()`),
+    /// separated by `$`, with `.` for a 'dead' cell, e.g. `**$**` for a
+    /// block.
+    pub fn get_code(&self) -> String {
+        let canonical = self.get_canonical();
+        let width = canonical.iter().map(|(x, _, _)| *x + 1).max().unwrap_or(0) as usize;
+        let height = canonical.iter().map(|(_, y, _)| *y + 1).max().unwrap_or(0) as usize;
+
+        let mut rows = vec![vec!['.'; width]; height];
+        for (x, y, state) in canonical {
+            rows[y as usize][x as usize] = LifeState(state).to_char();
+        }
+
+        rows.iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<String>>()
+            .join("$")
+    }
+}
+
+/// Separates the given cells into objects.
+/// 
+/// Cells are in the same object if they touch, including diagonally, or if
+/// there are no more than `gap` empty cells between them - so a larger gap
+/// keeps the parts of loosely connected objects together.
+pub fn separate(cells: &[ObjectCell], gap: usize) -> Vec<Object> {
+    let positions: HashMap<(isize, isize), usize> = cells.iter()
+        .enumerate()
+        .map(|(index, (x, y, _))| ((*x, *y), index))
+        .collect();
+
+    // Flood-fill from each cell that isn't yet part of an object...
+    let reach = gap as isize + 1;
+    let mut visited: HashSet<usize> = HashSet::new();
+    let mut objects = Vec::new();
+    for start in 0..cells.len() {
+        if !visited.insert(start) {
+            continue;
+        }
+
+        let mut object_cells = Vec::new();
+        let mut pending = vec![start];
+        while let Some(index) = pending.pop() {
+            let (x, y, state) = cells[index];
+            object_cells.push((x, y, state));
+
+            for dy in -reach..=reach {
+                for dx in -reach..=reach {
+                    if let Some(other) = positions.get(&(x + dx, y + dy)) {
+                        if visited.insert(*other) {
+                            pending.push(*other);
+                        }
+                    }
+                }
+            }
+        }
+
+        object_cells.sort_by_key(|(x, y, _)| (*y, *x));
+        objects.push(Object::from_cells(object_cells));
+    }
+
+    // Give the objects in the order of their top-left cells...
+    objects.sort_by_key(|object| object.cells.first().map(|(x, y, _)| (*y, *x)));
+    objects
+}
+
+
+/// Finds the spaceships that are escaping from the rest of the given cells.
+/// 
+/// A spaceship (see `Catalogue::is_spaceship()`) is escaping when there are
+/// more than `distance` empty cells between its bounding box and that of
+/// all the objects that aren't spaceships - so spaceships travelling
+/// together escape together. If every object is a spaceship, nothing is
+/// escaping.
+/// 
+/// Gives the escaping spaceships, with their names.
+pub fn find_escapees(cells: &[ObjectCell], catalogue: &Catalogue, gap: usize, distance: usize) -> Vec<(Object, String)> {
+    let mut spaceships = Vec::new();
+    let mut rest: Option<BoundingBox> = None;
+    for object in separate(cells, gap) {
+        match catalogue.identify(&object) {
+            Some(name) if catalogue.is_spaceship(name) => {
+                let name = String::from(name);
+                spaceships.push((object, name));
+            },
+            _ => {
+                let bounds = object.get_bounding_box();
+                rest = match (rest, bounds) {
+                    (Some(rest), Some(bounds)) => Some(rest.union(&bounds)),
+                    _ => rest.or(bounds)
+                };
+            }
+        }
+    }
+
+    let Some(rest) = rest else { return Vec::new() };
+    spaceships.into_iter()
+        .filter(|(object, _)| object.get_bounding_box().is_some_and(|bounds| separation(&bounds, &rest) > distance))
+        .collect()
+}
+
+/// Gives the number of empty cells between two bounding boxes, horizontally
+/// or vertically, whichever is greater.
+fn separation(a: &BoundingBox, b: &BoundingBox) -> usize {
+    let horizontal = (b.x - (a.x + a.width as isize)).max(a.x - (b.x + b.width as isize));
+    let vertical = (b.y - (a.y + a.height as isize)).max(a.y - (b.y + b.height as isize));
+
+    horizontal.max(vertical).max(0) as usize
+}
+
+
+//---------------------------------------------------------------------------//
+
+
+/// A catalogue of named objects, identified by their codes.
+/// 
+/// See `Object::get_code()`.
+pub struct Catalogue {
+    names: HashMap<String, String>,
+    spaceships: HashSet<String>,
+    gap: usize      // <--- The greatest gap between the parts of an object.
+}
+
+impl Catalogue {
+    /// Initialises an empty catalogue.
+    pub fn new() -> Self {
+        Self {
+            names: HashMap::new(),
+            spaceships: HashSet::new(),
+            gap: 0
+        }
+    }
+
+    /// Gives a catalogue of common still lifes, oscillators and spaceships
+    /// of Conway's Game of Life.
+    pub fn builtin() -> Self {
+        const OBJECTS: [(&str, usize, &[&str]); 15] = [
+            ("block", 1, &["**", "**"]),
+            ("beehive", 1, &[" ** ", "*  *", " ** "]),
+            ("loaf", 1, &[" ** ", "*  *", " * *", "  * "]),
+            ("boat", 1, &["** ", "* *", " * "]),
+            ("ship", 1, &["** ", "* *", " **"]),
+            ("tub", 1, &[" * ", "* *", " * "]),
+            ("pond", 1, &[" ** ", "*  *", "*  *", " ** "]),
+            ("long boat", 1, &["**  ", "* * ", " * *", "  * "]),
+            ("barge", 1, &[" *  ", "* * ", " * *", "  * "]),
+            ("blinker", 2, &["***"]),
+            ("toad", 2, &[" ***", "*** "]),
+            ("beacon", 2, &["**  ", "**  ", "  **", "  **"]),
+            ("pulsar", 3, &[
+                "  ***   ***  ",
+                "             ",
+                "*    * *    *",
+                "*    * *    *",
+                "*    * *    *",
+                "  ***   ***  ",
+                "             ",
+                "  ***   ***  ",
+                "*    * *    *",
+                "*    * *    *",
+                "*    * *    *",
+                "             ",
+                "  ***   ***  "
+            ]),
+            ("glider", 4, &[" * ", "  *", "***"]),
+            ("lightweight spaceship", 4, &[" *  *", "*    ", "*   *", "**** "])
+        ];
+
+        let rule = LifeRule::default();
+        let mut catalogue = Self::new();
+        for (name, period, rows) in OBJECTS {
+            let mut grid = LifeGridType::init_life(rows[0].len(), rows.len());
+            for (y, row) in rows.iter().enumerate() {
+                for (x, c) in row.chars().enumerate() {
+                    if c == '*' {
+                        grid.set_live(x, y);
+                    }
+                }
+            }
+
+            catalogue.add(name, &grid, period, &rule);
+        }
+
+        catalogue
+    }
+
+    /// Adds an object, given as a grid, to the catalogue under the given
+    /// name.
+    /// 
+    /// Each of the object's phases, found by running the given rule for the
+    /// given period, is added - so the object is identified in any phase.
+    /// If the object has moved after the period, it is a spaceship.
+    pub fn add(&mut self, name: &str, grid: &LifeGridType, period: usize, rule: &dyn Rule) {
+        let mut universe = Universe::from_grid(grid);
+        let origin = universe.get_origin();
+        for _ in 0..period {
+            let cells = universe.get_occupied_cells();
+            while separate(&cells, self.gap).len() > 1 {
+                self.gap += 1;
+            }
+
+            self.names.insert(Object::from_cells(cells).get_code(), String::from(name));
+            universe.step(rule);
+        }
+
+        if universe.get_origin() != origin {
+            self.spaceships.insert(String::from(name));
+        }
+    }
+
+    /// Gives the name of the object, if it is in the catalogue.
+    pub fn identify(&self, object: &Object) -> Option<&str> {
+        self.names.get(&object.get_code()).map(String::as_str)
+    }
+
+    /// Indicates if the catalogue has an object with the given name.
+    pub fn has_name(&self, name: &str) -> bool {
+        self.names.values().any(|value| value == name)
+    }
+
+    /// Indicates if the named object is a spaceship in the catalogue.
+    pub fn is_spaceship(&self, name: &str) -> bool {
+        self.spaceships.contains(name)
+    }
+
+    /// Gives the greatest gap between the separate parts of an object in
+    /// the catalogue (see `separate()`) - e.g. 1 for the quarters of a
+    /// pulsar.
+    pub fn get_gap(&self) -> usize {
+        self.gap
+    }
+}
+
+impl Default for Catalogue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+//---------------------------------------------------------------------------//
+
+
+/// A count of the objects of each kind.
+/// 
+/// Objects in the catalogue are counted by name, and any other objects by
+/// code (see `Object::get_code()`).
+pub struct Census {
+    counts: HashMap<String, usize>
+}
+
+impl Census {
+    /// Initialises an empty census.
+    pub fn new() -> Self {
+        Self {
+            counts: HashMap::new()
+        }
+    }
+
+    /// Takes a census of the given cells, separated into objects with the
+    /// given gap (see `separate()`) and named from the given catalogue.
+    /// 
+    /// Objects in the catalogue with parts further apart than the gap, such
+    /// as a pulsar, are still named - as long as nothing else is near them.
+    pub fn take(cells: &[ObjectCell], catalogue: &Catalogue, gap: usize) -> Self {
+        let mut census = Self::new();
+        let mut cells = cells.to_vec();
+        if catalogue.get_gap() > gap {
+            let mut named = HashSet::new();
+            for object in separate(&cells, catalogue.get_gap()) {
+                if let Some(name) = catalogue.identify(&object) {
+                    census.add(name, 1);
+                    named.extend(object.get_cells().iter().map(|(x, y, _)| (*x, *y)));
+                }
+            }
+
+            cells.retain(|(x, y, _)| !named.contains(&(*x, *y)));
+        }
+
+        for object in separate(&cells, gap) {
+            match catalogue.identify(&object) {
+                Some(name) => census.add(name, 1),
+                None => census.add(&object.get_code(), 1)
+            }
+        }
+
+        census
+    }
+
+    /// Adds the given number of objects of the given kind.
+    pub fn add(&mut self, name: &str, count: usize) {
+        *self.counts.entry(String::from(name)).or_insert(0) += count;
+    }
+
+    /// Adds the counts of another census.
+    pub fn merge(&mut self, other: &Census) {
+        for (name, count) in &other.counts {
+            self.add(name, *count);
+        }
+    }
+
+    /// Gives the number of objects of the given kind.
+    pub fn get_count(&self, name: &str) -> usize {
+        self.counts.get(name).copied().unwrap_or(0)
+    }
+
+    /// Gives the number of objects of all kinds.
+    pub fn get_total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Gives each kind of object with its count, most common first.
+    pub fn get_entries(&self) -> Vec<(&str, usize)> {
+        let mut entries: Vec<(&str, usize)> = self.counts.iter().map(|(name, count)| (name.as_str(), *count)).collect();
+        entries.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        entries
+    }
+}
+
+impl Default for Census {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for Census {
+    /// Gives a line for each kind of object, most common first.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (name, count) in self.get_entries() {
+            writeln!(f, "{:>6} {}", count, name)?;
+        }
+
+        Ok(())
+    }
+}
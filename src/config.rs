@@ -0,0 +1,244 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use crate::file::ReadBuffer;
+
+
+/// A value in a configuration file.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfigValue {
+    String(String),
+
+    /// A number, kept as it was written.
+    Number(String),
+
+    Boolean(bool)
+}
+
+impl Display for ConfigValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::String(text) | Self::Number(text) => write!(f, "{}", text),
+            Self::Boolean(value) => write!(f, "{}", value)
+        }
+    }
+}
+
+/// A setting - the long name of an option and its value.
+pub type ConfigSetting = (String, ConfigValue);
+
+/// The settings of a configuration file, a subset of TOML.
+/// 
+/// Settings before any section apply to every run. Settings in a
+/// `[profile.<name>]` section apply when that profile is chosen, over the
+/// others. Each setting is `<option> = <value>`, where the value is a
+/// quoted string, a number, or `true`/`false` for a flag, e.g.
+/// 
+/// ```text
+/// rule = "B3/S23"
+/// 
+/// [profile.bench]
+/// unbounded = true
+/// max = 500
+/// ```
+#[derive(Debug)]
+pub struct Config {
+    path: Option<String>,
+    defaults: Vec<ConfigSetting>,
+    profiles: Vec<(String, Vec<ConfigSetting>)>
+}
+
+impl Config {
+    /// The name of a configuration file in the working directory.
+    pub const FILE_NAME: &'static str = "conway-life.toml";
+
+    /// The name of a configuration file in the home directory.
+    pub const HOME_FILE_NAME: &'static str = ".conway-life.toml";
+
+    /// Parses the contents of a configuration file.
+    pub fn parse(text: &str, path: Option<&str>) -> Result<Self, String> {
+        let mut config = Self {
+            path: path.map(String::from),
+            defaults: Vec::new(),
+            profiles: Vec::new()
+        };
+
+        let mut profile: Option<usize> = None;
+        for (index, line) in text.lines().enumerate() {
+            let error = |message: String| format!("{}, at line {} of file '{}'", message, index + 1, path.unwrap_or("*unknown*"));
+
+            let line = Self::strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(section) = line.strip_prefix('[') {
+                let name = section.strip_suffix(']')
+                    .and_then(|section| section.trim().strip_prefix("profile."))
+                    .map(|name| name.trim())
+                    .filter(|name| !name.is_empty())
+                    .ok_or_else(|| error(format!("bad section '{}', expected '[profile.<name>]'", line)))?;
+
+                profile = Some(match config.profiles.iter().position(|(existing, _)| existing == name) {
+                    Some(position) => position,
+                    None => {
+                        config.profiles.push((String::from(name), Vec::new()));
+                        config.profiles.len() - 1
+                    }
+                });
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or_else(|| error(format!("expected '<option> = <value>', found '{}'", line)))?;
+            let key = key.trim();
+            if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || (c == '-') || (c == '_')) {
+                return Err(error(format!("bad option name '{}'", key)));
+            }
+
+            let value = Self::parse_value(value.trim()).ok_or_else(|| error(format!("bad value '{}' for option '{}'", value.trim(), key)))?;
+            let settings = match profile {
+                Some(position) => &mut config.profiles[position].1,
+                None => &mut config.defaults
+            };
+            settings.push((key.replace('_', "-"), value));
+        }
+
+        Ok(config)
+    }
+
+    /// Loads a configuration file.
+    pub fn from_path(path: &str) -> Result<Self, String> {
+        let buffer = ReadBuffer::from_path(path).map_err(|_| format!("cannot open file '{}'", path))?;
+        Self::parse(&String::from_utf8_lossy(buffer.as_slice()), Some(path))
+    }
+
+    /// Gives the path of the configuration file to use, if there is one -
+    /// in the working directory, otherwise in the home directory.
+    pub fn find() -> Option<PathBuf> {
+        let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+
+        std::iter::once(PathBuf::from(Self::FILE_NAME))
+            .chain(home.map(|home| Path::new(&home).join(Self::HOME_FILE_NAME)))
+            .find(|path| path.is_file())
+    }
+
+    /// Gives the path the configuration was loaded from, if any.
+    pub fn get_path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Gives the names of the profiles.
+    pub fn get_profiles(&self) -> Vec<&str> {
+        self.profiles.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Gives the settings of the given profile, over the settings that
+    /// apply to every run - or just the latter without a profile.
+    /// 
+    /// A later setting of the same option replaces an earlier one.
+    pub fn get_settings(&self, profile: Option<&str>) -> Result<Vec<ConfigSetting>, String> {
+        let mut settings = self.defaults.clone();
+        if let Some(name) = profile {
+            let (_, profile_settings) = self.profiles.iter()
+                .find(|(existing, _)| existing == name)
+                .ok_or_else(|| format!("profile '{}' not found in file '{}'", name, self.path.as_deref().unwrap_or("*unknown*")))?;
+            settings.extend(profile_settings.iter().cloned());
+        }
+
+        let mut merged: Vec<ConfigSetting> = Vec::new();
+        for (key, value) in settings {
+            match merged.iter_mut().find(|(existing, _)| *existing == key) {
+                Some(setting) => setting.1 = value,
+                None => merged.push((key, value))
+            }
+        }
+
+        Ok(merged)
+    }
+
+    /// Removes a comment from a line - a `#` that isn't within a string.
+    fn strip_comment(line: &str) -> &str {
+        let mut quote: Option<char> = None;
+        let mut is_escaped = false;
+        for (index, c) in line.char_indices() {
+            match (quote, c) {
+                (Some('"'), '\\') if !is_escaped => {
+                    is_escaped = true;
+                    continue;
+                },
+                (Some(q), _) if (c == q) && !is_escaped => quote = None,
+                (None, '"' | '\'') => quote = Some(c),
+                (None, '#') => return &line[..index],
+                _ => {}
+            }
+            is_escaped = false;
+        }
+
+        line
+    }
+
+    /// Parses a value - a basic (`"..."`) or literal (`'...'`) string, a
+    /// number or a boolean.
+    fn parse_value(text: &str) -> Option<ConfigValue> {
+        if let Some(literal) = text.strip_prefix('\'') {
+            let literal = literal.strip_suffix('\'')?;
+            return if literal.contains('\'') { None } else { Some(ConfigValue::String(String::from(literal))) };
+        }
+
+        if let Some(basic) = text.strip_prefix('"') {
+            let basic = basic.strip_suffix('"')?;
+            let mut value = String::new();
+            let mut chars = basic.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => value.push(match chars.next()? {
+                        'n' => '\n',
+                        't' => '\t',
+                        c @ ('"' | '\\') => c,
+                        _ => return None
+                    }),
+                    '"' => return None,
+                    _ => value.push(c)
+                }
+            }
+
+            return Some(ConfigValue::String(value));
+        }
+
+        match text {
+            "true" => Some(ConfigValue::Boolean(true)),
+            "false" => Some(ConfigValue::Boolean(false)),
+            _ => {
+                let number = text.replace('_', "");
+                let is_numeric = number.trim_start_matches(['-', '+']).starts_with(|c: char| c.is_ascii_digit());
+                number.parse::<f64>().ok().filter(|_| is_numeric).map(|_| ConfigValue::Number(number))
+            }
+        }
+    }
+}
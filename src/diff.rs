@@ -0,0 +1,140 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::io::Write;
+
+use crate::grid::{Grid, SimpleGrid, SizedGrid};
+use crate::life::{LifeGridType, LifeState};
+
+
+/// How a cell has changed between generations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CellChange {
+    /// The cell has become 'live'.
+    Birth,
+
+    /// The cell was 'live' and isn't any more.
+    Death,
+
+    /// The cell's state has changed otherwise - e.g. a 'dying' cell has
+    /// decayed.
+    Other
+}
+
+/// The differences between two grids of the same size.
+pub struct GridDiff {
+    changes: Vec<(usize, usize, CellChange)>
+}
+
+impl GridDiff {
+    /// Gives the differences from the previous grid to the current grid.
+    pub fn between(previous: &LifeGridType, current: &LifeGridType) -> Self {
+        let mut changes = Vec::new();
+        for cell in current {
+            let (x, y) = (cell.get_x(), cell.get_y());
+            let (before, after) = (*previous.get(x, y), *cell.get());
+            if before != after {
+                let change = if after == LifeState::LIVE {
+                    CellChange::Birth
+                }
+                else if before == LifeState::LIVE {
+                    CellChange::Death
+                }
+                else {
+                    CellChange::Other
+                };
+
+                changes.push((x, y, change));
+            }
+        }
+
+        Self { changes }
+    }
+
+    /// Gives every changed cell, in row order.
+    pub fn get_changes(&self) -> &[(usize, usize, CellChange)] {
+        &self.changes
+    }
+
+    /// Gives the cells that have become 'live'.
+    pub fn get_births(&self) -> Vec<(usize, usize)> {
+        self.get_positions(CellChange::Birth)
+    }
+
+    /// Gives the cells that were 'live' and aren't any more.
+    pub fn get_deaths(&self) -> Vec<(usize, usize)> {
+        self.get_positions(CellChange::Death)
+    }
+
+    /// Gives the change to the cell at the given position, if any.
+    pub fn get_change(&self, x: usize, y: usize) -> Option<CellChange> {
+        self.changes.iter().find(|(change_x, change_y, _)| (*change_x == x) && (*change_y == y)).map(|(_, _, change)| *change)
+    }
+
+    /// Indicates if nothing has changed.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    fn get_positions(&self, kind: CellChange) -> Vec<(usize, usize)> {
+        self.changes.iter().filter(|(_, _, change)| *change == kind).map(|(x, y, _)| (*x, *y)).collect()
+    }
+
+    /// Writes the current grid to the given output stream, as with
+    /// `SimpleGrid::write()`, with births and deaths marked in the given
+    /// style.
+    pub fn write(&self, current: &LifeGridType, style: DiffStyle, w: &mut dyn Write) -> std::io::Result<()> {
+        let mut marked = SimpleGrid::init_from(current.get_width(), current.get_height(), String::new);
+        for cell in current {
+            marked.set(cell.get_x(), cell.get_y(), cell.get().to_string());
+        }
+
+        for (x, y, change) in &self.changes {
+            let mark = match (style, change) {
+                (DiffStyle::Characters, CellChange::Birth) => String::from("+"),
+                (DiffStyle::Characters, CellChange::Death) => String::from("-"),
+                (DiffStyle::Colour, CellChange::Birth) => format!("\x1b[32m{}\x1b[0m", LifeState::LIVE),
+                (DiffStyle::Colour, CellChange::Death) => format!("\x1b[31m{}\x1b[0m", LifeState::LIVE),
+                (_, CellChange::Other) => continue
+            };
+
+            marked.set(*x, *y, mark);
+        }
+
+        marked.write(w)
+    }
+}
+
+/// How births and deaths are marked by `GridDiff::write()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffStyle {
+    /// A birth is a `+` and a death is a `-`.
+    Characters,
+
+    /// A birth is a green 'live' cell and a death is a red 'live' cell,
+    /// using ANSI escape codes.
+    Colour
+}
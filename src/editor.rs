@@ -0,0 +1,328 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::fs::File;
+use std::io::Write;
+use std::time::Duration;
+
+use crate::file;
+use crate::grid::{Grid, SizedGrid};
+use crate::life::{LifeGrid, LifeGridType, LifeState};
+use crate::rule::Rule;
+use crate::terminal::{self, Key, KeyReader, RawMode};
+
+
+/// A symbol that can be stamped onto the grid - its name and the offsets of
+/// its 'live' cells.
+pub type Symbol = (String, Vec<(usize, usize)>);
+
+/// An interactive editor & player of a grid, in a terminal.
+/// 
+/// A cursor is moved over the grid to toggle cells or stamp symbols (see
+/// `FileParser::parse_symbols()`), and the pattern can be stepped or run.
+/// Every change can be undone, and the grid saved as a 'life' file or, for
+/// a `.rle` path, an RLE file.
+pub struct Editor {
+    grid: LifeGridType,
+    path: String,
+    cursor: (usize, usize),
+    generation: usize,
+    history: Vec<(LifeGridType, usize)>,
+    symbols: Vec<Symbol>,
+    symbol: usize,
+    is_running: bool,
+    is_modified: bool,
+    is_quitting: bool,
+    message: String
+}
+
+impl Editor {
+    /// The number of changes that can be undone.
+    const MAX_UNDO: usize = 100;
+
+    const HELP: &'static str = "arrows/hjkl: move  space: toggle  n: step  r: run/pause  u: undo  [ ]: symbol  t: stamp  c: clear  w: save  q: quit";
+
+    /// Initialises an editor of the given grid, which is saved to the given
+    /// path.
+    pub fn new(grid: LifeGridType, path: &str) -> Self {
+        Self {
+            grid,
+            path: String::from(path),
+            cursor: (0, 0),
+            generation: 0,
+            history: Vec::new(),
+            symbols: Vec::new(),
+            symbol: 0,
+            is_running: false,
+            is_modified: false,
+            is_quitting: false,
+            message: String::new()
+        }
+    }
+
+    /// Sets the symbols that can be stamped.
+    pub fn set_symbols(&mut self, symbols: Vec<Symbol>) {
+        self.symbols = symbols;
+        self.symbol = 0;
+    }
+
+    pub fn get_grid(&self) -> &LifeGridType {
+        &self.grid
+    }
+
+    pub fn get_cursor(&self) -> (usize, usize) {
+        self.cursor
+    }
+
+    pub fn get_generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Indicates if the pattern is running.
+    pub fn is_running(&self) -> bool {
+        self.is_running
+    }
+
+    /// Gives the name of the symbol that would be stamped, if any.
+    pub fn get_symbol_name(&self) -> Option<&str> {
+        self.symbols.get(self.symbol).map(|(name, _)| name.as_str())
+    }
+
+    /// Edits & plays the grid in the terminal until quit, with the given
+    /// time between generations while running.
+    pub fn run(&mut self, rule: &dyn Rule, frame_time: Duration) -> Result<(), String> {
+        if !terminal::is_interactive() {
+            return Err(String::from("the editor needs a terminal"));
+        }
+
+        let raw_mode = RawMode::enable().ok_or_else(|| String::from("cannot set the terminal to raw mode"))?;
+        let keys = KeyReader::new();
+        let mut stdout = std::io::stdout();
+
+        // The terminal is restored as the keys & raw mode are dropped, even
+        // if it can't be written to...
+        let write_error = |error: std::io::Error| format!("cannot write to the terminal: {}", error);
+        stdout.write_all(format!("{}{}", terminal::HIDE_CURSOR, terminal::CLEAR_SCREEN).as_bytes()).map_err(write_error)?;
+        loop {
+            stdout.write_all(format!("{}{}", terminal::CURSOR_HOME, self.render()).as_bytes())
+                .and_then(|_| stdout.flush())
+                .map_err(write_error)?;
+
+            let key = if self.is_running { keys.read_timeout(frame_time) } else { keys.read() };
+            match key {
+                Some(key) => {
+                    if !self.handle_key(key, rule) {
+                        break;
+                    }
+                },
+                None if self.is_running => self.step(rule),
+                None => break
+            }
+        }
+
+        let result = stdout.write_all(format!("{}{}{}", terminal::CLEAR_SCREEN, terminal::CURSOR_HOME, terminal::SHOW_CURSOR).as_bytes())
+            .and_then(|_| stdout.flush())
+            .map_err(write_error);
+        drop(keys);
+        drop(raw_mode);
+
+        result
+    }
+
+    /// Handles a key.
+    /// 
+    /// Gives `false` when the editor should quit - quitting with unsaved
+    /// changes needs a second `q`.
+    pub fn handle_key(&mut self, key: Key, rule: &dyn Rule) -> bool {
+        let is_quitting = std::mem::take(&mut self.is_quitting);
+        self.message.clear();
+
+        let (x, y) = self.cursor;
+        let (width, height) = (self.grid.get_width(), self.grid.get_height());
+        match key {
+            Key::Left | Key::Char('h') => self.cursor.0 = x.saturating_sub(1),
+            Key::Right | Key::Char('l') => self.cursor.0 = (x + 1).min(width.saturating_sub(1)),
+            Key::Up | Key::Char('k') => self.cursor.1 = y.saturating_sub(1),
+            Key::Down | Key::Char('j') => self.cursor.1 = (y + 1).min(height.saturating_sub(1)),
+            Key::Char(' ') | Key::Enter => self.toggle(rule.get_states()),
+            Key::Char('n') | Key::Char('.') => {
+                self.save_undo();
+                self.step(rule);
+            },
+            Key::Char('r') => {
+                if !self.is_running {
+                    self.save_undo();
+                }
+                self.is_running = !self.is_running;
+            },
+            Key::Char('u') => self.undo(),
+            Key::Char('[') => self.select_symbol(-1),
+            Key::Char(']') => self.select_symbol(1),
+            Key::Char('t') => self.stamp(),
+            Key::Char('c') => {
+                self.save_undo();
+                self.grid = LifeGridType::init_life(width, height);
+                self.is_modified = true;
+            },
+            Key::Char('w') => self.message = match self.save(rule) {
+                Ok(()) => format!("saved '{}'", self.path),
+                Err(message) => format!("error: {}", message)
+            },
+            Key::Char('q') | Key::Escape | Key::CtrlC => {
+                if !self.is_modified || is_quitting {
+                    return false;
+                }
+
+                self.is_quitting = true;
+                self.message = String::from("unsaved changes - press q again to quit, or w to save");
+            },
+            _ => {}
+        }
+
+        true
+    }
+
+    /// Gives the grid with the cursor highlighted, followed by the status,
+    /// help & message lines.
+    pub fn render(&self) -> String {
+        let width = self.grid.get_width();
+        let mut frame = format!("/{}\\{}\r\n", "-".repeat(width), terminal::CLEAR_LINE);
+        for y in 0..self.grid.get_height() {
+            frame.push('|');
+            for x in 0..width {
+                let c = self.grid.get(x, y).to_char();
+                if (x, y) == self.cursor {
+                    frame += &format!("{}{}{}", terminal::REVERSE_VIDEO, c, terminal::RESET_ATTRIBUTES);
+                }
+                else {
+                    frame.push(c);
+                }
+            }
+            frame += &format!("|{}\r\n", terminal::CLEAR_LINE);
+        }
+        frame += &format!("\\{}/{}\r\n", "-".repeat(width), terminal::CLEAR_LINE);
+
+        let status = format!("generation {} | population {} | cursor ({},{}) | symbol: {} | {}{}",
+            self.generation,
+            self.grid.get_population(),
+            self.cursor.0, self.cursor.1,
+            self.get_symbol_name().unwrap_or("none"),
+            if self.is_running { "running" } else { "paused" },
+            if self.is_modified { " | modified" } else { "" });
+
+        frame + &format!("{}{}\r\n{}{}\r\n{}{}{}", status, terminal::CLEAR_LINE, Self::HELP, terminal::CLEAR_LINE, self.message, terminal::CLEAR_LINE, terminal::CLEAR_TO_END)
+    }
+
+    /// Steps the pattern by a generation.
+    fn step(&mut self, rule: &dyn Rule) {
+        self.grid = rule.step(&self.grid);
+        self.generation += 1;
+        self.is_modified = true;
+    }
+
+    /// Cycles the state of the cell under the cursor.
+    fn toggle(&mut self, states: u8) {
+        let (x, y) = self.cursor;
+        if (x >= self.grid.get_width()) || (y >= self.grid.get_height()) {
+            return;
+        }
+
+        self.save_undo();
+        let state = self.grid.get(x, y).get();
+        self.grid.set_state(x, y, (state + 1) % states.max(2));
+        self.is_modified = true;
+    }
+
+    /// Stamps the selected symbol with its top-left at the cursor - cells
+    /// beyond the edges of the grid are left out.
+    fn stamp(&mut self) {
+        let cells = match self.symbols.get(self.symbol) {
+            Some((_, cells)) => cells.clone(),
+            None => {
+                self.message = String::from("no symbols - give a library with '--library'");
+                return;
+            }
+        };
+
+        self.save_undo();
+        let (x, y) = self.cursor;
+        for (dx, dy) in cells {
+            if (x + dx < self.grid.get_width()) && (y + dy < self.grid.get_height()) {
+                self.grid.set_live(x + dx, y + dy);
+            }
+        }
+        self.is_modified = true;
+    }
+
+    /// Selects the previous or next symbol.
+    fn select_symbol(&mut self, direction: isize) {
+        if !self.symbols.is_empty() {
+            self.symbol = (self.symbol as isize + direction).rem_euclid(self.symbols.len() as isize) as usize;
+        }
+    }
+
+    /// Records the grid before a change, so that it can be undone.
+    fn save_undo(&mut self) {
+        if self.history.len() == Self::MAX_UNDO {
+            self.history.remove(0);
+        }
+
+        self.history.push((self.grid.clone(), self.generation));
+    }
+
+    /// Undoes the last change.
+    fn undo(&mut self) {
+        self.is_running = false;
+        match self.history.pop() {
+            Some((grid, generation)) => {
+                self.grid = grid;
+                self.generation = generation;
+                self.is_modified = true;
+            },
+            None => self.message = String::from("nothing to undo")
+        }
+    }
+
+    /// Saves the grid as a 'life' file or, for a `.rle` path, an RLE file
+    /// with the rule in its header.
+    fn save(&mut self, rule: &dyn Rule) -> Result<(), String> {
+        let mut output = Vec::new();
+        let result = if self.path.to_lowercase().ends_with(".rle") {
+            file::write_rle(&self.grid, Some(&rule.get_rulestring()), &mut output)
+        }
+        else {
+            file::write_life(&self.grid, &mut output)
+        };
+        result.map_err(|error| error.to_string())?;
+
+        File::create(&self.path)
+            .and_then(|mut file| file.write_all(&output))
+            .map_err(|_| format!("cannot write file '{}'", self.path))?;
+
+        self.is_modified = false;
+        Ok(())
+    }
+}
@@ -29,54 +29,361 @@ use std::ops::{Index};
 use std::{env};
 use std::vec::{Vec};
 
+use crate::config::{Config, ConfigSetting, ConfigValue};
 
+
+/// How an option takes a value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OptionValue {
+    /// A flag, which takes no value.
+    None,
+
+    /// A value is required, either as `--option value` or `--option=value`.
+    Required,
+
+    /// A value may be given, but only as `--option=value` - so that a
+    /// following argument isn't taken as the value.
+    Optional
+}
+
+/// The definition of a command-line option, e.g. `-r, --rule <rule>`.
+#[derive(Clone, Copy, Debug)]
+pub struct OptionSpec {
+    pub long: &'static str,
+    pub short: Option<char>,
+    pub value: OptionValue,
+    pub value_name: &'static str,   // <--- The value as shown in help, e.g. `<rule>` - empty for a flag.
+    pub help: &'static str
+}
+
+impl OptionSpec {
+    /// Gives the option as shown in help, e.g. `-r, --rule <rule>`.
+    fn get_synopsis(&self) -> String {
+        let short = match self.short {
+            Some(c) => format!("-{}, ", c),
+            None => String::from("    ")
+        };
+
+        let value = match self.value {
+            OptionValue::None => String::new(),
+            OptionValue::Required => format!(" {}", self.value_name),
+            OptionValue::Optional => format!("[={}]", self.value_name)
+        };
+
+        format!("{}--{}{}", short, self.long, value)
+    }
+}
+
+/// The options every command has - for choosing settings from a
+/// configuration file (see `Config`) and asking for help.
+const GLOBAL_OPTIONS: &[OptionSpec] = &[
+    OptionSpec {
+        long: "config",
+        short: None,
+        value: OptionValue::Required,
+        value_name: "<path>",
+        help: "The configuration file, instead of the one found in the working or home directory"
+    },
+    OptionSpec {
+        long: "profile",
+        short: Some('p'),
+        value: OptionValue::Required,
+        value_name: "<name>",
+        help: "The profile of settings to use from the configuration file"
+    },
+    OptionSpec {
+        long: "help",
+        short: Some('h'),
+        value: OptionValue::None,
+        value_name: "",
+        help: "Print this help"
+    }
+];
+
+/// The definition of a (sub)command - its positional arguments & options.
+#[derive(Clone, Copy, Debug)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub summary: &'static str,
+    pub args: &'static [&'static str],
+    pub options: &'static [OptionSpec]
+}
+
+impl CommandSpec {
+    /// Gives the usage line of the command.
+    pub fn get_usage(&self, program_name: &str) -> String {
+        let mut usage = format!("usage: {} {} [options]", program_name, self.name);
+        for arg in self.args {
+            usage += &format!(" <{}>", arg);
+        }
+
+        usage
+    }
+
+    /// Gives the full help of the command - its usage and every option.
+    pub fn get_help(&self, program_name: &str) -> String {
+        let options: Vec<(String, &str)> = self.options.iter()
+            .chain(GLOBAL_OPTIONS)
+            .map(|option| (option.get_synopsis(), option.help))
+            .collect();
+        let width = options.iter().map(|(synopsis, _)| synopsis.len()).max().unwrap_or(0);
+
+        let mut help = format!("{}\n\n{}\n\noptions:\n", self.get_usage(program_name), self.summary);
+        for (synopsis, text) in options {
+            help += &format!("  {:<width$}  {}\n", synopsis, text, width = width);
+        }
+
+        help
+    }
+
+    fn find_long(&self, name: &str) -> Option<&OptionSpec> {
+        self.options.iter().chain(GLOBAL_OPTIONS).find(|option| option.long == name)
+    }
+
+    fn find_short(&self, c: char) -> Option<&OptionSpec> {
+        self.options.iter().chain(GLOBAL_OPTIONS).find(|option| option.short == Some(c))
+    }
+}
+
+/// Gives the help listing every command.
+/// 
+/// The first command is the default, used when no command is named.
+pub fn get_commands_help(program_name: &str, commands: &[CommandSpec]) -> String {
+    let width = commands.iter().map(|command| command.name.len()).max().unwrap_or(0);
+
+    let mut help = format!("usage: {} [<command>] [options] <args>\n\ncommands:\n", program_name);
+    for (index, command) in commands.iter().enumerate() {
+        let default = if index == 0 { " (default)" } else { "" };
+        help += &format!("  {:<width$}  {}{}\n", command.name, command.summary, default, width = width);
+    }
+
+    help + &format!("\nuse '{} <command> --help' for the options of a command\n", program_name)
+}
+
+
+//---------------------------------------------------------------------------//
+
+
+/// The parsed command-line - the command, its positional arguments and the
+/// options given.
+/// 
+/// Options are `--long` or `-s` (short options may be grouped, e.g. `-vd`),
+/// with values given as `--long value`, `--long=value`, `-s value` or
+/// `-svalue`. Everything after `--` is a positional argument.
+#[derive(Debug)]
 pub struct ArgsHelper {
     program_name: String,
+    command: Option<CommandSpec>,
     args: Vec<String>,
-    options: Vec<String>
+    options: Vec<(String, Option<String>)>
 }
 
 impl ArgsHelper {
-    pub fn expect(arg_count: usize, message: &str) -> Self {
-        let new = Self::init();
+    /// Parses the program's command-line against the given commands, with
+    /// any settings from a configuration file under the options given.
+    /// 
+    /// Prints help and exits if asked for with `--help`, and prints an
+    /// error and exits if the command-line or configuration file isn't
+    /// valid.
+    pub fn init(commands: &[CommandSpec]) -> Self {
+        let mut command_line = env::args();
+        let program_name = command_line.next().unwrap_or_default();
+        let program_name = String::from(program_name.rsplit(['/', '\\']).next().unwrap_or_default());
+        let args: Vec<String> = command_line.collect();
 
-        if new.len() < arg_count {
-            exit_with_error(format!("{}: {}", new.program_name, message));
-            std::process::exit(0);
+        match Self::parse(&program_name, &args, commands) {
+            Ok(new) if new.has_option("help") => {
+                match new.command {
+                    Some(command) if new.is_command_named(&args) => print!("{}", command.get_help(&program_name)),
+                    _ => print!("{}", get_commands_help(&program_name, commands))
+                }
+                std::process::exit(0);
+            },
+            Ok(mut new) => match new.configure() {
+                Ok(()) => new,
+                Err(message) => {
+                    exit_with_error(format!("{}: error: {}", program_name, message));
+                    unreachable!()
+                }
+            },
+            Err(message) => {
+                exit_with_error(format!("{}: error: {}\nuse '{} --help' for more information", program_name, message, program_name));
+                unreachable!()
+            }
         }
-
-        new
     }
 
-    pub fn init() -> Self {
-        let mut args = Vec::new();
-        let mut options = Vec::new();
+    /// Parses the given arguments (excluding the program name) against the
+    /// given commands.
+    /// 
+    /// The command is named by the first argument - if it doesn't name one,
+    /// the first command is used. With `--help`, the arguments aren't
+    /// checked.
+    pub fn parse(program_name: &str, args: &[String], commands: &[CommandSpec]) -> Result<Self, String> {
+        let mut new = Self {
+            program_name: String::from(program_name),
+            command: None,
+            args: Vec::new(),
+            options: Vec::new()
+        };
 
-        let mut command_line = env::args();
-        let program_name = command_line.next().unwrap_or_default();
+        let mut remaining = args.iter().peekable();
+        let command = match args.first().and_then(|name| commands.iter().find(|command| command.name == name)) {
+            Some(command) => {
+                remaining.next();
+                command
+            },
+            None => commands.first().ok_or_else(|| String::from("no commands are defined"))?
+        };
+        new.command = Some(*command);
 
-        for arg in command_line {
-            if ['-', '+'].contains(&arg.chars().nth(0).unwrap_or_default()) {
-                options.push(arg);
+        let mut is_terminated = false;
+        while let Some(arg) = remaining.next() {
+            if is_terminated || (arg == "-") || !arg.starts_with('-') {
+                new.args.push(arg.clone());
+            }
+            else if arg == "--" {
+                is_terminated = true;
+            }
+            else if let Some(long) = arg.strip_prefix("--") {
+                let (name, value) = match long.split_once('=') {
+                    Some((name, value)) => (name, Some(String::from(value))),
+                    None => (long, None)
+                };
+
+                let option = command.find_long(name).ok_or_else(|| format!("unknown option '--{}' for command '{}'", name, command.name))?;
+                let value = match (option.value, value) {
+                    (OptionValue::None, Some(_)) => return Err(format!("option '--{}' doesn't take a value", name)),
+                    (OptionValue::Required, None) => Some(remaining.next().cloned().ok_or_else(|| format!("option '--{}' needs a value", name))?),
+                    (_, value) => value
+                };
+
+                new.options.push((String::from(option.long), value));
             }
             else {
-                args.push(arg);
+                // One or more short options, the last of which may take a
+                // value...
+                let shorts = &arg[1..];
+                for (index, c) in shorts.char_indices() {
+                    let option = command.find_short(c).ok_or_else(|| format!("unknown option '-{}' for command '{}'", c, command.name))?;
+                    if let OptionValue::None = option.value {
+                        new.options.push((String::from(option.long), None));
+                        continue;
+                    }
+
+                    let rest = shorts[index + c.len_utf8()..].trim_start_matches('=');
+                    let value = if !rest.is_empty() {
+                        Some(String::from(rest))
+                    }
+                    else if let OptionValue::Required = option.value {
+                        Some(remaining.next().cloned().ok_or_else(|| format!("option '-{}' needs a value", c))?)
+                    }
+                    else {
+                        None
+                    };
+
+                    new.options.push((String::from(option.long), value));
+                    break;
+                }
             }
         }
 
-        Self {
-            program_name,
-            args,
-            options
+        if !new.has_option("help") && (new.args.len() != command.args.len()) {
+            return Err(format!("command '{}' expects {} argument(s), but {} given\n{}", command.name, command.args.len(), new.args.len(), command.get_usage(program_name)));
         }
+
+        Ok(new)
+    }
+
+    /// Applies the settings from the configuration file given by `--config`,
+    /// or else found (see `Config::find()`), for the profile given by
+    /// `--profile`.
+    fn configure(&mut self) -> Result<(), String> {
+        let config = match self.get_option_value("config") {
+            Some(path) => Some(Config::from_path(path)?),
+            None => Config::find().map(|path| Config::from_path(&path.to_string_lossy())).transpose()?
+        };
+
+        match (config, self.get_option_value("profile")) {
+            (Some(config), profile) => self.apply_settings(&config.get_settings(profile)?),
+            (None, Some(profile)) => Err(format!("profile '{}' given, but no configuration file found", profile)),
+            (None, None) => Ok(())
+        }
+    }
+
+    /// Adds the given settings as options, unless they have been given on
+    /// the command-line.
+    /// 
+    /// Settings for options that the command doesn't have are ignored, as
+    /// they may be for other commands. A flag is set by `true`.
+    pub fn apply_settings(&mut self, settings: &[ConfigSetting]) -> Result<(), String> {
+        let command = match self.command {
+            Some(command) => command,
+            None => return Ok(())
+        };
+
+        for (name, value) in settings {
+            if GLOBAL_OPTIONS.iter().any(|option| option.long == name) {
+                return Err(format!("option '{}' cannot be set in a configuration file", name));
+            }
+
+            let option = match command.find_long(name) {
+                Some(option) if !self.has_option(name) => option,
+                _ => continue
+            };
+
+            let value = match (option.value, value) {
+                (OptionValue::None | OptionValue::Optional, ConfigValue::Boolean(is_set)) => {
+                    if !is_set {
+                        continue;
+                    }
+                    None
+                },
+                (OptionValue::None, _) => return Err(format!("setting '{}' should be 'true' or 'false'", name)),
+                (OptionValue::Required, ConfigValue::Boolean(_)) => return Err(format!("setting '{}' needs a value", name)),
+                (_, value) => Some(value.to_string())
+            };
+
+            self.options.push((String::from(option.long), value));
+        }
+
+        Ok(())
+    }
+
+    /// Indicates if the command was named in the arguments, rather than
+    /// being the default.
+    fn is_command_named(&self, args: &[String]) -> bool {
+        matches!((&self.command, args.first()), (Some(command), Some(name)) if command.name == name)
+    }
+
+    /// Gives the name of the program.
+    pub fn get_program_name(&self) -> &str {
+        &self.program_name
+    }
+
+    /// Gives the name of the command.
+    pub fn get_command(&self) -> &str {
+        self.command.map(|command| command.name).unwrap_or_default()
     }
 
     pub fn len(&self) -> usize {
         self.args.len()
     }
 
+    /// Indicates if the option with the given long name was given.
     pub fn has_option(&self, option: &str) -> bool {
-        self.options.contains(&String::from(option))
+        self.options.iter().any(|(name, _)| name == option)
+    }
+
+    /// Gives the value of the option with the given long name.
+    /// 
+    /// If the option is given more than once, the last value is given.
+    pub fn get_option_value(&self, option: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(name, _)| name == option)
+            .and_then(|(_, value)| value.as_deref())
     }
 }
 
@@ -28,9 +28,11 @@ use std::collections::hash_map::Entry;
 use std::marker::PhantomData;
 use std::{collections::HashMap};
 use std::fs::{read, File};
-use std::io::{BufRead, BufReader, Read};
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
 
 use crate::env::{exit_with_error, OptionUnwrapDisplay, OptionUnwrapExit};
+use crate::grid::{Grid, GridCell, SizedGrid};
+use crate::life::{LifeGridType, LifeState};
 
 
 pub type BufferType = u8;
@@ -128,7 +130,7 @@ impl<'a> FileParser<'a> {
     }
 
     /// Gives an iterator over the parsed contents of the file.
-    pub fn iter(&mut self) -> FileIterator {
+    pub fn iter(&mut self) -> FileIterator<'_> {
         FileIterator {
             reader: self.buffer.reader(),
             path: self.path.clone(),
@@ -143,8 +145,23 @@ impl<'a> FileParser<'a> {
         }
     }
 
+    /// Parses the whole of the file, giving the symbols it defines (sorted
+    /// by name) rather than its cells - e.g. for a library of symbols.
+    pub fn parse_symbols(&mut self) -> Vec<(String, Vec<CellCoords>)> {
+        let mut iter = self.iter();
+        for _ in iter.by_ref() {}
+
+        let mut symbols: Vec<(String, Vec<CellCoords>)> = iter.state.symbols
+            .into_values()
+            .map(|symbol| (symbol.name, symbol.cells))
+            .collect();
+        symbols.sort_by(|a, b| a.0.cmp(&b.0));
+
+        symbols
+    }
+
     /// Gives a reference to the internal buffer.
-    pub fn get_buffer(&'a self) -> &ReadBuffer<'a> {
+    pub fn get_buffer(&'a self) -> &'a ReadBuffer<'a> {
         &self.buffer
     }
 
@@ -405,4 +422,321 @@ impl SymbolDefinition {
     fn iter(&self) -> impl Iterator + '_ {
         self.cells.iter()
     }
-}
\ No newline at end of file
+}
+
+//---------------------------------------------------------------------------//
+
+
+/// A cell parsed from an RLE file - its coordinates and its state number.
+pub type RleCell = (usize, usize, u8);
+
+/// The pattern given by an RLE file.
+#[derive(Debug)]
+pub struct RlePattern {
+    width: usize,
+    height: usize,
+    rule: Option<String>,
+    cells: Vec<RleCell>
+}
+
+impl RlePattern {
+    /// Width of the pattern, as given by the header's `x` value.
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    /// Height of the pattern, as given by the header's `y` value.
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    /// Gives the header's rulestring, if any.
+    pub fn get_rule(&self) -> Option<String> {
+        self.rule.clone()
+    }
+
+    /// Gives all of the pattern's non-dead cells.
+    pub fn get_cells(&self) -> &[RleCell] {
+        &self.cells
+    }
+}
+
+/// Parses the contents of an RLE file.
+/// 
+/// Both two-state RLE (`b` for dead & `o` for live) and multi-state RLE
+/// (`.` for dead, `A` to `X` for states 1 to 24, and a prefix of `p` to
+/// `y` for higher states) are supported.
+pub struct RleParser<'a> {
+    buffer: ReadBuffer<'a>,
+    path: Option<String>,
+    line_number: u32,
+    allow_fatal: bool       // <--- See 'FileParser.allow_fatal'.
+}
+
+impl<'a> RleParser<'a> {
+    /// Initialise the parser from the contents of a string.
+    pub fn from_string(contents: &str) -> Self {
+        Self::init(ReadBuffer::from_string(contents), None)
+    }
+
+    /// Initialise the parser from the contents of a file.
+    pub fn from_path(path: &str) -> std::io::Result<Self> {
+        Ok(Self::init(ReadBuffer::from_path(path)?, Some(String::from(path))))
+    }
+
+    /// Initialise the parser with a pre-initialised buffer.
+    pub fn init(buffer: ReadBuffer<'a>, path: Option<String>) -> Self {
+        Self {
+            buffer,
+            path,
+            line_number: 0,
+            allow_fatal: true
+        }
+    }
+
+    /// Gives the path, if any.
+    pub fn get_path(&self) -> Option<String> {
+        self.path.clone()
+    }
+
+    // Sets a flag to cause a panic instead of a 'clean' exit
+    // when 'RleParser.fatal_error()' is called.
+    pub fn set_test(&mut self) {
+        self.allow_fatal = false;
+    }
+
+    fn fatal_error(&self, message: String) {
+        let message = format!("error: {}, at line {} of file '{}'", message, self.line_number, self.path.unwrap_display_or("*unknown*"));
+        
+        if self.allow_fatal {
+            exit_with_error(message);
+        }
+        else {
+            panic!("{}", message);
+        }
+    }
+
+    /// Parses the whole of the file.
+    pub fn parse(&mut self) -> RlePattern {
+        let mut pattern = RlePattern {
+            width: 0,
+            height: 0,
+            rule: None,
+            cells: Vec::new()
+        };
+
+        let text = String::from_utf8_lossy(self.buffer.as_slice()).into_owned();
+        let mut has_header = false;
+        let mut position = (0, 0);
+        let mut count = String::new();
+        let mut prefix: Option<char> = None;
+
+        self.line_number = 0;
+        for line in text.lines() {
+            self.line_number += 1;
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if !has_header {
+                self.parse_header(line, &mut pattern);
+                has_header = true;
+                continue;
+            }
+
+            for c in line.chars() {
+                if c.is_ascii_digit() {
+                    count.push(c);
+                    continue;
+                }
+
+                let run = if count.is_empty() { 1 } else { count.parse::<usize>().unwrap_or(1) };
+                count.clear();
+
+                match (prefix, c) {
+                    (None, 'p'..='y') => {
+                        prefix = Some(c);
+                        // Keep the run count for the state that follows...
+                        count = run.to_string();
+                        continue;
+                    },
+                    (Some(p), 'A'..='X') => {
+                        let state = 24 * ((p as u8 - b'p') as usize + 1) + (c as u8 - b'A') as usize + 1;
+                        if state > u8::MAX as usize {
+                            self.fatal_error(format!("state '{}{}' is out of range", p, c));
+                        }
+                        self.push_run(&mut pattern, &mut position, run, state as u8);
+                    },
+                    (Some(p), _) => self.fatal_error(format!("bad multi-state prefix '{}{}'", p, c)),
+                    (None, 'b' | '.') => position.0 += run,
+                    (None, 'o') => self.push_run(&mut pattern, &mut position, run, 1),
+                    (None, 'A'..='X') => self.push_run(&mut pattern, &mut position, run, (c as u8 - b'A') + 1),
+                    (None, '$') => position = (0, position.1 + run),
+                    (None, '!') => return pattern,
+                    (None, _) if c.is_whitespace() => {},
+                    (None, _) => self.fatal_error(format!("unrecognised character '{}'", c))
+                }
+
+                prefix = None;
+            }
+        }
+
+        if !has_header {
+            self.fatal_error("missing 'x = ..., y = ...' header".to_string());
+        }
+
+        pattern
+    }
+
+    fn parse_header(&self, line: &str, pattern: &mut RlePattern) {
+        let mut width = None;
+        let mut height = None;
+
+        // The rule is the last item and may itself contain commas (e.g. a
+        // Larger than Life rule), so it runs to the end of the line...
+        let (sizes, rule) = match line.find("rule") {
+            Some(index) => (&line[..index], line[index..].split_once('=').map(|(_, rule)| rule.trim())),
+            None => (line, None)
+        };
+        pattern.rule = rule.map(String::from);
+
+        for item in sizes.split(',') {
+            if let Some((key, value)) = item.split_once('=') {
+                let value = value.trim();
+                match key.trim() {
+                    "x" => width = value.parse::<usize>().ok(),
+                    "y" => height = value.parse::<usize>().ok(),
+                    _ => {}
+                }
+            }
+        }
+
+        match (width, height) {
+            (Some(width), Some(height)) => {
+                pattern.width = width;
+                pattern.height = height;
+            },
+            _ => self.fatal_error(format!("bad header '{}'", line))
+        }
+    }
+
+    fn push_run(&self, pattern: &mut RlePattern, position: &mut CellCoords, run: usize, state: u8) {
+        for _ in 0..run {
+            if (position.0 >= pattern.width) || (position.1 >= pattern.height) {
+                self.fatal_error(format!("cell location ({},{}) out of bounds ({},{})", position.0, position.1, pattern.width, pattern.height));
+            }
+
+            pattern.cells.push((position.0, position.1, state));
+            position.0 += 1;
+        }
+    }
+}
+
+
+//---------------------------------------------------------------------------//
+
+
+/// Writes a grid as a 'life' file - its width & height followed by the
+/// coordinates of its 'live' cells.
+/// 
+/// A 'life' file has only two states, so any other non-dead state is an
+/// error.
+pub fn write_life<W: Write>(grid: &LifeGridType, w: &mut W) -> std::io::Result<()> {
+    if grid.iter().any(|cell| cell.get().get() > 1) {
+        return Err(std::io::Error::new(ErrorKind::InvalidData, "a 'life' file can only have 'dead' and 'live' cells"));
+    }
+
+    writeln!(w, "# The size of the grid (width,height)...")?;
+    writeln!(w, "{},{}", grid.get_width(), grid.get_height())?;
+    writeln!(w)?;
+
+    for y in 0..grid.get_height() {
+        for x in 0..grid.get_width() {
+            if grid.get(x, y) == &LifeState::LIVE {
+                writeln!(w, "{},{}", x, y)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes a grid as an RLE file, with the given rule in its header.
+/// 
+/// See `encode_rle()`. Lines are at most 70 characters.
+pub fn write_rle<W: Write>(grid: &LifeGridType, rule: Option<&str>, w: &mut W) -> std::io::Result<()> {
+    const LINE_LENGTH: usize = 70;
+
+    write!(w, "x = {}, y = {}", grid.get_width(), grid.get_height())?;
+    match rule {
+        Some(rule) => writeln!(w, ", rule = {}", rule)?,
+        None => writeln!(w)?
+    }
+
+    let mut line = String::new();
+    for item in get_rle_items(grid) {
+        if line.len() + item.len() > LINE_LENGTH {
+            writeln!(w, "{}", line)?;
+            line.clear();
+        }
+        line += &item;
+    }
+
+    writeln!(w, "{}", line)
+}
+
+/// Gives the cells of a grid as RLE, without a header or line breaks.
+/// 
+/// Two-state RLE is given if every cell is 'dead' or 'live', otherwise
+/// multi-state RLE (see `RleParser`).
+pub fn encode_rle(grid: &LifeGridType) -> String {
+    get_rle_items(grid).concat()
+}
+
+/// Gives the runs of equal states of a grid, and the ends of rows, as RLE.
+fn get_rle_items(grid: &LifeGridType) -> Vec<String> {
+    let is_multi_state = grid.iter().any(|cell| cell.get().get() > 1);
+    let symbol = |state: u8| match (is_multi_state, state) {
+        (false, 0) => String::from("b"),
+        (false, _) => String::from("o"),
+        (true, 0) => String::from("."),
+        (true, 1..=24) => String::from((b'A' + state - 1) as char),
+        (true, _) => {
+            let (prefix, state) = ((state - 1) / 24 - 1, (state - 1) % 24);
+            format!("{}{}", (b'p' + prefix) as char, (b'A' + state) as char)
+        }
+    };
+
+    // Gather runs of equal states, leaving out 'dead' cells at the end of
+    // each row and merging empty rows...
+    let mut items: Vec<String> = Vec::new();
+    let mut rows_ended = 0;
+    for y in 0..grid.get_height() {
+        if y > 0 {
+            rows_ended += 1;
+        }
+
+        let row: Vec<u8> = (0..grid.get_width()).map(|x| grid.get(x, y).get()).collect();
+        let length = row.iter().rposition(|state| *state != 0).map(|index| index + 1).unwrap_or(0);
+        if length == 0 {
+            continue;
+        }
+
+        if rows_ended > 0 {
+            items.push(if rows_ended > 1 { format!("{}$", rows_ended) } else { String::from("$") });
+        }
+        rows_ended = 0;
+
+        let mut x = 0;
+        while x < length {
+            let run = row[x..length].iter().take_while(|state| **state == row[x]).count();
+            items.push(if run > 1 { format!("{}{}", run, symbol(row[x])) } else { symbol(row[x]) });
+            x += run;
+        }
+    }
+    items.push(String::from("!"));
+
+    items
+}
@@ -0,0 +1,351 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use crate::grid::{BoundingBox, Grid, SizedGrid};
+use crate::image::{Colour, Image, Palette};
+use crate::life::{LifeGrid, LifeGridType};
+
+
+/// Records the generations of a run, to be written as an animated GIF.
+/// 
+/// Every generation, or every k-th, is recorded as a frame. The frames are
+/// all the size of the region covering every frame - either the whole of
+/// each grid or, if cropped, just the pattern in each grid.
+pub struct GifRecorder {
+    frames: Vec<(LifeGridType, (isize, isize))>,
+    every: usize,
+    delay: u16,
+    cell_size: usize,
+    palette: Option<Palette>,
+    grid_lines: Option<Colour>,
+    is_cropped: bool
+}
+
+impl GifRecorder {
+    /// Initialises a recorder of every generation, with a delay of 100ms
+    /// between frames and a pixel for each cell.
+    pub fn new() -> Self {
+        Self {
+            frames: Vec::new(),
+            every: 1,
+            delay: 10,
+            cell_size: 1,
+            palette: None,
+            grid_lines: None,
+            is_cropped: false
+        }
+    }
+
+    /// Sets the recorder to record only every k-th generation.
+    pub fn set_every(&mut self, every: usize) {
+        self.every = every.max(1);
+    }
+
+    /// Sets the delay between frames, in milliseconds - which is rounded
+    /// to hundredths of a second.
+    pub fn set_delay(&mut self, delay: u32) {
+        self.delay = ((delay + 5) / 10).min(u16::MAX as u32) as u16;
+    }
+
+    /// Sets the size of each cell, in pixels.
+    pub fn set_cell_size(&mut self, cell_size: usize) {
+        self.cell_size = cell_size;
+    }
+
+    /// Sets the colours of the states of cells - by default, those of
+    /// `Palette::default_states()`.
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = Some(palette);
+    }
+
+    /// Sets the colour of lines between the cells, if any.
+    pub fn set_grid_lines(&mut self, grid_lines: Option<Colour>) {
+        self.grid_lines = grid_lines;
+    }
+
+    /// Sets the recorder to crop the frames to the largest extent of the
+    /// pattern, rather than the whole of each grid.
+    pub fn set_cropped(&mut self, is_cropped: bool) {
+        self.is_cropped = is_cropped;
+    }
+
+    /// Records the grid of the given generation, if it's one to record.
+    /// 
+    /// The origin is the position of the grid's top-left cell (see
+    /// `Universe`).
+    pub fn add(&mut self, generation: usize, grid: &LifeGridType, origin: (isize, isize)) {
+        if !generation.is_multiple_of(self.every) {
+            return;
+        }
+
+        // Keep just the pattern, when cropping...
+        let frame = match (self.is_cropped, grid.get_bounding_box()) {
+            (true, Some(bounds)) => (grid.crop(&bounds, 0), (origin.0 + bounds.x, origin.1 + bounds.y)),
+            (true, None) => (LifeGridType::init_life(0, 0), origin),
+            (false, _) => (grid.clone(), origin)
+        };
+
+        self.frames.push(frame);
+    }
+
+    /// Gives the number of frames recorded.
+    pub fn get_frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Gives the region covering every frame, or `None` if every frame is
+    /// empty.
+    pub fn get_bounds(&self) -> Option<BoundingBox> {
+        self.frames.iter()
+            .filter(|(grid, _)| (grid.get_width() > 0) && (grid.get_height() > 0))
+            .map(|(grid, (x, y))| BoundingBox::new(*x, *y, grid.get_width(), grid.get_height()))
+            .reduce(|bounds, other| bounds.union(&other))
+    }
+
+    /// Writes the frames as an animated GIF that loops forever.
+    pub fn write(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        let bounds = self.get_bounds().unwrap_or(BoundingBox::new(0, 0, 1, 1));
+        let palette = self.palette.clone().unwrap_or_else(|| {
+            let max_state = self.frames.iter().flat_map(|(grid, _)| grid.iter().map(|cell| cell.get().get())).max().unwrap_or(0);
+            Palette::default_states(max_state.max(1) + 1)
+        });
+
+        let mut encoder: Option<GifEncoder> = None;
+        for (grid, (x, y)) in &self.frames {
+            // Place the frame within the region covering every frame...
+            let mut frame = LifeGridType::init_life(bounds.width, bounds.height);
+            let (dx, dy) = ((x - bounds.x) as usize, (y - bounds.y) as usize);
+            for cell in grid {
+                frame.set(cell.get_x() + dx, cell.get_y() + dy, *cell.get());
+            }
+
+            let image = Image::from_grid(&frame, &palette, self.cell_size, self.grid_lines);
+            if encoder.is_none() {
+                encoder = Some(GifEncoder::new(w, image.get_width(), image.get_height(), image.get_palette().get_colours())?);
+            }
+
+            if let Some(encoder) = encoder.as_mut() {
+                encoder.write_frame(w, image.get_indices(), self.delay)?;
+            }
+        }
+
+        match encoder {
+            Some(encoder) => encoder.finish(w),
+            None => Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "no frames were recorded"))
+        }
+    }
+
+    /// Writes the frames as an animated GIF to a file.
+    pub fn write_path(&self, path: &str) -> Result<(), String> {
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path).map_err(|_| format!("cannot create file '{}'", path))?);
+        self.write(&mut file).and_then(|_| file.flush()).map_err(|error| format!("cannot write file '{}': {}", path, error))
+    }
+}
+
+impl Default for GifRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+//---------------------------------------------------------------------------//
+
+
+/// Writes the blocks of an animated GIF (version `89a`), with a global
+/// colour table and full-size frames.
+pub struct GifEncoder {
+    width: u16,
+    height: u16,
+    min_code_size: u8
+}
+
+impl GifEncoder {
+    /// Writes the header, colour table and looping extension of a GIF of
+    /// the given size - at most 65,535 pixels each way - and up to 256
+    /// colours.
+    pub fn new(w: &mut dyn Write, width: usize, height: usize, colours: &[Colour]) -> std::io::Result<Self> {
+        if (width > u16::MAX as usize) || (height > u16::MAX as usize) {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("a GIF cannot be {}x{} pixels", width, height)));
+        }
+
+        // The colour table has 2^(n+1) entries, for n from 0 to 7...
+        let bits = (1..=8).find(|bits| (1 << bits) >= colours.len()).unwrap_or(8);
+        let mut table: Vec<u8> = colours.iter().take(256).flat_map(|(red, green, blue)| [*red, *green, *blue]).collect();
+        table.resize(3 << bits, 0);
+
+        w.write_all(b"GIF89a")?;
+        w.write_all(&(width as u16).to_le_bytes())?;
+        w.write_all(&(height as u16).to_le_bytes())?;
+        w.write_all(&[0xF0 | (bits as u8 - 1), 0, 0])?;
+        w.write_all(&table)?;
+
+        // The 'NETSCAPE2.0' application extension, to loop forever...
+        w.write_all(&[0x21, 0xFF, 0x0B])?;
+        w.write_all(b"NETSCAPE2.0")?;
+        w.write_all(&[0x03, 0x01, 0x00, 0x00, 0x00])?;
+
+        Ok(Self {
+            width: width as u16,
+            height: height as u16,
+            min_code_size: (bits as u8).max(2)
+        })
+    }
+
+    /// Writes a frame of the full size of the GIF, shown for the given
+    /// delay in hundredths of a second.
+    pub fn write_frame(&mut self, w: &mut dyn Write, indices: &[u8], delay: u16) -> std::io::Result<()> {
+        // The graphic control extension, with the delay and the frame left
+        // in place for the next one to cover...
+        w.write_all(&[0x21, 0xF9, 0x04, 0x04])?;
+        w.write_all(&delay.to_le_bytes())?;
+        w.write_all(&[0x00, 0x00])?;
+
+        // The image descriptor, at the top-left with no local colour table...
+        w.write_all(&[0x2C, 0x00, 0x00, 0x00, 0x00])?;
+        w.write_all(&self.width.to_le_bytes())?;
+        w.write_all(&self.height.to_le_bytes())?;
+        w.write_all(&[0x00])?;
+
+        // The compressed pixels, in sub-blocks of up to 255 bytes...
+        w.write_all(&[self.min_code_size])?;
+        for block in encode_lzw(indices, self.min_code_size).chunks(255) {
+            w.write_all(&[block.len() as u8])?;
+            w.write_all(block)?;
+        }
+
+        w.write_all(&[0x00])
+    }
+
+    /// Writes the trailer that ends the GIF.
+    pub fn finish(self, w: &mut dyn Write) -> std::io::Result<()> {
+        w.write_all(&[0x3B])
+    }
+}
+
+/// The greatest width of an LZW code, in bits.
+const MAX_CODE_WIDTH: u8 = 12;
+
+/// Compresses the palette indices of a GIF image with the variable-width
+/// LZW compression of GIF.
+/// 
+/// Every index must be below 2^`min_code_size`. The codes start one bit
+/// wider than that and grow to 12 bits, after which the table of codes is
+/// cleared and started again.
+pub fn encode_lzw(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    let mut writer = BitWriter::new();
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut width = min_code_size + 1;
+
+    writer.write(clear, width);
+    let mut prefix: Option<u16> = None;
+    for index in indices {
+        let code = match prefix {
+            Some(code) => code,
+            None => {
+                prefix = Some(*index as u16);
+                continue;
+            }
+        };
+
+        if let Some(extended) = table.get(&(code, *index)) {
+            prefix = Some(*extended);
+            continue;
+        }
+
+        writer.write(code, width);
+        table.insert((code, *index), next);
+        next += 1;
+
+        // The decoder adds each code a step later, so widens once the code
+        // after it has been added...
+        if next == (1 << MAX_CODE_WIDTH) {
+            writer.write(clear, width);
+            table.clear();
+            next = end + 1;
+            width = min_code_size + 1;
+        }
+        else if (next > (1 << width)) && (width < MAX_CODE_WIDTH) {
+            width += 1;
+        }
+
+        prefix = Some(*index as u16);
+    }
+
+    if let Some(code) = prefix {
+        writer.write(code, width);
+
+        // ...and adds a code for the last one too...
+        if (next == (1 << width)) && (width < MAX_CODE_WIDTH) {
+            width += 1;
+        }
+    }
+
+    writer.write(end, width);
+    writer.finish()
+}
+
+/// Packs codes into bytes, least-significant bit first.
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            buffer: 0,
+            bits: 0
+        }
+    }
+
+    fn write(&mut self, code: u16, width: u8) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += width;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+
+        self.bytes
+    }
+}
@@ -25,9 +25,10 @@
 ---------------------------------------------------------------------------- */
 
 use std::collections::HashMap;
-use std::collections::hash_map::{Entry, Iter};
+use std::collections::hash_map::{DefaultHasher, Entry, Iter};
 use std::default;
 use std::fmt::{Debug, Display};
+use std::hash::{Hash, Hasher};
 use std::io::Write;
 use std::ops::{Index, IndexMut};
 
@@ -68,6 +69,88 @@ pub trait SizedGrid : Grid {
 
 type GridIndexTuple = (usize, usize);
 
+/// One of the 8 rotations & reflections of a square (the dihedral group D4).
+/// 
+/// Rotations are clockwise. A transpose reflects in the leading diagonal
+/// (top-left to bottom-right) and an anti-transpose in the other diagonal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+    Transpose,
+    AntiTranspose
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::FlipHorizontal,
+        Symmetry::FlipVertical,
+        Symmetry::Transpose,
+        Symmetry::AntiTranspose
+    ];
+
+    /// Indicates if the symmetry is a reflection, rather than a rotation.
+    pub fn is_reflection(&self) -> bool {
+        matches!(self, Symmetry::FlipHorizontal | Symmetry::FlipVertical | Symmetry::Transpose | Symmetry::AntiTranspose)
+    }
+}
+
+impl Display for Symmetry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Symmetry::Identity => "identity",
+            Symmetry::Rotate90 => "rotate 90",
+            Symmetry::Rotate180 => "rotate 180",
+            Symmetry::Rotate270 => "rotate 270",
+            Symmetry::FlipHorizontal => "flip horizontal",
+            Symmetry::FlipVertical => "flip vertical",
+            Symmetry::Transpose => "transpose",
+            Symmetry::AntiTranspose => "anti-transpose"
+        })
+    }
+}
+
+/// A rectangular region of a grid.
+/// 
+/// The co-ordinates of the top-left corner are signed so that regions of an
+/// unbounded grid may be given.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BoundingBox {
+    pub x: isize,
+    pub y: isize,
+    pub width: usize,
+    pub height: usize
+}
+
+impl BoundingBox {
+    pub fn new(x: isize, y: isize, width: usize, height: usize) -> Self {
+        Self { x, y, width, height }
+    }
+
+    /// Gives the bounding box moved by the given offset.
+    pub fn translate(&self, dx: isize, dy: isize) -> Self {
+        Self::new(self.x + dx, self.y + dy, self.width, self.height)
+    }
+
+    /// Gives the smallest bounding box containing both bounding boxes.
+    pub fn union(&self, other: &BoundingBox) -> Self {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        let right = (self.x + self.width as isize).max(other.x + other.width as isize);
+        let bottom = (self.y + self.height as isize).max(other.y + other.height as isize);
+
+        Self::new(x, y, (right - x) as usize, (bottom - y) as usize)
+    }
+}
+
 
 //---------------------------------------------------------------------------//
 
@@ -76,6 +159,7 @@ type GridIndexTuple = (usize, usize);
 /// 
 /// The contents of the Grid are stored in a `Vector` of `Vector`s. Therefore,
 /// space is allocated, on initialisation, for every item in the grid.
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct SimpleGrid<T> {
     rows: Vec<Vec<T>>,
     width: usize,
@@ -178,6 +262,46 @@ impl<T> Grid for SimpleGrid<T> {
     }
 }
 
+impl<T> SimpleGrid<T> where T: Clone {
+    /// Gives a copy of the `Grid` rotated and/or reflected by the given
+    /// symmetry.
+    /// 
+    /// A quarter-turn or (anti-)transpose swaps the width and height.
+    pub fn transform(&self, symmetry: Symmetry) -> Self {
+        let (width, height) = match symmetry {
+            Symmetry::Rotate90 | Symmetry::Rotate270 | Symmetry::Transpose | Symmetry::AntiTranspose => (self.height, self.width),
+            _ => (self.width, self.height)
+        };
+
+        let (last_x, last_y) = (self.width.saturating_sub(1), self.height.saturating_sub(1));
+        let mut rows: Vec<Vec<T>> = Vec::with_capacity(height);
+        for y in 0..height {
+            let mut row: Vec<T> = Vec::with_capacity(width);
+            for x in 0..width {
+                // Find the cell of this grid that moves to (x, y)...
+                let (from_x, from_y) = match symmetry {
+                    Symmetry::Identity => (x, y),
+                    Symmetry::Rotate90 => (y, last_y - x),
+                    Symmetry::Rotate180 => (last_x - x, last_y - y),
+                    Symmetry::Rotate270 => (last_x - y, x),
+                    Symmetry::FlipHorizontal => (last_x - x, y),
+                    Symmetry::FlipVertical => (x, last_y - y),
+                    Symmetry::Transpose => (y, x),
+                    Symmetry::AntiTranspose => (last_x - y, last_y - x)
+                };
+                row.push(self.rows[from_y][from_x].clone());
+            }
+            rows.push(row);
+        }
+
+        Self {
+            rows,
+            width,
+            height
+        }
+    }
+}
+
 impl<T> SizedGrid for SimpleGrid<T> {
     fn get_width(&self) -> usize {
         self.width
@@ -193,8 +317,8 @@ impl<T> SimpleGrid<T> where T: Display {
     /// output stream.
     /// 
     /// To write to standard-out, `grid.write(&mut stdout())`.
-    pub fn write(&self, w: &mut dyn Write) {
-        w.write_all(format!("/{}\\\n", "-".repeat(self.width)).as_bytes());
+    pub fn write(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        w.write_all(format!("/{}\\\n", "-".repeat(self.width)).as_bytes())?;
         
         let mut line = String::with_capacity(self.width);
         for row in &self.rows {
@@ -202,11 +326,11 @@ impl<T> SimpleGrid<T> where T: Display {
                 line += format!("{}", column).as_str();
             }
             
-            w.write_all(format!("|{}|\n", line).as_bytes());
+            w.write_all(format!("|{}|\n", line).as_bytes())?;
             line.clear();
         }
 
-        w.write_all(format!("\\{}/\n", "-".repeat(self.width)).as_bytes());
+        w.write_all(format!("\\{}/\n", "-".repeat(self.width)).as_bytes())
     }
 }
 
@@ -396,7 +520,7 @@ impl<T> SparseGrid<T> where T: Default {
     /// 
     /// For all cells that have not explicitly had a value set, the `Iterator`
     /// gives the `Default` value.
-    pub fn iter_all(&self, width: usize, height: usize) -> BoundedGridIterator<T> {
+    pub fn iter_all(&self, width: usize, height: usize) -> BoundedGridIterator<'_, T> {
         BoundedGridIterator {
             grid: self,
             width,
@@ -453,6 +577,33 @@ impl<T> Grid for SparseGrid<T> where T: Default {
     }
 }
 
+impl<T> PartialEq for SparseGrid<T> where T: Default + PartialEq {
+    /// Grids are equal if every cell has the same value - a cell explicitly
+    /// set to the default value is the same as one that isn't set.
+    fn eq(&self, other: &Self) -> bool {
+        self.items.iter().all(|((x, y), item)| item == other.get(*x, *y))
+            && other.items.iter().all(|((x, y), item)| item == self.get(*x, *y))
+    }
+}
+
+impl<T> Eq for SparseGrid<T> where T: Default + Eq {}
+
+impl<T> Hash for SparseGrid<T> where T: Default + PartialEq + Hash {
+    /// Hashes the cells that don't have the default value, independently of
+    /// the order in which they are stored.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut sum: u64 = 0;
+        for (position, item) in self.items.iter().filter(|(_, item)| **item != self.default) {
+            let mut hasher = DefaultHasher::new();
+            position.hash(&mut hasher);
+            item.hash(&mut hasher);
+            sum = sum.wrapping_add(hasher.finish());
+        }
+
+        state.write_u64(sum);
+    }
+}
+
 impl<T> Default for SparseGrid<T> where T: Default {
     fn default() -> Self {
         Self::new()
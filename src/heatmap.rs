@@ -0,0 +1,107 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use crate::grid::{Grid, SimpleGrid, SizedGrid};
+use crate::life::{LifeGridType, LifeState};
+
+
+/// The activity of every cell of a grid across a run.
+/// 
+/// For each cell, counts the generations in which it was 'live' and the
+/// number of times its state changed.
+pub struct HeatMap {
+    live: SimpleGrid<u32>,
+    changes: SimpleGrid<u32>,
+    previous: Option<LifeGridType>,
+    generations: u32
+}
+
+impl HeatMap {
+    /// Characters used to shade the cells, from least to most active.
+    pub const SHADES: &'static str = " .:-=+*#%@";
+
+    /// Initialises a heat-map for a grid of the given size.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            live: SimpleGrid::new(width, height),
+            changes: SimpleGrid::new(width, height),
+            previous: None,
+            generations: 0
+        }
+    }
+
+    /// Adds the next generation of the run.
+    /// 
+    /// The grid must be the same size as the heat-map.
+    pub fn add(&mut self, grid: &LifeGridType) {
+        for cell in grid {
+            let (x, y) = (cell.get_x(), cell.get_y());
+            if cell.get() == &LifeState::LIVE {
+                self.live[(x, y)] += 1;
+            }
+
+            if let Some(previous) = &self.previous {
+                if previous.get(x, y) != cell.get() {
+                    self.changes[(x, y)] += 1;
+                }
+            }
+        }
+
+        self.previous = Some(grid.clone());
+        self.generations += 1;
+    }
+
+    /// Gives the number of generations in which each cell was 'live'.
+    pub fn get_live(&self) -> &SimpleGrid<u32> {
+        &self.live
+    }
+
+    /// Gives the number of times each cell's state changed.
+    pub fn get_changes(&self) -> &SimpleGrid<u32> {
+        &self.changes
+    }
+
+    /// Gives the number of generations added.
+    pub fn get_generations(&self) -> u32 {
+        self.generations
+    }
+
+    /// Gives a grid of characters shading each of the given counts, relative
+    /// to the greatest count - a space for 0, through to `@` for the
+    /// greatest.
+    pub fn shade(counts: &SimpleGrid<u32>) -> SimpleGrid<char> {
+        let max = counts.iter().map(|cell| *cell.get()).max().unwrap_or(0);
+        let levels = Self::SHADES.len() as u32 - 1;
+
+        let mut shaded = SimpleGrid::init(counts.get_width(), counts.get_height(), ' ');
+        for cell in counts {
+            let level = if max == 0 { 0 } else { (cell.get() * levels).div_ceil(max) };
+            shaded.set(cell.get_x(), cell.get_y(), Self::SHADES.as_bytes()[level as usize] as char);
+        }
+
+        shaded
+    }
+}
@@ -0,0 +1,306 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::fmt::Display;
+
+use crate::grid::{Grid, SimpleGrid, SizedGrid};
+use crate::life::{LifeGridType, LifeState};
+use crate::rule::{next_generations_state, Rule};
+
+
+/// The number of possible configurations of the 8 neighbours of a cell.
+const CONFIGURATIONS: usize = 256;
+
+/// The offsets of the 8 neighbours, in the order of their bits in a
+/// configuration index - bit 0 is the top-left (NW) neighbour and bit 7 is
+/// the bottom-right (SE) neighbour.
+const NEIGHBOUR_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1), (0, -1), (1, -1),
+    (-1, 0),           (1, 0),
+    (-1, 1),  (0, 1),  (1, 1)
+];
+
+/// The Hensel letters for each count of 'live' neighbours from 0 to 4.
+/// 
+/// Counts 5 to 8 use the letters of 8 minus the count, for the complement
+/// of that configuration.
+const LETTERS: [&str; 5] = ["", "ce", "ceaikn", "ceaiknjqry", "ceaiknjqrytwz"];
+
+/// A representative configuration index for each of `LETTERS` - all other
+/// configurations with a letter are reflections or rotations of these.
+const REPRESENTATIVES: [&[u8]; 5] = [
+    &[0],
+    &[1, 2],
+    &[5, 10, 3, 24, 17, 36],
+    &[37, 26, 11, 7, 50, 13, 14, 38, 25, 49],
+    &[165, 90, 15, 29, 51, 39, 58, 54, 27, 53, 57, 46, 60]
+];
+
+/// An isotropic non-totalistic rule in Hensel notation, e.g. `B2-a/S12`.
+/// 
+/// Rather than counting neighbours, each cell's 8 neighbours give a
+/// configuration index (see `config_index()`). Each digit of the rule may be
+/// followed by letters to select only some of the configurations with that
+/// many 'live' neighbours, or by `-` and letters to select all but those
+/// configurations. A final `/C` part gives a Generations rule.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HenselRule {
+    birth: Vec<bool>,
+    survival: Vec<bool>,
+    states: u8
+}
+
+impl HenselRule {
+    /// Parses a rulestring in Hensel notation.
+    pub fn parse(rulestring: &str) -> Result<Self, String> {
+        let classes = Self::classify();
+
+        let mut birth = None;
+        let mut survival = None;
+        let mut states = None;
+
+        for part in rulestring.trim().split('/').map(|part| part.trim()) {
+            let mut chars = part.chars();
+            let prefix = chars.next().map(|c| c.to_ascii_uppercase());
+            let text = chars.as_str().to_lowercase();
+            match prefix {
+                Some('B') if birth.is_none() => birth = Some(Self::parse_configs(&text, &classes, rulestring)?),
+                Some('S') if survival.is_none() => survival = Some(Self::parse_configs(&text, &classes, rulestring)?),
+                Some('C' | 'G') if states.is_none() => states = match text.parse::<u8>() {
+                    Ok(n) if n >= 2 => Some(n),
+                    _ => return Err(format!("rule '{}' has a bad number of states '{}'", rulestring, text))
+                },
+                _ => return Err(format!("rule '{}' has an unexpected part '{}'", rulestring, part))
+            }
+        }
+
+        match (birth, survival) {
+            (Some(birth), Some(survival)) => Ok(Self {
+                birth,
+                survival,
+                states: states.unwrap_or(2)
+            }),
+            _ => Err(format!("rule '{}' should have both a birth and a survival part", rulestring))
+        }
+    }
+
+    /// Indicates if the rulestring uses Hensel notation - i.e. if any of its
+    /// birth or survival parts has letters, before any custom neighbourhood
+    /// (whose hex digits aren't letters).
+    pub fn is_hensel(rulestring: &str) -> bool {
+        let rulestring = rulestring.split('@').next().unwrap_or_default();
+        rulestring.split('/')
+            .map(|part| part.trim())
+            .filter(|part| part.starts_with(['B', 'b', 'S', 's']))
+            .any(|part| part[1..].chars().any(|c| c == '-' || Self::is_letter(c.to_ascii_lowercase())))
+    }
+
+    fn is_letter(c: char) -> bool {
+        LETTERS[4].contains(c)
+    }
+
+    /// Parses the digits & letters of a birth or survival part into the set
+    /// of configurations that it selects.
+    fn parse_configs(text: &str, classes: &[(usize, char)], rulestring: &str) -> Result<Vec<bool>, String> {
+        let mut configs = vec![false; CONFIGURATIONS];
+        let mut chars = text.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            let count = match c.to_digit(10) {
+                Some(count) if count <= 8 => count as usize,
+                _ => return Err(format!("rule '{}' has an unexpected '{}'", rulestring, c))
+            };
+
+            let is_negated = chars.next_if_eq(&'-').is_some();
+            let mut letters = String::new();
+            while let Some(letter) = chars.next_if(|c| Self::is_letter(*c)) {
+                if !Self::get_letters(count).contains(letter) {
+                    return Err(format!("rule '{}' has a bad letter '{}' for count {}", rulestring, letter, count));
+                }
+                letters.push(letter);
+            }
+
+            if is_negated && letters.is_empty() {
+                return Err(format!("rule '{}' has a '-' without letters for count {}", rulestring, count));
+            }
+
+            for (index, (class_count, class_letter)) in classes.iter().enumerate() {
+                if *class_count == count && (letters.is_empty() || (letters.contains(*class_letter) != is_negated)) {
+                    configs[index] = true;
+                }
+            }
+        }
+
+        Ok(configs)
+    }
+
+    /// Gives the Hensel letters for the given count of 'live' neighbours.
+    fn get_letters(count: usize) -> &'static str {
+        LETTERS[count.min(8 - count)]
+    }
+
+    /// Gives the count of 'live' neighbours and the Hensel letter of every
+    /// configuration index.
+    /// 
+    /// Counts 0 & 8 have a single configuration and no letters - they are
+    /// given as a space.
+    pub fn classify() -> Vec<(usize, char)> {
+        let mut classes = vec![(0, ' '); CONFIGURATIONS];
+
+        for (count, representatives) in REPRESENTATIVES.iter().enumerate() {
+            let letters: Vec<char> = LETTERS[count].chars().collect();
+            for (i, representative) in representatives.iter().enumerate() {
+                let letter = letters.get(i).copied().unwrap_or(' ');
+                for index in Self::symmetries(*representative) {
+                    classes[index as usize] = (count, letter);
+
+                    // The complement of a count 4 configuration is another
+                    // count 4 configuration, with its own letter...
+                    if count < 4 {
+                        classes[!index as usize] = (8 - count, letter);
+                    }
+                }
+            }
+        }
+
+        classes
+    }
+
+    /// Gives all of the reflections and rotations of a configuration index.
+    fn symmetries(index: u8) -> Vec<u8> {
+        let mut result = Vec::with_capacity(8);
+        for transform in 0..8 {
+            let mut transformed = 0u8;
+            for (bit, (dx, dy)) in NEIGHBOUR_OFFSETS.iter().enumerate() {
+                if index & (1 << bit) != 0 {
+                    // Rotate by a quarter-turn as many times as needed, then
+                    // reflect for the second half of the transforms...
+                    let (mut x, mut y) = (*dx, *dy);
+                    for _ in 0..(transform % 4) {
+                        (x, y) = (-y, x);
+                    }
+                    if transform >= 4 {
+                        x = -x;
+                    }
+
+                    let target = NEIGHBOUR_OFFSETS.iter().position(|offset| *offset == (x, y)).unwrap();
+                    transformed |= 1 << target;
+                }
+            }
+            result.push(transformed);
+        }
+
+        result
+    }
+
+    /// Gives the configuration index of the cell at the given coordinates -
+    /// a bit for each 'live' neighbour, in the order of `NEIGHBOUR_OFFSETS`.
+    /// 
+    /// Any neighbours that are out of bounds count as 'dead'.
+    pub fn config_index(grid: &LifeGridType, x: usize, y: usize) -> i32 {
+        let (width, height) = (grid.get_width() as isize, grid.get_height() as isize);
+
+        let mut index = 0;
+        for (bit, (dx, dy)) in NEIGHBOUR_OFFSETS.iter().enumerate() {
+            let (nx, ny) = (x as isize + dx, y as isize + dy);
+            if (nx >= 0) && (nx < width) && (ny >= 0) && (ny < height)
+                && (grid.get(nx as usize, ny as usize) == &LifeState::LIVE) {
+                index |= 1 << bit;
+            }
+        }
+
+        index
+    }
+
+    /// Writes the configurations of a birth or survival part in Hensel
+    /// notation, using `-` where that gives fewer letters.
+    fn format_configs(configs: &[bool], classes: &[(usize, char)]) -> String {
+        let mut text = String::new();
+        for count in 0..=8 {
+            let all = Self::get_letters(count);
+            let letters: String = all.chars()
+                                    .filter(|letter| classes.iter().enumerate().any(|(index, class)| configs[index] && *class == (count, *letter)))
+                                    .collect();
+            let has_any = classes.iter().enumerate().any(|(index, (class_count, _))| configs[index] && *class_count == count);
+
+            if !has_any {
+                continue;
+            }
+
+            text += &count.to_string();
+            if letters.len() < all.len() {
+                if letters.len() * 2 <= all.len() {
+                    text += &letters;
+                }
+                else {
+                    text.push('-');
+                    text.extend(all.chars().filter(|letter| !letters.contains(*letter)));
+                }
+            }
+        }
+
+        text
+    }
+}
+
+impl Rule for HenselRule {
+    fn get_states(&self) -> u8 {
+        self.states
+    }
+
+    /// The given count is the cell's configuration index (see
+    /// `config_index()`), not a count of neighbours.
+    fn next_state(&self, state: LifeState, count: i32) -> LifeState {
+        let index = count as usize;
+        next_generations_state(state, self.states, self.birth[index], self.survival[index])
+    }
+
+    /// Gives a grid of the configuration index of every cell, rather than
+    /// the count of neighbours.
+    fn count_neighbours(&self, grid: &LifeGridType) -> SimpleGrid<i32> {
+        let mut neighbours_grid = SimpleGrid::init(grid.get_width(), grid.get_height(), 0);
+        for y in 0..grid.get_height() {
+            for x in 0..grid.get_width() {
+                neighbours_grid.set(x, y, Self::config_index(grid, x, y));
+            }
+        }
+
+        neighbours_grid
+    }
+}
+
+impl Display for HenselRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let classes = Self::classify();
+
+        write!(f, "B{}/S{}", Self::format_configs(&self.birth, &classes), Self::format_configs(&self.survival, &classes))?;
+        if self.states > 2 {
+            write!(f, "/C{}", self.states)?;
+        }
+
+        Ok(())
+    }
+}
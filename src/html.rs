@@ -0,0 +1,308 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::io::Write;
+
+use crate::file;
+use crate::grid::SizedGrid;
+use crate::image::{self, Colour, Palette};
+use crate::life::LifeGridType;
+
+
+/// Writes successive generations as a self-contained web page, with a
+/// player to view them in a browser.
+/// 
+/// Each generation is embedded as RLE (see `file::encode_rle()`), and
+/// drawn on a canvas by inline JavaScript, with controls to play, pause,
+/// step and scrub through the generations.
+#[derive(Clone, Debug)]
+pub struct HtmlPlayer {
+    title: String,
+    cell_size: usize,
+    palette: Palette,
+    grid_lines: Option<Colour>,
+    fps: f64
+}
+
+impl HtmlPlayer {
+    /// The script of the player, which reads the generations from `DATA`.
+    const SCRIPT: &'static str = r#"(function () {
+    const canvas = document.getElementById("grid");
+    const context = canvas.getContext("2d");
+    const playButton = document.getElementById("play");
+    const scrub = document.getElementById("scrub");
+    const status = document.getElementById("status");
+    const pitch = DATA.cellSize + (DATA.gridLines ? 1 : 0);
+    const offset = pitch - DATA.cellSize;
+    const frames = [];
+    let frame = 0;
+    let timer = null;
+
+    // Decodes the RLE of a generation into the state of every cell...
+    function decode(rle) {
+        const cells = new Uint8Array(DATA.width * DATA.height);
+        let x = 0, y = 0, count = "", prefix = 0;
+        for (const c of rle) {
+            if (c >= "0" && c <= "9") {
+                count += c;
+                continue;
+            }
+
+            if (c >= "p" && c <= "y") {
+                prefix = (c.charCodeAt(0) - 111) * 24;
+                continue;
+            }
+
+            const run = count === "" ? 1 : parseInt(count, 10);
+            count = "";
+            if (c === "!") {
+                break;
+            }
+
+            if (c === "$") {
+                x = 0;
+                y += run;
+                continue;
+            }
+
+            let state = 0;
+            if (c === "o") {
+                state = 1;
+            }
+            else if (c >= "A" && c <= "X") {
+                state = prefix + c.charCodeAt(0) - 64;
+            }
+
+            prefix = 0;
+            for (let i = 0; i < run; i++, x++) {
+                if (state && x < DATA.width && y < DATA.height) {
+                    cells[(y * DATA.width) + x] = state;
+                }
+            }
+        }
+
+        return cells;
+    }
+
+    function getCells(index) {
+        if (!frames[index]) {
+            frames[index] = decode(DATA.frames[index]);
+        }
+
+        return frames[index];
+    }
+
+    function show(index) {
+        frame = Math.max(0, Math.min(index, DATA.frames.length - 1));
+        const cells = getCells(frame);
+
+        context.fillStyle = DATA.gridLines || DATA.palette[0];
+        context.fillRect(0, 0, canvas.width, canvas.height);
+
+        let population = 0;
+        for (let y = 0; y < DATA.height; y++) {
+            for (let x = 0; x < DATA.width; x++) {
+                const state = cells[(y * DATA.width) + x];
+                population += state === 1 ? 1 : 0;
+                if (state || DATA.gridLines) {
+                    context.fillStyle = DATA.palette[Math.min(state, DATA.palette.length - 1)];
+                    context.fillRect((x * pitch) + offset, (y * pitch) + offset, DATA.cellSize, DATA.cellSize);
+                }
+            }
+        }
+
+        scrub.value = frame;
+        status.textContent = "generation " + (DATA.first + frame) + " | population " + population;
+    }
+
+    function pause() {
+        clearInterval(timer);
+        timer = null;
+        playButton.textContent = "Play";
+    }
+
+    function play() {
+        if (frame === DATA.frames.length - 1) {
+            show(0);
+        }
+
+        timer = setInterval(function () {
+            if (frame === DATA.frames.length - 1) {
+                pause();
+            }
+            else {
+                show(frame + 1);
+            }
+        }, 1000 / DATA.fps);
+        playButton.textContent = "Pause";
+    }
+
+    function toggle() {
+        if (timer) {
+            pause();
+        }
+        else {
+            play();
+        }
+    }
+
+    function step(delta) {
+        pause();
+        show(frame + delta);
+    }
+
+    playButton.addEventListener("click", toggle);
+    document.getElementById("back").addEventListener("click", function () { step(-1); });
+    document.getElementById("step").addEventListener("click", function () { step(1); });
+    scrub.addEventListener("input", function () {
+        pause();
+        show(parseInt(scrub.value, 10));
+    });
+    document.addEventListener("keydown", function (event) {
+        if (event.target === scrub) {
+            return;
+        }
+
+        if (event.key === " ") {
+            toggle();
+        }
+        else if (event.key === "ArrowLeft") {
+            step(-1);
+        }
+        else if (event.key === "ArrowRight") {
+            step(1);
+        }
+        else {
+            return;
+        }
+
+        event.preventDefault();
+    });
+
+    show(0);
+})();"#;
+
+    /// Initialises a player of 10-pixel cells in the default colours (see
+    /// `Palette::default_states()`), at 10 frames per second.
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: String::from(title),
+            cell_size: 10,
+            palette: Palette::default_states(2),
+            grid_lines: None,
+            fps: 10.0
+        }
+    }
+
+    /// Sets the size of each cell, in pixels.
+    pub fn set_cell_size(&mut self, cell_size: usize) {
+        self.cell_size = cell_size.max(1);
+    }
+
+    /// Sets the colours of the states of cells.
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
+    }
+
+    /// Sets the colour of lines between the cells, if any.
+    pub fn set_grid_lines(&mut self, grid_lines: Option<Colour>) {
+        self.grid_lines = grid_lines;
+    }
+
+    /// Sets the frames per second when playing.
+    pub fn set_fps(&mut self, fps: f64) {
+        self.fps = fps;
+    }
+
+    /// Writes the page for successive generations, starting from the given
+    /// generation.
+    /// 
+    /// The grids must all be the same size.
+    pub fn write(&self, generation: usize, grids: &[LifeGridType], w: &mut dyn Write) -> std::io::Result<()> {
+        let (columns, rows) = grids.first().map(|grid| (grid.get_width(), grid.get_height())).unwrap_or((0, 0));
+        let pitch = self.cell_size + usize::from(self.grid_lines.is_some());
+        let offset = pitch - self.cell_size;
+        let title = escape_html(&self.title);
+
+        writeln!(w, "<!DOCTYPE html>")?;
+        writeln!(w, "<html lang=\"en\">")?;
+        writeln!(w, "<head>")?;
+        writeln!(w, "<meta charset=\"utf-8\">")?;
+        writeln!(w, "<title>{}</title>", title)?;
+        writeln!(w, "<style>")?;
+        writeln!(w, "body {{ font-family: sans-serif; margin: 20px; color: #303030; }}")?;
+        writeln!(w, "h1 {{ font-size: 1.2em; }}")?;
+        writeln!(w, "canvas {{ display: block; margin-bottom: 10px; border: 1px solid #c0c0c0; }}")?;
+        writeln!(w, "#controls {{ display: flex; gap: 8px; align-items: center; }}")?;
+        writeln!(w, "#scrub {{ width: 300px; }}")?;
+        writeln!(w, "</style>")?;
+        writeln!(w, "</head>")?;
+        writeln!(w, "<body>")?;
+        writeln!(w, "<h1>{}</h1>", title)?;
+        writeln!(w, "<canvas id=\"grid\" width=\"{}\" height=\"{}\"></canvas>", (columns * pitch) + offset, (rows * pitch) + offset)?;
+        writeln!(w, "<div id=\"controls\">")?;
+        writeln!(w, "<button id=\"play\">Play</button>")?;
+        writeln!(w, "<button id=\"back\" title=\"Step back (left arrow)\">&lt;</button>")?;
+        writeln!(w, "<button id=\"step\" title=\"Step (right arrow)\">&gt;</button>")?;
+        writeln!(w, "<input id=\"scrub\" type=\"range\" min=\"0\" max=\"{}\" value=\"0\">", grids.len().saturating_sub(1))?;
+        writeln!(w, "<span id=\"status\"></span>")?;
+        writeln!(w, "</div>")?;
+        writeln!(w, "<script>")?;
+
+        // The generations, and how to draw them...
+        let palette: Vec<String> = self.palette.get_colours().iter().map(|colour| format!("\"{}\"", image::format_colour(*colour))).collect();
+        let grid_lines = self.grid_lines.map(|colour| format!("\"{}\"", image::format_colour(colour))).unwrap_or(String::from("null"));
+        writeln!(w, "const DATA = {{")?;
+        writeln!(w, "    width: {}, height: {}, cellSize: {}, first: {}, fps: {},", columns, rows, self.cell_size, generation, self.fps)?;
+        writeln!(w, "    palette: [{}], gridLines: {},", palette.join(", "), grid_lines)?;
+        writeln!(w, "    frames: [")?;
+        for grid in grids {
+            writeln!(w, "        \"{}\",", file::encode_rle(grid))?;
+        }
+        writeln!(w, "    ]")?;
+        writeln!(w, "}};")?;
+
+        writeln!(w, "{}", Self::SCRIPT)?;
+        writeln!(w, "</script>")?;
+        writeln!(w, "</body>")?;
+        writeln!(w, "</html>")
+    }
+
+    /// Writes the page to a file.
+    /// 
+    /// See `write()`.
+    pub fn write_path(&self, path: &str, generation: usize, grids: &[LifeGridType]) -> Result<(), String> {
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path).map_err(|_| format!("cannot create file '{}'", path))?);
+        self.write(generation, grids, &mut file)
+            .and_then(|_| file.flush())
+            .map_err(|error| format!("cannot write file '{}': {}", path, error))
+    }
+}
+
+/// Escapes the characters of text that are special in HTML.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
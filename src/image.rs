@@ -0,0 +1,336 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::io::Write;
+
+use crate::grid::{Grid, SimpleGrid, SizedGrid};
+use crate::life::LifeGridType;
+
+
+/// A colour - its red, green & blue values.
+pub type Colour = (u8, u8, u8);
+
+/// The colour of the lines between cells.
+pub const GRID_LINE_COLOUR: Colour = (192, 192, 192);
+
+/// The colours of the states of cells, by state number.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Palette {
+    colours: Vec<Colour>
+}
+
+impl Palette {
+    /// Colours of states 2 onwards, by default.
+    const STATE_COLOURS: &'static [Colour] = &[
+        (220, 40, 40), (40, 160, 60), (40, 80, 220), (240, 160, 20),
+        (140, 60, 180), (20, 170, 180), (200, 200, 40), (120, 120, 120)
+    ];
+
+    /// The number of levels of heat used for heat-maps.
+    pub const HEAT_LEVELS: usize = 128;
+
+    /// The number of levels of grey used for heat-maps - as many as an image
+    /// can have, besides grid lines.
+    pub const GREY_LEVELS: usize = Image::LINE_INDEX as usize;
+
+    /// Initialises a palette of the given colours.
+    pub fn new(colours: Vec<Colour>) -> Self {
+        Self { colours }
+    }
+
+    /// Initialises the default palette - white for 'dead', black for 'live'
+    /// and distinct colours for any other states.
+    pub fn default_states(states: u8) -> Self {
+        let mut colours = vec![(255, 255, 255), (0, 0, 0)];
+        colours.extend((2..states as usize).map(|state| Self::STATE_COLOURS[(state - 2) % Self::STATE_COLOURS.len()]));
+
+        Self { colours }
+    }
+
+    /// Initialises a palette of the given number of levels of heat, from
+    /// black through red & yellow to white.
+    pub fn heat(levels: usize) -> Self {
+        let colours = (0..levels)
+            .map(|level| {
+                let heat = if levels > 1 { (level * 765) / (levels - 1) } else { 0 };
+                (heat.min(255) as u8, heat.saturating_sub(255).min(255) as u8, heat.saturating_sub(510).min(255) as u8)
+            })
+            .collect();
+
+        Self { colours }
+    }
+
+    /// Initialises a palette of the given number of levels of grey, from
+    /// black to white.
+    pub fn grey(levels: usize) -> Self {
+        let colours = (0..levels)
+            .map(|level| {
+                let grey = if levels > 1 { ((level * 255) / (levels - 1)) as u8 } else { 0 };
+                (grey, grey, grey)
+            })
+            .collect();
+
+        Self { colours }
+    }
+
+    /// Parses a palette given as colours separated by commas, each a name
+    /// (e.g. `white`) or `#rrggbb`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let colours = text.split(',').map(|colour| parse_colour(colour.trim())).collect::<Option<Vec<Colour>>>()?;
+        if colours.is_empty() { None } else { Some(Self { colours }) }
+    }
+
+    /// Gives the colour of the given index - the last colour for any index
+    /// beyond the palette.
+    pub fn get(&self, index: usize) -> Colour {
+        self.colours.get(index).or(self.colours.last()).copied().unwrap_or((0, 0, 0))
+    }
+
+    pub fn len(&self) -> usize {
+        self.colours.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colours.is_empty()
+    }
+
+    /// Gives the colours.
+    pub fn get_colours(&self) -> &[Colour] {
+        &self.colours
+    }
+
+    /// Adds a colour, giving its index.
+    pub fn push(&mut self, colour: Colour) -> usize {
+        self.colours.push(colour);
+        self.colours.len() - 1
+    }
+}
+
+/// Parses a colour given as a name (e.g. `white`) or `#rrggbb`.
+pub fn parse_colour(text: &str) -> Option<Colour> {
+    let colour = match text.to_lowercase().as_str() {
+        "white" => (255, 255, 255),
+        "black" => (0, 0, 0),
+        "grey" | "gray" => (128, 128, 128),
+        "silver" => (192, 192, 192),
+        "red" => (255, 0, 0),
+        "green" => (0, 128, 0),
+        "blue" => (0, 0, 255),
+        "yellow" => (255, 255, 0),
+        "orange" => (255, 165, 0),
+        "purple" => (128, 0, 128),
+        "cyan" => (0, 255, 255),
+        "magenta" => (255, 0, 255),
+        hex => {
+            let hex = hex.strip_prefix('#').filter(|hex| (hex.len() == 6) && hex.is_ascii())?;
+            let value = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+            (value(0)?, value(2)?, value(4)?)
+        }
+    };
+
+    Some(colour)
+}
+
+/// Gives a colour as `#rrggbb`.
+pub fn format_colour((red, green, blue): Colour) -> String {
+    format!("#{:02x}{:02x}{:02x}", red, green, blue)
+}
+
+
+//---------------------------------------------------------------------------//
+
+
+/// An image of a grid, where each pixel is an index into a palette.
+/// 
+/// Each cell is drawn as a square of the given size, optionally separated
+/// by one-pixel grid lines (which are also drawn around the edges).
+#[derive(Clone, Debug)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+    palette: Palette,
+    line: Option<u8>
+}
+
+impl Image {
+    /// The palette index of grid lines.
+    const LINE_INDEX: u8 = 255;
+
+    /// Initialises an image of a grid, with each cell's state as its index
+    /// into the palette.
+    /// 
+    /// With grid lines, their colour is added to the end of the palette.
+    pub fn from_grid(grid: &LifeGridType, palette: &Palette, cell_size: usize, grid_lines: Option<Colour>) -> Self {
+        Self::from_indices(grid.get_width(), grid.get_height(), |x, y| grid.get(x, y).get(), palette, cell_size, grid_lines)
+    }
+
+    /// Initialises an image of counts (e.g. of a `HeatMap`), shaded relative
+    /// to the greatest count across the levels of the palette.
+    pub fn from_counts(counts: &SimpleGrid<u32>, palette: &Palette, cell_size: usize, grid_lines: Option<Colour>) -> Self {
+        // Counts are widened, so they can't overflow when scaled...
+        let max = counts.iter().map(|cell| *cell.get()).max().unwrap_or(0) as u64;
+        let levels = palette.len().clamp(1, Self::LINE_INDEX as usize) as u64 - 1;
+        let level = |x, y| (((*counts.get(x, y) as u64) * levels) + (max / 2)).checked_div(max).unwrap_or(0) as u8;
+
+        Self::from_indices(counts.get_width(), counts.get_height(), level, palette, cell_size, grid_lines)
+    }
+
+    fn from_indices(columns: usize, rows: usize, index: impl Fn(usize, usize) -> u8, palette: &Palette, cell_size: usize, grid_lines: Option<Colour>) -> Self {
+        let cell_size = cell_size.max(1);
+        // Any grid lines take the last index, after padding the palette with
+        // its last colour - which indices beyond it still take...
+        let mut palette = palette.clone();
+        palette.colours.truncate(Self::LINE_INDEX as usize);
+        let line = grid_lines.map(|colour| {
+            let last = palette.get(Self::LINE_INDEX as usize);
+            palette.colours.resize(Self::LINE_INDEX as usize, last);
+            palette.push(colour) as u8
+        });
+
+        // Each cell is preceded by a line, and the last is followed by one...
+        let pitch = cell_size + line.map(|_| 1).unwrap_or(0);
+        let offset = pitch - cell_size;
+        let (width, height) = ((columns * pitch) + offset, (rows * pitch) + offset);
+
+        let mut pixels = vec![line.unwrap_or(0); width * height];
+        for y in 0..rows {
+            for x in 0..columns {
+                let value = index(x, y);
+                for dy in 0..cell_size {
+                    let start = (((y * pitch) + offset + dy) * width) + (x * pitch) + offset;
+                    pixels[start..start + cell_size].fill(value);
+                }
+            }
+        }
+
+        Self {
+            width,
+            height,
+            pixels,
+            palette,
+            line
+        }
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    /// Gives the palette index of the given pixel.
+    pub fn get_index(&self, x: usize, y: usize) -> u8 {
+        self.pixels[(y * self.width) + x]
+    }
+
+    /// Gives the colour of the given pixel.
+    pub fn get_colour(&self, x: usize, y: usize) -> Colour {
+        self.palette.get(self.get_index(x, y) as usize)
+    }
+
+    /// Gives the palette indices of every pixel, row by row.
+    pub fn get_indices(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn get_palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    /// Writes the image as a binary (`P4`) PBM image, where a pixel of index
+    /// 0 or of a grid line is white and any other is black.
+    pub fn write_pbm(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        write!(w, "P4\n{} {}\n", self.width, self.height)?;
+
+        let mut row = vec![0u8; self.width.div_ceil(8)];
+        for y in 0..self.height {
+            row.fill(0);
+            for x in 0..self.width {
+                let index = self.get_index(x, y);
+                if (index != 0) && (Some(index) != self.line) {
+                    row[x / 8] |= 0x80 >> (x % 8);
+                }
+            }
+            w.write_all(&row)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the image as a binary (`P6`) PPM image.
+    pub fn write_ppm(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        write!(w, "P6\n{} {}\n255\n", self.width, self.height)?;
+
+        let bytes: Vec<u8> = self.pixels.iter()
+            .flat_map(|index| {
+                let (red, green, blue) = self.palette.get(*index as usize);
+                [red, green, blue]
+            })
+            .collect();
+
+        w.write_all(&bytes)
+    }
+
+    /// Writes the image as a binary (`P5`) PGM image, with the brightness of
+    /// each pixel's colour.
+    pub fn write_pgm(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        write!(w, "P5\n{} {}\n255\n", self.width, self.height)?;
+
+        let bytes: Vec<u8> = self.pixels.iter()
+            .map(|index| {
+                let (red, green, blue) = self.palette.get(*index as usize);
+                (((red as u32 * 299) + (green as u32 * 587) + (blue as u32 * 114) + 500) / 1000) as u8
+            })
+            .collect();
+
+        w.write_all(&bytes)
+    }
+
+    /// Writes the image to a file, as a PBM, PGM or PPM image according to
+    /// the path's extension.
+    /// 
+    /// A PBM image can only be written of a grid with 'dead' and 'live'
+    /// cells.
+    pub fn write_path(&self, path: &str) -> Result<(), String> {
+        let extension = path.rsplit_once('.').map(|(_, extension)| extension.to_lowercase()).unwrap_or_default();
+        let write = match extension.as_str() {
+            "pbm" => Self::write_pbm,
+            "pgm" => Self::write_pgm,
+            "ppm" => Self::write_ppm,
+            _ => return Err(format!("'{}' should be a '.pbm', '.pgm' or '.ppm' path", path))
+        };
+
+        if (extension == "pbm") && self.pixels.iter().any(|index| (*index >= 2) && (Some(*index) != self.line)) {
+            return Err(format!("a PBM image can only have 'dead' and 'live' cells - use a '.ppm' path for '{}'", path));
+        }
+
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path).map_err(|_| format!("cannot create file '{}'", path))?);
+        write(self, &mut file).and_then(|_| file.flush()).map_err(|error| format!("cannot write file '{}': {}", path, error))
+    }
+}
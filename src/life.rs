@@ -24,15 +24,78 @@
 
 ---------------------------------------------------------------------------- */
 
-use crate::grid::{Grid, GridCell, SimpleGrid, SizedGrid, SparseGrid};
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
 
+use crate::grid::{BoundingBox, Grid, GridCell, SimpleGrid, SizedGrid, SparseGrid, Symmetry};
+use crate::neighbourhood::Offset;
 
-type LifeCellType = char;
-type LifeGridType = SimpleGrid<LifeCellType>;
+
+/// The state of a 'life' cell.
+/// 
+/// State 0 is 'dead' and state 1 is 'live'. For Generations rules, any
+/// higher state is a 'dying' (refractory) state - the cell no longer
+/// counts as 'live' but cannot yet be born into.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LifeState(pub u8);
+
+impl LifeState {
+    pub const DEAD: LifeState = LifeState(0);
+    pub const LIVE: LifeState = LifeState(1);
+
+    /// Characters used to display the 'dying' states, in order from the
+    /// first dying state (2) onwards.
+    const DYING_CHARS: &'static str = "o+=-:.,`";
+
+    /// Gives the state number.
+    pub fn get(&self) -> u8 {
+        self.0
+    }
+
+    /// Indicates if the state is neither 'dead' nor 'live'.
+    pub fn is_dying(&self) -> bool {
+        self.0 > 1
+    }
+
+    /// Gives the character used to display the state.
+    /// 
+    /// Dead cells are a space and live cells are a `*`. Dying states use
+    /// a distinct character each, falling back to lower-case letters then
+    /// `?` when there are more dying states than characters.
+    pub fn to_char(self) -> char {
+        match self.0 {
+            0 => ' ',
+            1 => '*',
+            n => {
+                let index = (n - 2) as usize;
+                let count = Self::DYING_CHARS.len();
+                if index < count {
+                    Self::DYING_CHARS.as_bytes()[index] as char
+                }
+                else if index - count < 26 {
+                    (b'a' + (index - count) as u8) as char
+                }
+                else {
+                    '?'
+                }
+            }
+        }
+    }
+}
+
+impl Display for LifeState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+pub type LifeCellType = LifeState;
+pub type LifeGridType = SimpleGrid<LifeCellType>;
 
 pub trait LifeGrid {
-    const DEAD_CELL: LifeCellType = ' ';
-    const LIVE_CELL: LifeCellType = '*';
+    const DEAD_CELL: LifeCellType = LifeState::DEAD;
+    const LIVE_CELL: LifeCellType = LifeState::LIVE;
     
     /// Initialises a grid with 'dead' cells.
     fn init_life(width: usize, height: usize) -> Self;
@@ -47,6 +110,32 @@ pub trait LifeGrid {
     /// 
     /// See `is_dead()`.
     fn set_dead(&mut self, x: usize, y: usize);
+
+    /// Sets a cell to the given state number.
+    /// 
+    /// See `get_state()`.
+    fn set_state(&mut self, x: usize, y: usize, state: u8);
+
+    /// Gives the number of 'live' cells.
+    fn get_population(&self) -> usize;
+
+    /// Gives the smallest region containing every cell that isn't 'dead',
+    /// or `None` if every cell is 'dead'.
+    fn get_bounding_box(&self) -> Option<BoundingBox>;
+
+    /// Gives a copy of the given region of the grid, with an empty margin
+    /// of the given size around it.
+    /// 
+    /// The region may extend beyond the grid, in which case those cells are
+    /// 'dead'.
+    fn crop(&self, bounds: &BoundingBox, margin: usize) -> Self;
+
+    /// Gives a hash of the pattern in the grid that doesn't depend on where
+    /// the pattern is in the grid.
+    /// 
+    /// If `is_symmetric`, the hash also doesn't depend on how the pattern
+    /// is rotated or reflected.
+    fn get_pattern_hash(&self, is_symmetric: bool) -> u64;
 }
 
 impl LifeGrid for LifeGridType {
@@ -61,6 +150,63 @@ impl LifeGrid for LifeGridType {
     fn set_dead(&mut self, x: usize, y: usize) {
         self.set(x, y, <LifeGridType as LifeGrid>::DEAD_CELL);
     }
+
+    fn set_state(&mut self, x: usize, y: usize, state: u8) {
+        self.set(x, y, LifeState(state));
+    }
+
+    fn get_population(&self) -> usize {
+        self.iter().filter(|cell| cell.get() == &LifeState::LIVE).count()
+    }
+
+    fn get_bounding_box(&self) -> Option<BoundingBox> {
+        let mut bounds: Option<(usize, usize, usize, usize)> = None;
+        for cell in self.iter().filter(|cell| cell.get() != &LifeState::DEAD) {
+            let (x, y) = (cell.get_x(), cell.get_y());
+            bounds = match bounds {
+                Some((left, top, right, bottom)) => Some((left.min(x), top.min(y), right.max(x), bottom.max(y))),
+                None => Some((x, y, x, y))
+            };
+        }
+
+        bounds.map(|(left, top, right, bottom)| BoundingBox::new(left as isize, top as isize, right - left + 1, bottom - top + 1))
+    }
+
+    fn crop(&self, bounds: &BoundingBox, margin: usize) -> Self {
+        let mut grid = Self::init_life(bounds.width + (2 * margin), bounds.height + (2 * margin));
+        for y in 0..bounds.height {
+            for x in 0..bounds.width {
+                let (from_x, from_y) = (bounds.x + x as isize, bounds.y + y as isize);
+                if (from_x >= 0) && (from_y >= 0) && ((from_x as usize) < self.get_width()) && ((from_y as usize) < self.get_height()) {
+                    grid.set(x + margin, y + margin, *self.get(from_x as usize, from_y as usize));
+                }
+            }
+        }
+
+        grid
+    }
+
+    fn get_pattern_hash(&self, is_symmetric: bool) -> u64 {
+        let pattern = match self.get_bounding_box() {
+            Some(bounds) => self.crop(&bounds, 0),
+            None => Self::init_life(0, 0)
+        };
+
+        let hash = |grid: &Self| {
+            let mut hasher = DefaultHasher::new();
+            grid.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        // The least of the hashes of the 8 symmetries is the same for any
+        // of them...
+        if is_symmetric {
+            Symmetry::ALL.iter().map(|symmetry| hash(&pattern.transform(*symmetry))).min().unwrap_or(0)
+        }
+        else {
+            hash(&pattern)
+        }
+    }
 }
 
 pub trait LifeCell {
@@ -70,8 +216,21 @@ pub trait LifeCell {
     /// Indicates if this cell is 'dead'.
     fn is_dead(&self) -> bool;
 
+    /// Indicates if this cell is 'dying' - i.e. in one of the refractory
+    /// states of a Generations rule.
+    fn is_dying(&self) -> bool;
+
+    /// Gives the state number of this cell.
+    fn get_state(&self) -> u8;
+
     /// Indicates how many of the cell's neighbours are 'live'.
     fn count_neighbours(&self) -> i32;
+
+    /// Indicates how many of the cells at the given offsets from this cell
+    /// are 'live'.
+    /// 
+    /// See `Neighbourhood::get_offsets()`.
+    fn count_neighbours_in(&self, offsets: &[Offset]) -> i32;
 }
 
 impl<'a> LifeCell for GridCell<'a, LifeGridType> {
@@ -82,6 +241,14 @@ impl<'a> LifeCell for GridCell<'a, LifeGridType> {
     fn is_dead(&self) -> bool {
         self.get() == &<LifeGridType as LifeGrid>::DEAD_CELL
     }
+
+    fn is_dying(&self) -> bool {
+        self.get().is_dying()
+    }
+
+    fn get_state(&self) -> u8 {
+        self.get().get()
+    }
     
     fn count_neighbours(&self) -> i32 {
         // Use an 'adjust' closure to convert the neighbouring cells (which
@@ -104,4 +271,12 @@ impl<'a> LifeCell for GridCell<'a, LifeGridType> {
             + adjust(-1, 0) + adjust(1, 0)
             + adjust(-1, 1) + adjust(0, 1) + adjust(1, 1)
     }
-}
+
+    fn count_neighbours_in(&self, offsets: &[Offset]) -> i32 {
+        // As with `count_neighbours()`, compare the relatives' contents
+        // directly rather than instantiating a `GridCell` for each...
+        offsets.iter()
+            .filter(|(dx, dy)| self.get_relative(*dx, *dy) == Some(&<LifeGridType as LifeGrid>::LIVE_CELL))
+            .count() as i32
+    }
+}
\ No newline at end of file
@@ -0,0 +1,249 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
+use crate::grid::{Grid, SimpleGrid, SizedGrid};
+use crate::life::{LifeGridType, LifeState};
+use crate::neighbourhood::Neighbourhood;
+use crate::rule::{next_generations_state, Rule};
+
+
+/// A Larger than Life rule, e.g. `R5,C0,M1,S34..58,B34..45,NM`.
+/// 
+/// Neighbours are counted over a Moore or von Neumann neighbourhood of
+/// range `R`, including the cell itself if `M1`. A cell is born when its
+/// count is within the `B` range and survives when its count is within the
+/// `S` range. With `C` greater than 2 the rule has Generations-style 'dying'
+/// states.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LargerThanLifeRule {
+    range: usize,
+    states: u8,
+    has_middle: bool,
+    survival: RangeInclusive<i32>,
+    birth: RangeInclusive<i32>,
+    neighbourhood: Neighbourhood
+}
+
+impl LargerThanLifeRule {
+    /// Parses a Larger than Life rulestring.
+    /// 
+    /// The `R`, `S` & `B` parts are required - `C` defaults to 2 states, `M`
+    /// to excluding the cell itself, and `N` to the Moore (`NM`)
+    /// neighbourhood, with `NN` for von Neumann.
+    pub fn parse(rulestring: &str) -> Result<Self, String> {
+        let text = rulestring.trim().to_uppercase();
+        let bad_part = |part: &str| format!("rule '{}' has a bad part '{}'", rulestring, part);
+
+        let mut range = None;
+        let mut states = 2;
+        let mut has_middle = false;
+        let mut survival = None;
+        let mut birth = None;
+        let mut is_von_neumann = false;
+
+        for part in text.split(',').map(|part| part.trim()) {
+            let (prefix, value) = part.split_at(part.chars().next().map(|c| c.len_utf8()).unwrap_or(0));
+            match prefix {
+                "R" => range = Some(value.parse::<usize>().ok().filter(|r| *r > 0).ok_or_else(|| bad_part(part))?),
+                "C" => states = match value.parse::<u8>() {
+                    Ok(0) => 2,
+                    Ok(n) if n >= 2 => n,
+                    _ => return Err(bad_part(part))
+                },
+                "M" => has_middle = match value {
+                    "0" => false,
+                    "1" => true,
+                    _ => return Err(bad_part(part))
+                },
+                "S" => survival = Some(Self::parse_range(value).ok_or_else(|| bad_part(part))?),
+                "B" => birth = Some(Self::parse_range(value).ok_or_else(|| bad_part(part))?),
+                "N" => is_von_neumann = match value {
+                    "M" => false,
+                    "N" => true,
+                    _ => return Err(bad_part(part))
+                },
+                _ => return Err(bad_part(part))
+            }
+        }
+
+        match (range, survival, birth) {
+            (Some(range), Some(survival), Some(birth)) => Ok(Self {
+                range,
+                states,
+                has_middle,
+                survival,
+                birth,
+                neighbourhood: if is_von_neumann { Neighbourhood::VonNeumann(range) } else { Neighbourhood::Moore(range) }
+            }),
+            _ => Err(format!("rule '{}' should have 'R', 'S' and 'B' parts", rulestring))
+        }
+    }
+
+    /// Converts a range such as `34..58` into a `RangeInclusive`.
+    fn parse_range(text: &str) -> Option<RangeInclusive<i32>> {
+        let (min, max) = text.split_once("..")?;
+        let min = min.parse::<i32>().ok()?;
+        let max = max.parse::<i32>().ok()?;
+
+        if min <= max { Some(min..=max) } else { None }
+    }
+
+    /// Gives the range (radius) of the neighbourhood.
+    pub fn get_range(&self) -> usize {
+        self.range
+    }
+
+    /// Gives the neighbourhood over which neighbours are counted.
+    pub fn get_neighbourhood(&self) -> &Neighbourhood {
+        &self.neighbourhood
+    }
+}
+
+impl Rule for LargerThanLifeRule {
+    fn get_states(&self) -> u8 {
+        self.states
+    }
+
+    fn next_state(&self, state: LifeState, count: i32) -> LifeState {
+        next_generations_state(state, self.states, self.birth.contains(&count), self.survival.contains(&count))
+    }
+
+    /// Counts the neighbours of every cell using prefix sums over the grid,
+    /// rather than visiting each of the (2R+1)^2 neighbours of every cell.
+    /// 
+    /// For the Moore neighbourhood a summed-area table gives each count in
+    /// constant time. For the von Neumann neighbourhood, per-row prefix sums
+    /// give each count in time proportional to the range.
+    fn count_neighbours(&self, grid: &LifeGridType) -> SimpleGrid<i32> {
+        let (width, height) = (grid.get_width(), grid.get_height());
+        let r = self.range as isize;
+        let sums = PrefixSums::init(grid);
+
+        let mut neighbours_grid = SimpleGrid::init(width, height, 0);
+        for y in 0..height as isize {
+            for x in 0..width as isize {
+                let mut count = match self.neighbourhood {
+                    Neighbourhood::VonNeumann(_) => (-r..=r)
+                        .map(|dy| {
+                            let span = r - dy.abs();
+                            sums.row_sum(y + dy, x - span, x + span)
+                        })
+                        .sum(),
+                    _ => sums.box_sum(x - r, y - r, x + r, y + r)
+                };
+
+                if !self.has_middle && (grid.get(x as usize, y as usize) == &LifeState::LIVE) {
+                    count -= 1;
+                }
+
+                neighbours_grid.set(x as usize, y as usize, count);
+            }
+        }
+
+        neighbours_grid
+    }
+
+    fn get_radius(&self) -> usize {
+        self.range
+    }
+}
+
+impl Display for LargerThanLifeRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "R{},C{},M{},S{}..{},B{}..{},N{}",
+            self.range,
+            if self.states > 2 { self.states } else { 0 },
+            if self.has_middle { 1 } else { 0 },
+            self.survival.start(), self.survival.end(),
+            self.birth.start(), self.birth.end(),
+            if matches!(self.neighbourhood, Neighbourhood::VonNeumann(_)) { "N" } else { "M" })
+    }
+}
+
+
+//---------------------------------------------------------------------------//
+
+
+/// Prefix sums of the 'live' cells of a grid.
+/// 
+/// Any region that is partly out of bounds is clipped to the grid, so cells
+/// out of bounds count as 'dead'.
+struct PrefixSums {
+    width: isize,
+    height: isize,
+    rows: Vec<Vec<i32>>,        // <--- rows[y][x] is the count of 'live'
+                                //      cells in row y left of column x.
+    table: Vec<Vec<i32>>        // <--- table[y][x] is the count of 'live'
+                                //      cells above row y and left of
+                                //      column x.
+}
+
+impl PrefixSums {
+    fn init(grid: &LifeGridType) -> Self {
+        let (width, height) = (grid.get_width(), grid.get_height());
+        let mut rows = vec![vec![0; width + 1]; height];
+        let mut table = vec![vec![0; width + 1]; height + 1];
+
+        for y in 0..height {
+            for x in 0..width {
+                let live = if grid.get(x, y) == &LifeState::LIVE { 1 } else { 0 };
+                rows[y][x + 1] = rows[y][x] + live;
+                table[y + 1][x + 1] = table[y][x + 1] + rows[y][x + 1];
+            }
+        }
+
+        Self {
+            width: width as isize,
+            height: height as isize,
+            rows,
+            table
+        }
+    }
+
+    /// Gives the count of 'live' cells in the given row from `x0` to `x1`
+    /// inclusive.
+    fn row_sum(&self, y: isize, x0: isize, x1: isize) -> i32 {
+        if (y < 0) || (y >= self.height) {
+            return 0;
+        }
+
+        let (x0, x1) = (x0.clamp(0, self.width) as usize, (x1 + 1).clamp(0, self.width) as usize);
+        let row = &self.rows[y as usize];
+        row[x1] - row[x0]
+    }
+
+    /// Gives the count of 'live' cells in the box from (`x0`,`y0`) to
+    /// (`x1`,`y1`) inclusive.
+    fn box_sum(&self, x0: isize, y0: isize, x1: isize, y1: isize) -> i32 {
+        let (x0, x1) = (x0.clamp(0, self.width) as usize, (x1 + 1).clamp(0, self.width) as usize);
+        let (y0, y1) = (y0.clamp(0, self.height) as usize, (y1 + 1).clamp(0, self.height) as usize);
+
+        self.table[y1][x1] - self.table[y0][x1] - self.table[y1][x0] + self.table[y0][x0]
+    }
+}
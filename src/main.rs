@@ -26,85 +26,1103 @@
 
 #![allow(dead_code, unused)]
 
+mod animation;
+mod census;
+mod config;
+mod diff;
+mod editor;
 mod env;
 mod file;
+mod gif;
 mod life;
 mod grid;
+mod heatmap;
+mod hensel;
+mod html;
+mod image;
+mod ltl;
+mod methuselah;
+mod neighbourhood;
+mod period;
+mod render;
+mod rule;
+mod search;
+mod stats;
+mod svg;
+mod symmetry;
+mod table;
+mod terminal;
+mod universe;
 mod tests;
 
-use std::io::{BufRead, IsTerminal};
+use std::fs::File;
+use std::io::{BufRead, BufWriter, IsTerminal, Write};
 
-use env::{ArgsHelper, OptionUnwrapExit, ResultUnwrapExit};
-use file::FileParser;
-use life::{LifeCell, LifeGrid};
-use grid::{Grid, GridCell, SimpleGrid};
+use animation::Animation;
+use census::{Catalogue, Census, OccupiedCells};
+use diff::{DiffStyle, GridDiff};
+use editor::Editor;
+use env::{ArgsHelper, CommandSpec, OptionSpec, OptionUnwrapExit, OptionValue, ResultUnwrapExit};
+use file::{FileParser, ReadBuffer, RleParser};
+use gif::GifRecorder;
+use life::{LifeCell, LifeGrid, LifeGridType};
+use grid::{BoundingBox, Grid, GridCell, SimpleGrid, SizedGrid};
+use heatmap::HeatMap;
+use html::HtmlPlayer;
+use image::{Colour, Image, Palette};
+use methuselah::Methuselah;
+use neighbourhood::Neighbourhood;
+use period::{MotionDetector, PeriodDetector, Stabilisation};
+use render::{RenderStyle, Renderer};
+use rule::{LifeRule, Rule};
+use search::SoupSearch;
+use stats::{GenerationStats, StatsWriter};
+use svg::SvgRenderer;
+use table::TableRule;
+use universe::Universe;
+
+
+/// The rule option, shared by every command that runs a pattern.
+const RULE_OPTION: OptionSpec = OptionSpec { long: "rule", short: Some('r'), value: OptionValue::Required, value_name: "<rule>", help: "The rule, or a rule table path - overrides a rule in the pattern file" };
+
+/// The neighbourhood mask option, shared by every command that runs a
+/// pattern.
+const MASK_OPTION: OptionSpec = OptionSpec { long: "mask", short: Some('m'), value: OptionValue::Required, value_name: "<mask-file-path>", help: "A custom neighbourhood for a Life-like or Generations rule" };
+
+/// The census gap option, shared by every command that takes a census.
+const GAP_OPTION: OptionSpec = OptionSpec { long: "gap", short: None, value: OptionValue::Required, value_name: "<n>", help: "The number of empty cells between objects in a census" };
+
+/// The margin option, shared by every command that loads a pattern.
+const MARGIN_OPTION: OptionSpec = OptionSpec { long: "margin", short: None, value: OptionValue::Required, value_name: "<n>", help: "An empty margin around an RLE pattern" };
+
+/// The generation limit option, shared by every command that runs a
+/// pattern until it stabilises.
+const MAX_OPTION: OptionSpec = OptionSpec { long: "max", short: None, value: OptionValue::Required, value_name: "<n>", help: "The maximum number of generations" };
+
+/// The unbounded option, shared by every command that runs a pattern for a
+/// number of generations.
+const UNBOUNDED_OPTION: OptionSpec = OptionSpec { long: "unbounded", short: Some('u'), value: OptionValue::None, value_name: "", help: "Let the pattern grow beyond the grid" };
+
+/// The viewport option, shared by every command that prints grids.
+const VIEWPORT_OPTION: OptionSpec = OptionSpec { long: "viewport", short: None, value: OptionValue::Required, value_name: "<x>,<y>,<width>x<height>", help: "Show just this region of the grid" };
+
+/// The follow option, shared by every command that prints grids.
+const FOLLOW_OPTION: OptionSpec = OptionSpec { long: "follow", short: Some('f'), value: OptionValue::None, value_name: "", help: "Show the region around the pattern - of the viewport's size, if given" };
+
+/// The zoom option, shared by every command that prints grids.
+const ZOOM_OPTION: OptionSpec = OptionSpec { long: "zoom", short: Some('z'), value: OptionValue::Required, value_name: "<n>", help: "Show each NxN block of cells as one character, shaded by density" };
+
+/// The style option, shared by every command that prints grids.
+const STYLE_OPTION: OptionSpec = OptionSpec { long: "style", short: None, value: OptionValue::Required, value_name: "<ascii|box|braille|halfblock>", help: "How cells are drawn (default ascii)" };
+
+/// The characters option, shared by every command that prints grids.
+const CHARS_OPTION: OptionSpec = OptionSpec { long: "chars", short: None, value: OptionValue::Required, value_name: "<live><dead>", help: "The characters of 'live' and 'dead' cells in the ascii and box styles, e.g. '#.'" };
+
+/// The cell size option, shared by every command that writes images.
+const CELL_SIZE_OPTION: OptionSpec = OptionSpec { long: "cell-size", short: None, value: OptionValue::Required, value_name: "<pixels>", help: "The size of each cell in an image, in pixels (default 1, or 10 in an SVG)" };
+
+/// The grid lines option, shared by every command that writes images.
+const GRID_LINES_OPTION: OptionSpec = OptionSpec { long: "grid-lines", short: None, value: OptionValue::None, value_name: "", help: "Draw lines between the cells in an image" };
+
+/// The palette option, shared by every command that writes images.
+const PALETTE_OPTION: OptionSpec = OptionSpec { long: "palette", short: None, value: OptionValue::Required, value_name: "<colours>", help: "The colours of the states in an image, e.g. 'white,black,#ff0000'" };
+
+/// The commands, with `run` the default.
+const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "run",
+        summary: "Run a pattern for a number of generations",
+        args: &["input-file-path", "iterations"],
+        options: &[
+            OptionSpec { long: "verbose", short: Some('v'), value: OptionValue::None, value_name: "", help: "Print every generation" },
+            OptionSpec { long: "debug", short: Some('d'), value: OptionValue::None, value_name: "", help: "Print the neighbour counts of every generation" },
+            OptionSpec { long: "animate", short: Some('a'), value: OptionValue::None, value_name: "", help: "Play every generation in place, in a terminal" },
+            OptionSpec { long: "fps", short: None, value: OptionValue::Required, value_name: "<n>", help: "The frames per second when animating (default 10)" },
+            OptionSpec { long: "diff", short: None, value: OptionValue::Optional, value_name: "<chars|colour>", help: "Print every generation with its births and deaths marked" },
+            OptionSpec { long: "until-stable", short: Some('s'), value: OptionValue::None, value_name: "", help: "Stop once the pattern is stable" },
+            UNBOUNDED_OPTION,
+            OptionSpec { long: "census", short: Some('c'), value: OptionValue::None, value_name: "", help: "Print a census of the objects in the final grid" },
+            GAP_OPTION,
+            OptionSpec { long: "stats", short: None, value: OptionValue::Required, value_name: "<csv-or-jsonl-path>", help: "Write statistics of every generation" },
+            OptionSpec { long: "heatmap", short: None, value: OptionValue::None, value_name: "", help: "Print heat-maps of the activity of every cell" },
+            OptionSpec { long: "heatmap-pgm", short: None, value: OptionValue::Required, value_name: "<path>", help: "Write the 'live' heat-map as a PGM image" },
+            OptionSpec { long: "changes-pgm", short: None, value: OptionValue::Required, value_name: "<path>", help: "Write the changes heat-map as a PGM image" },
+            OptionSpec { long: "heatmap-ppm", short: None, value: OptionValue::Required, value_name: "<path>", help: "Write the 'live' heat-map as a PPM image, shaded from black through red to white" },
+            OptionSpec { long: "changes-ppm", short: None, value: OptionValue::Required, value_name: "<path>", help: "Write the changes heat-map as a PPM image, shaded from black through red to white" },
+            OptionSpec { long: "gif", short: None, value: OptionValue::Required, value_name: "<path>", help: "Write every generation as an animated GIF" },
+            OptionSpec { long: "gif-every", short: None, value: OptionValue::Required, value_name: "<k>", help: "Write just every k-th generation to the GIF (default 1)" },
+            OptionSpec { long: "gif-delay", short: None, value: OptionValue::Required, value_name: "<ms>", help: "The delay between frames of the GIF (default 100)" },
+            OptionSpec { long: "gif-crop", short: None, value: OptionValue::None, value_name: "", help: "Crop the GIF to the largest extent of the pattern" },
+            CELL_SIZE_OPTION,
+            GRID_LINES_OPTION,
+            PALETTE_OPTION,
+            VIEWPORT_OPTION,
+            FOLLOW_OPTION,
+            ZOOM_OPTION,
+            STYLE_OPTION,
+            CHARS_OPTION,
+            RULE_OPTION,
+            MASK_OPTION,
+            MARGIN_OPTION
+        ]
+    },
+    CommandSpec {
+        name: "convert",
+        summary: "Convert a pattern to a 'life' file or, for a '.rle' path, an RLE file",
+        args: &["input-file-path", "output-file-path"],
+        options: &[RULE_OPTION, MARGIN_OPTION]
+    },
+    CommandSpec {
+        name: "analyse",
+        summary: "Analyse the symmetries of a pattern",
+        args: &["input-file-path"],
+        options: &[MAX_OPTION, RULE_OPTION, MASK_OPTION]
+    },
+    CommandSpec {
+        name: "report",
+        summary: "Run a pattern until it stabilises and report its lifespan and what it leaves",
+        args: &["input-file-path"],
+        options: &[MAX_OPTION, GAP_OPTION, RULE_OPTION, MASK_OPTION]
+    },
+    CommandSpec {
+        name: "search",
+        summary: "Search random soups and take a census of the objects they leave",
+        args: &["soups"],
+        options: &[
+            OptionSpec { long: "size", short: None, value: OptionValue::Required, value_name: "<width>x<height>", help: "The size of each soup (default 16x16)" },
+            OptionSpec { long: "density", short: None, value: OptionValue::Required, value_name: "<d>", help: "The proportion of 'live' cells, from 0 to 1 (default 0.5)" },
+            OptionSpec { long: "seed", short: None, value: OptionValue::Required, value_name: "<n>", help: "The seed from which the soups are generated" },
+            MAX_OPTION,
+            GAP_OPTION,
+            RULE_OPTION,
+            MASK_OPTION
+        ]
+    },
+    CommandSpec {
+        name: "edit",
+        summary: "Edit and play a pattern interactively, in a terminal - a new pattern if the file doesn't exist",
+        args: &["file-path"],
+        options: &[
+            OptionSpec { long: "size", short: None, value: OptionValue::Required, value_name: "<width>x<height>", help: "The size of a new pattern (default 40x20)" },
+            OptionSpec { long: "library", short: Some('l'), value: OptionValue::Required, value_name: "<life-file-path>", help: "A 'life' file of symbols to stamp" },
+            OptionSpec { long: "fps", short: None, value: OptionValue::Required, value_name: "<n>", help: "The generations per second when running (default 10)" },
+            RULE_OPTION,
+            MASK_OPTION,
+            MARGIN_OPTION
+        ]
+    },
+    CommandSpec {
+        name: "render",
+        summary: "Print a pattern, or write it as an image, after a number of generations",
+        args: &["input-file-path"],
+        options: &[
+            OptionSpec { long: "generations", short: Some('g'), value: OptionValue::Required, value_name: "<n>", help: "The number of generations to run first (default 0)" },
+            UNBOUNDED_OPTION,
+            OptionSpec { long: "image", short: None, value: OptionValue::Required, value_name: "<pbm-pgm-or-ppm-path>", help: "Write the pattern as a Netpbm image, instead of printing it" },
+            OptionSpec { long: "svg", short: None, value: OptionValue::Required, value_name: "<path>", help: "Write the pattern as an SVG image, instead of printing it" },
+            OptionSpec { long: "strip", short: None, value: OptionValue::Required, value_name: "<n>", help: "Show n successive generations side by side in the SVG (default 1)" },
+            OptionSpec { long: "labels", short: None, value: OptionValue::None, value_name: "", help: "Label the rows and columns of the SVG with their coordinates" },
+            OptionSpec { long: "highlight", short: None, value: OptionValue::None, value_name: "", help: "Highlight the cells born and dying in each generation of the SVG" },
+            OptionSpec { long: "html", short: None, value: OptionValue::Required, value_name: "<path>", help: "Write every generation up to '--generations' as a web page with a player" },
+            OptionSpec { long: "fps", short: None, value: OptionValue::Required, value_name: "<n>", help: "The frames per second of the web page's player (default 10)" },
+            PALETTE_OPTION,
+            CELL_SIZE_OPTION,
+            GRID_LINES_OPTION,
+            VIEWPORT_OPTION,
+            FOLLOW_OPTION,
+            ZOOM_OPTION,
+            STYLE_OPTION,
+            CHARS_OPTION,
+            RULE_OPTION,
+            MASK_OPTION,
+            MARGIN_OPTION
+        ]
+    }
+];
 
 
 fn main() {
-    // Expect 2 command-line arguments (excluding options) - so exit the
-    // program if the incorrect number of arguments are found...
-    let args = ArgsHelper::expect(2, "expected [-v] <input-file-path> <iterations>");
+    let args = ArgsHelper::init(COMMANDS);
+
+    match args.get_command() {
+        "convert" => convert(&args),
+        "analyse" => analyse(&args),
+        "report" => report(&args),
+        "search" => search(&args),
+        "edit" => edit(&args),
+        "render" => render(&args),
+        _ => run(&args)
+    }
+}
 
+/// Runs a pattern for a number of generations, printing the starting and
+/// final grids and whatever else is asked for.
+fn run(args: &ArgsHelper) {
     // Assign the given command-line arguments...
     let path = &args[0];
-    let is_debug = args.has_option("-d");
-    let is_verbose = args.has_option("-v");
+    let is_unbounded = args.has_option("unbounded");
     let cycles = args[1].parse::<usize>().unwrap_or_exit(format!("error: argument '{}' is not a valid iteration value", args[1]));
 
-    // Open the file containing the grid/cell info...
-    let mut parser = FileParser::from_path(path.as_str()).unwrap_or_exit(format!("error: cannot open file '{}'", path));
-    let mut cells = parser.iter();
-    
-    // Create an empty 'life' grid with the dimensions given in the file...
-    let (width, height) = cells.next().unwrap_or_exit(format!("error: cannot find width+height from file '{}'", path));
-    let mut life_grid = SimpleGrid::init_life(width, height);
+    // Load the grid from the file, along with any rule given in the
+    // file...
+    let (mut life_grid, file_rule) = load_pattern(path, get_margin(args));
 
-    // Loop through the cell info given in the file, setting a grid-cell to
-    // 'live' for each cell...
-    for (x, y) in cells {
-        life_grid.set_live(x, y);
-    }
+    let rule = get_rule(args, file_rule);
+    let renderer = get_renderer(args);
 
     // Print the starting grid...
-    println!("Starting:");
-    life_grid.write(&mut std::io::stdout());
-    
-    // Iterate for the given number of cycles...
-    for count in 1..=cycles {
-        let mut neighbours_grid = SimpleGrid::init(width, height, 0);
-        for cell in &life_grid {
-            neighbours_grid.set(cell.get_x(), cell.get_y(), cell.count_neighbours());
-        }
-
-        let mut new_grid = SimpleGrid::init_life(width, height);
-        for cell in &life_grid {
-            let neighbour_count = neighbours_grid[(cell.get_x(), cell.get_y())];
-            
-            if (cell.is_live()) {
-                if [2, 3].contains(&neighbour_count) {
-                    new_grid.set_live(cell.get_x(), cell.get_y());
-                }
+    println!("Starting ({}):", rule);
+    renderer.write(&life_grid, (0, 0), &mut std::io::stdout()).unwrap_or_exit(String::from("error: cannot write to standard output"));
+
+    // Write statistics of every generation, if asked...
+    let stats = args.get_option_value("stats").map(|path| {
+        StatsWriter::from_path(path).unwrap_or_exit(format!("error: cannot create file '{}'", path))
+    });
+
+    // Accumulate the activity of every cell, if asked - which needs the
+    // grid to stay the same size...
+    let is_heatmap = ["heatmap", "heatmap-pgm", "changes-pgm", "heatmap-ppm", "changes-ppm"].iter().any(|option| args.has_option(option));
+    if is_heatmap && is_unbounded {
+        env::exit_with_error(String::from("error: a heat-map cannot be made with option '--unbounded'"));
+    }
+
+    let heatmap = if is_heatmap { Some(HeatMap::new(life_grid.get_width(), life_grid.get_height())) } else { None };
+
+    // Show what changed in each generation, if asked...
+    let diff_style = match args.get_option_value("diff") {
+        Some("colour") | Some("color") => Some(DiffStyle::Colour),
+        Some("chars") => Some(DiffStyle::Characters),
+        Some(value) => {
+            env::exit_with_error(format!("error: option '--diff={}' should be 'chars' or 'colour'", value));
+            unreachable!()
+        },
+        None => if args.has_option("diff") { Some(DiffStyle::Characters) } else { None }
+    };
+
+    // Play the generations in place, if asked - which can't be combined
+    // with showing the changes...
+    if args.has_option("animate") && diff_style.is_some() {
+        env::exit_with_error(String::from("error: option '--animate' cannot be used with option '--diff'"));
+    }
+
+    let fps = if args.has_option("animate") {
+        match args.get_option_value("fps") {
+            Some(value) => Some(value.parse::<f64>().ok().filter(|fps| *fps > 0.0).unwrap_or_exit(format!("error: option '--fps={}' is not a valid frame rate", value))),
+            None => Some(10.0)
+        }
+    }
+    else {
+        None
+    };
+
+    // Record the generations as an animated GIF, if asked...
+    let gif = args.get_option_value("gif").map(|_| get_gif_recorder(args));
+
+    // Start the animation last, as it takes over the terminal - which must
+    // be restored before exiting on any later error...
+    let animation = fps.map(|fps| Animation::new(fps, renderer.clone(), std::io::stdout().is_terminal()));
+
+    let mut options = RunOptions {
+        cycles,
+        is_verbose: args.has_option("verbose"),
+        is_debug: args.has_option("debug"),
+        is_until_stable: args.has_option("until-stable"),
+        diff_style,
+        stats,
+        heatmap,
+        animation,
+        renderer,
+        gif
+    };
+
+    let final_grid = if is_unbounded {
+        run_unbounded(&life_grid, rule.as_ref(), &mut options)
+    }
+    else {
+        run_bounded(life_grid, rule.as_ref(), &mut options)
+    };
+
+    if let Some(stats) = options.stats.as_mut() {
+        stats.flush().unwrap_or_exit(String::from("error: cannot write statistics"));
+    }
+
+    if let Some(heatmap) = &options.heatmap {
+        write_heatmap(args, heatmap);
+    }
+
+    if let (Some(gif), Some(path)) = (&options.gif, args.get_option_value("gif")) {
+        if let Err(message) = gif.write_path(path) {
+            env::exit_with_error(format!("error: {}", message));
+        }
+    }
+
+    // Print a census of the objects in the ending grid...
+    if args.has_option("census") {
+        let census = Census::take(&final_grid.get_occupied_cells(), &Catalogue::builtin(), get_gap(args));
+        println!("Census ({} objects):", census.get_total());
+        print!("{}", census);
+    }
+}
+
+/// Prints and/or writes the maps of a heat-map, as asked.
+fn write_heatmap(args: &ArgsHelper, heatmap: &HeatMap) {
+    if args.has_option("heatmap") {
+        println!("Live heat-map ({} generations):", heatmap.get_generations());
+        HeatMap::shade(heatmap.get_live()).write(&mut std::io::stdout()).unwrap_or_exit(String::from("error: cannot write to standard output"));
+        println!("Changes heat-map ({} generations):", heatmap.get_generations());
+        HeatMap::shade(heatmap.get_changes()).write(&mut std::io::stdout()).unwrap_or_exit(String::from("error: cannot write to standard output"));
+    }
+
+    let (cell_size, grid_lines) = get_image_options(args, 1);
+    for (option, counts) in [("heatmap-pgm", heatmap.get_live()), ("changes-pgm", heatmap.get_changes())] {
+        if let Some(path) = args.get_option_value(option) {
+            let image = Image::from_counts(counts, &Palette::grey(Palette::GREY_LEVELS), cell_size, grid_lines);
+            let mut file = BufWriter::new(File::create(path).unwrap_or_exit(format!("error: cannot create file '{}'", path)));
+            image.write_pgm(&mut file).and_then(|_| file.flush()).unwrap_or_exit(format!("error: cannot write file '{}'", path));
+        }
+    }
+
+    for (option, counts) in [("heatmap-ppm", heatmap.get_live()), ("changes-ppm", heatmap.get_changes())] {
+        if let Some(path) = args.get_option_value(option) {
+            let image = Image::from_counts(counts, &Palette::heat(Palette::HEAT_LEVELS), cell_size, grid_lines);
+            if let Err(message) = image.write_path(path) {
+                env::exit_with_error(format!("error: {}", message));
             }
-            else if neighbour_count == 3 {
-                new_grid.set_live(cell.get_x(), cell.get_y());
+        }
+    }
+}
+
+/// Converts a pattern file to a 'life' file or, for a `.rle` path, an RLE
+/// file.
+fn convert(args: &ArgsHelper) {
+    let (path, output_path) = (&args[0], &args[1]);
+    let (life_grid, file_rule) = load_pattern(path, get_margin(args));
+    let rule = args.get_option_value("rule").map(String::from).or(file_rule);
+
+    let mut file = File::create(output_path).unwrap_or_exit(format!("error: cannot create file '{}'", output_path));
+    let result = if output_path.to_lowercase().ends_with(".rle") {
+        file::write_rle(&life_grid, rule.as_deref(), &mut file)
+    }
+    else {
+        file::write_life(&life_grid, &mut file)
+    };
+
+    if let Err(error) = result {
+        env::exit_with_error(format!("error: cannot write file '{}': {}", output_path, error));
+    }
+}
+
+/// Edits and plays a pattern interactively.
+/// 
+/// See `Editor`.
+fn edit(args: &ArgsHelper) {
+    let path = &args[0];
+    let (life_grid, file_rule) = if std::path::Path::new(path).exists() {
+        load_pattern(path, get_margin(args))
+    }
+    else {
+        let (width, height) = match args.get_option_value("size") {
+            Some(value) => parse_size(value).unwrap_or_exit(format!("error: option '--size={}' is not a valid size, e.g. 40x20", value)),
+            None => (40, 20)
+        };
+
+        (SimpleGrid::init_life(width, height), None)
+    };
+    let rule = get_rule(args, file_rule);
+
+    let fps = match args.get_option_value("fps") {
+        Some(value) => value.parse::<f64>().ok().filter(|fps| *fps > 0.0).unwrap_or_exit(format!("error: option '--fps={}' is not a valid frame rate", value)),
+        None => 10.0
+    };
+
+    let mut editor = Editor::new(life_grid, path);
+    if let Some(library) = args.get_option_value("library") {
+        let mut parser = FileParser::from_path(library).unwrap_or_exit(format!("error: cannot open file '{}'", library));
+        editor.set_symbols(parser.parse_symbols());
+    }
+
+    if let Err(message) = editor.run(rule.as_ref(), std::time::Duration::from_secs_f64(1.0 / fps)) {
+        env::exit_with_error(format!("error: {}", message));
+    }
+}
+
+/// Prints a pattern after a number of generations.
+fn render(args: &ArgsHelper) {
+    let path = &args[0];
+    let (life_grid, file_rule) = load_pattern(path, get_margin(args));
+    let rule = get_rule(args, file_rule);
+
+    let generations = match args.get_option_value("generations") {
+        Some(value) => value.parse::<usize>().unwrap_or_exit(format!("error: option '--generations={}' is not a valid number of generations", value)),
+        None => 0
+    };
+
+    let strip = match args.get_option_value("strip") {
+        Some(value) => value.parse::<usize>().ok().filter(|strip| *strip > 0).unwrap_or_exit(format!("error: option '--strip={}' is not a valid number of generations", value)),
+        None => 1
+    };
+
+    let outputs: Vec<&str> = ["image", "svg", "html"].into_iter().filter(|option| args.has_option(option)).collect();
+    if let [first, second, ..] = outputs[..] {
+        env::exit_with_error(format!("error: option '--{}' cannot be used with option '--{}'", first, second));
+    }
+
+    let renderer = get_renderer(args);
+    if let Some(html_path) = args.get_option_value("html") {
+        let frames = get_generations(&life_grid, rule.as_ref(), args.has_option("unbounded"), 0, generations + 1);
+        write_html(args, &renderer, &frames, html_path);
+        return;
+    }
+
+    if let Some(svg_path) = args.get_option_value("svg") {
+        // Run to the first generation shown - or the one before it, to
+        // highlight the changes to it...
+        let is_previous = args.has_option("highlight") && (generations > 0);
+        let first = generations - usize::from(is_previous);
+        let frames = get_generations(&life_grid, rule.as_ref(), args.has_option("unbounded"), first, strip + usize::from(is_previous));
+        write_svg(args, &renderer, &frames, generations, is_previous, svg_path);
+        return;
+    }
+
+    for option in ["strip", "labels", "highlight"].iter().filter(|option| args.has_option(option)) {
+        env::exit_with_error(format!("error: option '--{}' can only be used with option '--svg'", option));
+    }
+
+    let (life_grid, origin) = get_generations(&life_grid, rule.as_ref(), args.has_option("unbounded"), generations, 1).remove(0);
+    match args.get_option_value("image") {
+        Some(path) => write_image(args, &life_grid.crop(&renderer.get_view(&life_grid, origin), 0), path),
+        None => renderer.write(&life_grid, origin, &mut std::io::stdout()).unwrap_or_exit(String::from("error: cannot write to standard output"))
+    }
+}
+
+/// Gives the grids of the given number of successive generations of a
+/// pattern, from the given generation, with the origin of each.
+/// 
+/// In an unbounded universe, each grid is trimmed to the pattern (see
+/// `Universe`).
+fn get_generations(life_grid: &LifeGridType, rule: &dyn Rule, is_unbounded: bool, first: usize, count: usize) -> Vec<(LifeGridType, (isize, isize))> {
+    let mut frames = Vec::with_capacity(count);
+    if is_unbounded {
+        let mut universe = Universe::from_grid(life_grid);
+        for generation in 0..(first + count) {
+            if generation > 0 {
+                universe.step(rule);
+            }
+
+            if generation >= first {
+                frames.push((universe.get_grid().clone(), universe.get_origin()));
+            }
+        }
+    }
+    else {
+        let mut grid = life_grid.clone();
+        for generation in 0..(first + count) {
+            if generation > 0 {
+                grid = rule.step(&grid);
+            }
+
+            if generation >= first {
+                frames.push((grid.clone(), (0, 0)));
             }
-        }        
+        }
+    }
+
+    frames
+}
+
+/// Writes successive generations as an SVG image, showing the same region
+/// of each - the region covering what would be printed of each.
+/// 
+/// The first generation is only used to highlight the changes to the
+/// next, if asked. See `SvgRenderer`.
+fn write_svg(args: &ArgsHelper, renderer: &Renderer, frames: &[(LifeGridType, (isize, isize))], generation: usize, is_previous: bool, path: &str) {
+    let (grids, region) = get_common_region(renderer, frames);
+    let (previous, grids) = if is_previous { (grids.first(), &grids[1..]) } else { (None, &grids[..]) };
+
+    let mut svg = SvgRenderer::new();
+    let (cell_size, grid_lines) = get_image_options(args, 10);
+    svg.set_cell_size(cell_size);
+    svg.set_grid_lines(grid_lines);
+    svg.set_labelled(args.has_option("labels"));
+    svg.set_highlighting(args.has_option("highlight"));
+    svg.set_palette(get_palette(args).unwrap_or_else(|| get_default_palette(grids)));
+
+    if let Err(message) = svg.write_path(path, generation, grids, previous, (region.x, region.y)) {
+        env::exit_with_error(format!("error: {}", message));
+    }
+}
+
+/// Writes the generations from 0 as a web page with a player, showing the
+/// same region of each - the region covering what would be printed of
+/// each.
+/// 
+/// See `HtmlPlayer`.
+fn write_html(args: &ArgsHelper, renderer: &Renderer, frames: &[(LifeGridType, (isize, isize))], path: &str) {
+    let (grids, _) = get_common_region(renderer, frames);
+
+    let mut player = HtmlPlayer::new(&args[0]);
+    let (cell_size, grid_lines) = get_image_options(args, 10);
+    player.set_cell_size(cell_size);
+    player.set_grid_lines(grid_lines);
+    player.set_palette(get_palette(args).unwrap_or_else(|| get_default_palette(&grids)));
+
+    if let Some(value) = args.get_option_value("fps") {
+        player.set_fps(value.parse::<f64>().ok().filter(|fps| *fps > 0.0).unwrap_or_exit(format!("error: option '--fps={}' is not a valid frame rate", value)));
+    }
+
+    if let Err(message) = player.write_path(path, 0, &grids) {
+        env::exit_with_error(format!("error: {}", message));
+    }
+}
+
+/// Gives the grids of the same region of each generation - the region
+/// covering the view of each (see `Renderer::get_view()`) - and the region.
+fn get_common_region(renderer: &Renderer, frames: &[(LifeGridType, (isize, isize))]) -> (Vec<LifeGridType>, BoundingBox) {
+    let region = frames.iter()
+        .map(|(grid, (x, y))| renderer.get_view(grid, (*x, *y)).translate(*x, *y))
+        .reduce(|region, view| region.union(&view))
+        .unwrap_or(BoundingBox::new(0, 0, 0, 0));
+
+    (frames.iter().map(|(grid, (x, y))| grid.crop(&region.translate(-x, -y), 0)).collect(), region)
+}
+
+/// Gives the default colours of the states in images of the given grids.
+/// 
+/// See `Palette::default_states()`.
+fn get_default_palette(grids: &[LifeGridType]) -> Palette {
+    Palette::default_states(grids.iter().flat_map(|grid| grid.iter().map(|cell| cell.get().get() + 1)).max().unwrap_or(0).max(2))
+}
+
+/// Writes a grid as a PBM, PGM or PPM image.
+/// 
+/// See `Image`.
+fn write_image(args: &ArgsHelper, grid: &LifeGridType, path: &str) {
+    let palette = get_palette(args).unwrap_or_else(|| get_default_palette(std::slice::from_ref(grid)));
+
+    let (cell_size, grid_lines) = get_image_options(args, 1);
+    if let Err(message) = Image::from_grid(grid, &palette, cell_size, grid_lines).write_path(path) {
+        env::exit_with_error(format!("error: {}", message));
+    }
+}
+
+/// Gives the size of each cell in an image - the given size by default -
+/// and the colour of any grid lines.
+fn get_image_options(args: &ArgsHelper, default_cell_size: usize) -> (usize, Option<Colour>) {
+    let cell_size = match args.get_option_value("cell-size") {
+        Some(value) => value.parse::<usize>().ok().filter(|size| *size > 0).unwrap_or_exit(format!("error: option '--cell-size={}' is not a valid cell size", value)),
+        None => default_cell_size
+    };
+
+    (cell_size, if args.has_option("grid-lines") { Some(image::GRID_LINE_COLOUR) } else { None })
+}
+
+/// Gives the colours of the states in an image, if given.
+fn get_palette(args: &ArgsHelper) -> Option<Palette> {
+    args.get_option_value("palette").map(|value| {
+        Palette::parse(value).unwrap_or_exit(format!("error: option '--palette={}' is not a valid palette, e.g. 'white,black,#ff0000'", value))
+    })
+}
+
+/// Gives the recorder of the generations to write as an animated GIF.
+/// 
+/// See `GifRecorder`.
+fn get_gif_recorder(args: &ArgsHelper) -> GifRecorder {
+    let mut gif = GifRecorder::new();
+    if let Some(value) = args.get_option_value("gif-every") {
+        gif.set_every(value.parse::<usize>().ok().filter(|every| *every > 0).unwrap_or_exit(format!("error: option '--gif-every={}' is not a valid number of generations", value)));
+    }
+
+    if let Some(value) = args.get_option_value("gif-delay") {
+        gif.set_delay(value.parse::<u32>().unwrap_or_exit(format!("error: option '--gif-delay={}' is not a valid delay, in milliseconds", value)));
+    }
+
+    if let Some(palette) = get_palette(args) {
+        gif.set_palette(palette);
+    }
+
+    let (cell_size, grid_lines) = get_image_options(args, 1);
+    gif.set_cell_size(cell_size);
+    gif.set_grid_lines(grid_lines);
+    gif.set_cropped(args.has_option("gif-crop"));
+    gif
+}
+
+/// Prints an analysis of the symmetries of a pattern.
+/// 
+/// See `symmetry::analyse()`.
+fn analyse(args: &ArgsHelper) {
+    let path = &args[0];
+    let (life_grid, file_rule) = load_pattern(path, 0);
+    let rule = get_rule(args, file_rule);
+
+    let max_generations = match args.get_option_value("max") {
+        Some(value) => value.parse::<usize>().unwrap_or_exit(format!("error: option '--max={}' is not a valid number of generations", value)),
+        None => 1000
+    };
+
+    println!("Pattern: {} ({})", path, rule);
+    print!("{}", symmetry::analyse(&life_grid, rule.as_ref(), max_generations));
+}
+
+/// Runs a pattern until it stabilises, printing its lifespan, population
+/// and what it leaves.
+/// 
+/// See `Methuselah`.
+fn report(args: &ArgsHelper) {
+    let path = &args[0];
+    let (life_grid, file_rule) = load_pattern(path, 0);
+    let rule = get_rule(args, file_rule);
+
+    let mut methuselah = Methuselah::new();
+    methuselah.set_gap(get_gap(args));
+
+    if let Some(value) = args.get_option_value("max") {
+        methuselah.set_max_generations(value.parse::<usize>().unwrap_or_exit(format!("error: option '--max={}' is not a valid number of generations", value)));
+    }
+
+    println!("Pattern: {} ({})", path, rule);
+    print!("{}", methuselah.run(&life_grid, rule.as_ref(), &Catalogue::builtin()));
+}
+
+/// Searches random soups, printing a report of the objects they leave.
+/// 
+/// See `SoupSearch`.
+fn search(args: &ArgsHelper) {
+    let soups = args[0].parse::<usize>().unwrap_or_exit(format!("error: argument '{}' is not a valid number of soups", args[0]));
+
+    let (width, height) = match args.get_option_value("size") {
+        Some(value) => parse_size(value).unwrap_or_exit(format!("error: option '--size={}' is not a valid size, e.g. 16x16", value)),
+        None => (16, 16)
+    };
+
+    let mut search = SoupSearch::new(width, height);
+    search.set_gap(get_gap(args));
+
+    if let Some(value) = args.get_option_value("density") {
+        let density = value.parse::<f64>().ok().filter(|density| (0.0..=1.0).contains(density));
+        search.set_density(density.unwrap_or_exit(format!("error: option '--density={}' is not a valid density, from 0 to 1", value)));
+    }
+
+    if let Some(value) = args.get_option_value("seed") {
+        search.set_seed(value.parse::<u64>().unwrap_or_exit(format!("error: option '--seed={}' is not a valid seed value", value)));
+    }
+
+    if let Some(value) = args.get_option_value("max") {
+        search.set_max_generations(value.parse::<usize>().unwrap_or_exit(format!("error: option '--max={}' is not a valid number of generations", value)));
+    }
+
+    let rule = get_rule(args, None);
+    println!("Rule: {}", rule);
+    print!("{}", search.run(soups, rule.as_ref(), &Catalogue::builtin()));
+}
+
+/// Gives the rule to use.
+/// 
+/// A rule given on the command-line takes priority over one given in the
+/// file, and Conway's Game of Life is the default.
+fn get_rule(args: &ArgsHelper, file_rule: Option<String>) -> Box<dyn Rule> {
+    // A custom neighbourhood mask is used unless the rule has its own
+    // neighbourhood suffix...
+    let neighbourhood = match args.get_option_value("mask") {
+        Some(mask_path) => load_mask(mask_path),
+        None => Neighbourhood::default()
+    };
+
+    let rulestring = match args.get_option_value("rule").map(String::from).or(file_rule) {
+        Some(rulestring) => rulestring,
+        None => {
+            let mut rule = LifeRule::default();
+            rule.set_neighbourhood(neighbourhood);
+            return Box::new(rule);
+        }
+    };
+
+    let is_table = rulestring.to_lowercase().ends_with(".rule");
+    if args.has_option("mask") && (is_table || !rule::takes_neighbourhood(&rulestring)) {
+        env::exit_with_error(format!("error: the rule '{}' has its own neighbourhood, so it can't have a mask", rulestring));
+    }
+
+    if is_table {
+        load_table(&rulestring)
+    }
+    else {
+        rule::parse_rule(&rulestring, neighbourhood).unwrap_or_exit(format!("error: '{}' is not a valid rule", rulestring))
+    }
+}
+
+/// Gives the gap used to separate objects for a census.
+/// 
+/// See `census::separate()`.
+fn get_gap(args: &ArgsHelper) -> usize {
+    match args.get_option_value("gap") {
+        Some(value) => value.parse::<usize>().unwrap_or_exit(format!("error: option '--gap={}' is not a valid gap value", value)),
+        None => 0
+    }
+}
+
+/// Gives the renderer of the part of each grid to print.
+/// 
+/// See `Renderer`.
+fn get_renderer(args: &ArgsHelper) -> Renderer {
+    let mut renderer = Renderer::new();
+    if let Some(value) = args.get_option_value("viewport") {
+        renderer.set_viewport(Renderer::parse_viewport(value).unwrap_or_exit(format!("error: option '--viewport={}' is not a valid viewport, e.g. 0,0,80x40", value)));
+    }
+
+    if let Some(value) = args.get_option_value("zoom") {
+        renderer.set_zoom(value.parse::<usize>().ok().filter(|zoom| *zoom > 0).unwrap_or_exit(format!("error: option '--zoom={}' is not a valid zoom", value)));
+    }
+
+    if let Some(value) = args.get_option_value("style") {
+        renderer.set_style(RenderStyle::parse(value).unwrap_or_exit(format!("error: option '--style={}' should be 'ascii', 'box', 'braille' or 'halfblock'", value)));
+    }
+
+    if let Some(value) = args.get_option_value("chars") {
+        let chars: Vec<char> = value.chars().collect();
+        match chars[..] {
+            [live, dead] => renderer.set_chars(live, dead),
+            _ => env::exit_with_error(format!("error: option '--chars={}' should be two characters, for 'live' and 'dead' cells", value))
+        }
+    }
+
+    renderer.set_following(args.has_option("follow"));
+    renderer
+}
+
+/// Parses a size given as `<width>x<height>`.
+fn parse_size(value: &str) -> Option<(usize, usize)> {
+    value.split_once('x').and_then(|(width, height)| Some((width.parse::<usize>().ok()?, height.parse::<usize>().ok()?)))
+}
+
+/// Gives the size of the empty margin around an RLE pattern.
+fn get_margin(args: &ArgsHelper) -> usize {
+    match args.get_option_value("margin") {
+        Some(value) => value.parse::<usize>().unwrap_or_exit(format!("error: option '--margin={}' is not a valid margin value", value)),
+        None => 0
+    }
+}
+
+/// Options for iterating a grid.
+struct RunOptions {
+    cycles: usize,
+    is_verbose: bool,
+    is_debug: bool,
+    is_until_stable: bool,
+    diff_style: Option<DiffStyle>,
+    stats: Option<StatsWriter>,
+    heatmap: Option<HeatMap>,
+    animation: Option<Animation>,
+    renderer: Renderer,
+    gif: Option<GifRecorder>
+}
+
+/// Iterates a grid for the given number of cycles, or until the pattern is
+/// stable, with cells beyond the edges of the grid always 'dead'.
+/// 
+/// Gives the final grid.
+fn run_bounded(mut life_grid: LifeGridType, rule: &dyn Rule, options: &mut RunOptions) -> LifeGridType {
+    // Watch for the pattern entering a cycle, if asked...
+    let mut detector = if options.is_until_stable { Some(PeriodDetector::new()) } else { None };
+    if let Some(detector) = detector.as_mut() {
+        detector.push(0, &life_grid);
+    }
+    write_stats(options, GenerationStats::from_grid(0, &life_grid));
+    if let Some(heatmap) = options.heatmap.as_mut() {
+        heatmap.add(&life_grid);
+    }
+
+    if let Some(gif) = options.gif.as_mut() {
+        gif.add(0, &life_grid, (0, 0));
+    }
+
+    // Iterate for the given number of cycles, or until the pattern is
+    // stable (or the animation is stopped)...
+    let mut generation = 0;
+    let is_playing = show_frame(options, 0, &life_grid, (0, 0));
+    for count in (1..=options.cycles).take_while(|_| is_playing) {
+        let neighbours_grid = rule.count_neighbours(&life_grid);
+        let new_grid = rule.apply(&life_grid, &neighbours_grid);
     
         // DEBUG: Print the neighbour-count grid...
-        if is_debug {
-            neighbours_grid.write(&mut std::io::stdout());
+        if options.is_debug {
+            let result = neighbours_grid.write(&mut std::io::stdout());
+            check_output(options, result);
         }
 
-        if is_verbose {
+        if let Some(style) = options.diff_style {
+            let result = print_diff(count, &GridDiff::between(&life_grid, &new_grid), &new_grid, style);
+            check_output(options, result);
+        }
+        else if options.is_verbose && options.animation.is_none() {
             println!("iteration: {}", count);
-            new_grid.write(&mut std::io::stdout());
+            let result = options.renderer.write(&new_grid, (0, 0), &mut std::io::stdout());
+            check_output(options, result);
+        }
+
+        write_stats(options, GenerationStats::from_grids(count, &life_grid, &new_grid));
+        if let Some(heatmap) = options.heatmap.as_mut() {
+            heatmap.add(&new_grid);
+        }
+
+        if let Some(gif) = options.gif.as_mut() {
+            gif.add(count, &new_grid, (0, 0));
         }
 
         // Swap to the new grid...
         life_grid = new_grid;
+        generation = count;
+
+        if !show_frame(options, count, &life_grid, (0, 0)) {
+            break;
+        }
+
+        if let Some(detector) = detector.as_mut() {
+            if detector.push(count, &life_grid).is_some() {
+                break;
+            }
+        }
     }
 
+    finish_animation(options);
+
     // Print the ending grid...
-    println!("Final iteration {}:", cycles);
-    life_grid.write(&mut std::io::stdout());
+    println!("Final iteration {}:", generation);
+    options.renderer.write(&life_grid, (0, 0), &mut std::io::stdout()).unwrap_or_exit(String::from("error: cannot write to standard output"));
+
+    if let Some(detector) = detector {
+        print_stabilisation(detector.get_result(), generation);
+    }
+
+    life_grid
+}
+
+/// Iterates a grid for the given number of cycles, or until the pattern is
+/// stable, in an unbounded universe - the pattern may grow or travel beyond
+/// the edges of the grid.
+/// 
+/// Gives the final pattern, trimmed to its bounding box.
+fn run_unbounded(life_grid: &LifeGridType, rule: &dyn Rule, options: &mut RunOptions) -> LifeGridType {
+    let mut universe = Universe::from_grid(life_grid);
+
+    // Watch for the pattern entering a cycle, possibly while moving, if
+    // asked...
+    let mut detector = if options.is_until_stable { Some(MotionDetector::new()) } else { None };
+    if let Some(detector) = detector.as_mut() {
+        detector.push(0, &universe);
+    }
+    write_stats(options, GenerationStats::from_universes(0, &universe, &universe));
+    if let Some(gif) = options.gif.as_mut() {
+        gif.add(0, universe.get_grid(), universe.get_origin());
+    }
+
+    let mut generation = 0;
+    let is_playing = show_frame(options, 0, universe.get_grid(), universe.get_origin());
+    for count in (1..=options.cycles).take_while(|_| is_playing) {
+        if options.stats.is_some() || options.diff_style.is_some() {
+            let previous = universe.clone();
+            universe.step(rule);
+            write_stats(options, GenerationStats::from_universes(count, &previous, &universe));
+
+            // Show the changes over the region covering both generations...
+            if let Some(style) = options.diff_style {
+                let region = match (previous.get_bounding_box(), universe.get_bounding_box()) {
+                    (Some(before), Some(after)) => before.union(&after),
+                    (before, after) => before.or(after).unwrap_or(BoundingBox::new(0, 0, 0, 0))
+                };
+
+                let current = universe.get_region(&region);
+                let result = print_diff(count, &GridDiff::between(&previous.get_region(&region), &current), &current, style);
+                check_output(options, result);
+            }
+        }
+        else {
+            universe.step(rule);
+        }
+
+        generation = count;
+
+        if let Some(gif) = options.gif.as_mut() {
+            gif.add(count, universe.get_grid(), universe.get_origin());
+        }
+
+        if options.is_verbose && options.diff_style.is_none() && options.animation.is_none() {
+            let (x, y) = universe.get_origin();
+            println!("iteration: {} (at {},{})", count, x, y);
+            let result = options.renderer.write(universe.get_grid(), (x, y), &mut std::io::stdout());
+            check_output(options, result);
+        }
+
+        if !show_frame(options, count, universe.get_grid(), universe.get_origin()) {
+            break;
+        }
+
+        if let Some(detector) = detector.as_mut() {
+            if detector.push(count, &universe).is_some() {
+                break;
+            }
+        }
+    }
+
+    finish_animation(options);
+
+    // Print the ending pattern, trimmed to its bounding box...
+    let (x, y) = universe.get_origin();
+    println!("Final iteration {} (at {},{}):", generation, x, y);
+    options.renderer.write(universe.get_grid(), (x, y), &mut std::io::stdout()).unwrap_or_exit(String::from("error: cannot write to standard output"));
+
+    if let Some(detector) = detector {
+        print_stabilisation(detector.get_result(), generation);
+    }
+
+    universe.into_grid()
+}
+
+/// Shows a generation in the animation, if animating.
+/// 
+/// Gives `false` if the animation has been stopped.
+fn show_frame(options: &mut RunOptions, generation: usize, grid: &LifeGridType, origin: (isize, isize)) -> bool {
+    let result = match options.animation.as_mut() {
+        Some(animation) => animation.show(generation, grid, origin, &mut std::io::stdout()),
+        None => Ok(true)
+    };
+
+    match result {
+        Ok(is_playing) => is_playing,
+        Err(_) => {
+            exit_run_with_error(options, String::from("error: cannot write to standard output"));
+            false
+        }
+    }
+}
+
+/// Ends the animation, if animating, restoring the terminal.
+fn finish_animation(options: &mut RunOptions) {
+    if let Some(animation) = options.animation.as_mut() {
+        let result = animation.finish(&mut std::io::stdout());
+        check_output(options, result);
+    }
+}
+
+/// Prints a generation with its births and deaths marked.
+fn print_diff(generation: usize, diff: &GridDiff, grid: &LifeGridType, style: DiffStyle) -> std::io::Result<()> {
+    println!("iteration: {} (+{} -{})", generation, diff.get_births().len(), diff.get_deaths().len());
+    diff.write(grid, style, &mut std::io::stdout())
+}
+
+/// Writes the statistics of a generation, if they're being written.
+fn write_stats(options: &mut RunOptions, generation_stats: GenerationStats) {
+    if let Some(stats) = options.stats.as_mut() {
+        if stats.write(&generation_stats).is_err() {
+            exit_run_with_error(options, String::from("error: cannot write statistics"));
+        }
+    }
+}
+
+/// Exits part-way through a run if its output couldn't be written - e.g.
+/// to a closed pipe.
+fn check_output(options: &mut RunOptions, result: std::io::Result<()>) {
+    if result.is_err() {
+        exit_run_with_error(options, String::from("error: cannot write to standard output"));
+    }
+}
+
+/// Exits with an error part-way through a run, first restoring the terminal
+/// if animating.
+fn exit_run_with_error(options: &mut RunOptions, message: String) {
+    if let Some(animation) = options.animation.as_mut() {
+        animation.finish(&mut std::io::stdout()).ok();
+    }
+
+    env::exit_with_error(message);
+}
+
+/// Prints how a pattern has stabilised, if it has.
+fn print_stabilisation(stabilisation: Option<Stabilisation>, generation: usize) {
+    match stabilisation {
+        Some(stabilisation) => println!("{}", stabilisation),
+        None => println!("not stabilised after {} generations", generation)
+    }
+}
+
+/// Loads a grid from a 'life' file or, for a `.rle` path, an RLE file with
+/// an empty margin of the given size around the pattern.
+/// 
+/// Also gives the rule from the file, if any.
+fn load_pattern(path: &str, margin: usize) -> (LifeGridType, Option<String>) {
+    if path.to_lowercase().ends_with(".rle") {
+        load_rle(path, margin)
+    }
+    else {
+        (load_life(path), None)
+    }
+}
+
+/// Loads a grid from a 'life' file.
+fn load_life(path: &str) -> LifeGridType {
+    // Open the file containing the grid/cell info...
+    let mut parser = FileParser::from_path(path).unwrap_or_exit(format!("error: cannot open file '{}'", path));
+    let mut cells = parser.iter();
+    
+    // Create an empty 'life' grid with the dimensions given in the file...
+    let (width, height) = cells.next().unwrap_or_exit(format!("error: cannot find width+height from file '{}'", path));
+    let mut life_grid = SimpleGrid::init_life(width, height);
+
+    // Loop through the cell info given in the file, setting a grid-cell to
+    // 'live' for each cell...
+    for (x, y) in cells {
+        life_grid.set_live(x, y);
+    }
+
+    life_grid
+}
+
+/// Loads a grid from an RLE file, with an empty margin of the given size
+/// around the pattern.
+/// 
+/// Also gives the rule from the file's header, if any.
+fn load_rle(path: &str, margin: usize) -> (LifeGridType, Option<String>) {
+    let mut parser = RleParser::from_path(path).unwrap_or_exit(format!("error: cannot open file '{}'", path));
+    let pattern = parser.parse();
+
+    let mut life_grid = SimpleGrid::init_life(pattern.get_width() + (2 * margin), pattern.get_height() + (2 * margin));
+    for (x, y, state) in pattern.get_cells() {
+        life_grid.set_state(x + margin, y + margin, *state);
+    }
+
+    (life_grid, pattern.get_rule())
+}
+
+/// Loads a custom neighbourhood from a mask file.
+/// 
+/// See `Neighbourhood::parse_mask()`.
+fn load_mask(path: &str) -> Neighbourhood {
+    let buffer = ReadBuffer::from_path(path).unwrap_or_exit(format!("error: cannot open file '{}'", path));
+    let mask = String::from_utf8_lossy(buffer.as_slice());
+
+    match Neighbourhood::parse_mask(&mask) {
+        Ok(neighbourhood) => neighbourhood,
+        Err(message) => {
+            env::exit_with_error(format!("error: {}, in file '{}'", message, path));
+            unreachable!()
+        }
+    }
+}
+
+/// Loads a rule from a rule table file.
+/// 
+/// See `TableRule::parse()`.
+fn load_table(path: &str) -> Box<dyn Rule> {
+    let buffer = ReadBuffer::from_path(path).unwrap_or_exit(format!("error: cannot open file '{}'", path));
+    let contents = String::from_utf8_lossy(buffer.as_slice());
+
+    match TableRule::parse(&contents) {
+        Ok(mut table) => {
+            table.set_path(path);
+            Box::new(table)
+        },
+        Err(message) => {
+            env::exit_with_error(format!("error: {}, in file '{}'", message, path));
+            unreachable!()
+        }
+    }
 }
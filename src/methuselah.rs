@@ -0,0 +1,253 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::fmt::Display;
+
+use crate::census::{Catalogue, Census, OccupiedCells, find_escapees};
+use crate::life::LifeGridType;
+use crate::period::MotionDetector;
+use crate::rule::Rule;
+use crate::universe::Universe;
+
+
+/// Runs a pattern in an unbounded universe until it stabilises.
+/// 
+/// A pattern is stable when the whole pattern repeats, possibly displaced
+/// (see `MotionDetector`), or when its population has been periodic for a
+/// while - which allows for spaceships escaping in different directions.
+/// Spaceships that have escaped from the rest of the pattern (see
+/// `find_escapees()`) are removed as they go, so that the universe doesn't
+/// keep growing, but are still counted in the population.
+pub struct Methuselah {
+    max_generations: usize,
+    gap: usize
+}
+
+impl Methuselah {
+    /// The longest population period treated as stable - enough for
+    /// mixtures of period 1, 2, 3 & 4 objects.
+    const MAX_POPULATION_PERIOD: usize = 12;
+
+    /// The number of generations for which the population must have been
+    /// periodic.
+    const POPULATION_WINDOW: usize = 100;
+
+    /// The number of generations between looking for escaping spaceships.
+    const ESCAPE_INTERVAL: usize = 50;
+
+    /// The number of empty cells between an escaping spaceship and every
+    /// other object.
+    const ESCAPE_DISTANCE: usize = 16;
+
+    /// Initialises a runner with a limit of 10,000 generations.
+    pub fn new() -> Self {
+        Self {
+            max_generations: 10_000,
+            gap: 0
+        }
+    }
+
+    /// Sets the number of generations after which a pattern that hasn't
+    /// stabilised is abandoned.
+    pub fn set_max_generations(&mut self, max_generations: usize) {
+        self.max_generations = max_generations;
+    }
+
+    /// Sets the gap used to separate objects (see `separate()`).
+    pub fn set_gap(&mut self, gap: usize) {
+        self.gap = gap;
+    }
+
+    /// Runs the pattern in the given grid until it stabilises, or for the
+    /// maximum number of generations, and takes a census of what it leaves.
+    pub fn run(&self, grid: &LifeGridType, rule: &dyn Rule, catalogue: &Catalogue) -> MethuselahReport {
+        let mut universe = Universe::from_grid(grid);
+        let mut detector = MotionDetector::new();
+        let mut escapees: Vec<String> = Vec::new();
+
+        // The population includes the spaceships that have been removed...
+        let mut escaped_population = 0;
+        let mut populations = vec![universe.get_population()];
+
+        detector.push(0, &universe);
+        let mut lifespan = None;
+        for generation in 1..=self.max_generations {
+            universe.step(rule);
+
+            // Removing spaceships changes the pattern, so start watching for
+            // it repeating again...
+            if generation % Self::ESCAPE_INTERVAL == 0 {
+                let removed = self.remove_escapees(&mut universe, catalogue, &mut escapees);
+                if removed > 0 {
+                    escaped_population += removed;
+                    detector = MotionDetector::new();
+                }
+            }
+
+            populations.push(universe.get_population() + escaped_population);
+
+            if let Some(stabilisation) = detector.push(generation, &universe) {
+                lifespan = Some(stabilisation.get_generation());
+                break;
+            }
+
+            if let Some(period) = Self::get_population_period(&populations) {
+                lifespan = Some(Self::get_periodic_start(&populations, period));
+                break;
+            }
+        }
+
+        // Spaceships may have escaped since they were last looked for...
+        escaped_population += self.remove_escapees(&mut universe, catalogue, &mut escapees);
+
+        let mut census = Census::new();
+        for name in &escapees {
+            census.add(name, 1);
+        }
+
+        census.merge(&Census::take(&universe.get_occupied_cells(), catalogue, self.gap));
+
+        let (max_generation, max_population) = populations.iter()
+            .enumerate()
+            .fold((0, 0), |max, (generation, population)| if *population > max.1 { (generation, *population) } else { max });
+
+        MethuselahReport {
+            lifespan,
+            generations: populations.len() - 1,
+            max_population,
+            max_generation,
+            final_population: universe.get_population() + escaped_population,
+            escaped_population,
+            escapees,
+            census,
+            universe
+        }
+    }
+
+    /// Removes the spaceships that have escaped, adding their names to the
+    /// given list.
+    /// 
+    /// Gives the population removed.
+    fn remove_escapees(&self, universe: &mut Universe, catalogue: &Catalogue, escapees: &mut Vec<String>) -> usize {
+        let mut removed = 0;
+        for (object, name) in find_escapees(&universe.get_occupied_cells(), catalogue, self.gap, Self::ESCAPE_DISTANCE) {
+            let cells: Vec<(isize, isize)> = object.get_cells().iter().map(|(x, y, _)| (*x, *y)).collect();
+            universe.remove(&cells);
+            removed += object.get_population();
+            escapees.push(name);
+        }
+
+        removed
+    }
+
+    /// Gives the period of the most recent populations, if they have been
+    /// periodic for long enough.
+    fn get_population_period(populations: &[usize]) -> Option<usize> {
+        if populations.len() < Self::POPULATION_WINDOW + Self::MAX_POPULATION_PERIOD {
+            return None;
+        }
+
+        let end = populations.len();
+        (1..=Self::MAX_POPULATION_PERIOD).find(|period| {
+            ((end - Self::POPULATION_WINDOW)..end).all(|index| populations[index] == populations[index - period])
+        })
+    }
+
+    /// Gives the first generation from which the populations are periodic
+    /// with the given period.
+    fn get_periodic_start(populations: &[usize], period: usize) -> usize {
+        let mut start = populations.len() - period;
+        while (start > 0) && (populations[start - 1] == populations[start - 1 + period]) {
+            start -= 1;
+        }
+
+        start
+    }
+}
+
+impl Default for Methuselah {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+//---------------------------------------------------------------------------//
+
+
+/// The results of running a pattern until it stabilises.
+/// 
+/// See `Methuselah::run()`.
+pub struct MethuselahReport {
+    /// The generation from which the pattern is stable, if it stabilised.
+    pub lifespan: Option<usize>,
+
+    /// The number of generations run.
+    pub generations: usize,
+
+    /// The greatest population, including spaceships that had escaped.
+    pub max_population: usize,
+
+    /// The generation at which the population was greatest.
+    pub max_generation: usize,
+
+    /// The final population, including spaceships that had escaped.
+    pub final_population: usize,
+
+    /// The population of the spaceships that escaped.
+    pub escaped_population: usize,
+
+    /// The names of the spaceships that escaped, and were removed from the
+    /// universe.
+    pub escapees: Vec<String>,
+
+    /// The census of the objects left, including those that escaped.
+    pub census: Census,
+
+    /// The final universe.
+    pub universe: Universe
+}
+
+impl Display for MethuselahReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.lifespan {
+            Some(lifespan) => writeln!(f, "Lifespan: {} generations", lifespan)?,
+            None => writeln!(f, "Lifespan: not stabilised after {} generations", self.generations)?
+        }
+
+        writeln!(f, "Maximum population: {} at generation {}", self.max_population, self.max_generation)?;
+        writeln!(f, "Final population: {} ({} escaped)", self.final_population, self.escaped_population)?;
+
+        let gliders = self.escapees.iter().filter(|name| *name == "glider").count();
+        writeln!(f, "Escaping gliders: {}", gliders)?;
+        if self.escapees.len() > gliders {
+            writeln!(f, "Escaping spaceships (other): {}", self.escapees.len() - gliders)?;
+        }
+
+        writeln!(f, "Census ({} objects):", self.census.get_total())?;
+        write!(f, "{}", self.census)
+    }
+}
@@ -0,0 +1,216 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::fmt::Display;
+
+
+
+/// An offset of a neighbouring cell, relative to the cell itself.
+pub type Offset = (isize, isize);
+
+/// The set of cells that count as a cell's neighbours.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// All cells within a square of the given radius - radius 1 gives the
+    /// usual 8 neighbours.
+    Moore(usize),
+
+    /// All cells within the given Manhattan distance - radius 1 gives the 4
+    /// orthogonal neighbours.
+    VonNeumann(usize),
+
+    /// The 6 neighbours of a hexagonal grid, skewed onto a square grid by
+    /// leaving out the top-right and bottom-left Moore neighbours.
+    Hexagonal,
+
+    /// A user-supplied set of offsets.
+    Custom(Vec<Offset>)
+}
+
+impl Neighbourhood {
+    /// Parses a rulestring suffix that selects a neighbourhood.
+    /// 
+    /// `M`, `V` & `H` give the Moore, von Neumann & hexagonal
+    /// neighbourhoods. `M` & `V` may be followed by a radius, e.g. `M2`. `@`
+    /// followed by hex digits gives a custom neighbourhood, as written by
+    /// `Display`.
+    pub fn parse_suffix(suffix: &str) -> Option<Self> {
+        if let Some(hex) = suffix.strip_prefix('@') {
+            return Self::parse_hex(hex);
+        }
+
+        let mut chars = suffix.chars();
+        let kind = chars.next()?.to_ascii_uppercase();
+        let radius = chars.as_str();
+        let radius = if radius.is_empty() { Some(1) } else { radius.parse::<usize>().ok().filter(|r| *r > 0) };
+
+        match (kind, radius) {
+            ('M', Some(radius)) => Some(Self::Moore(radius)),
+            ('V', Some(radius)) => Some(Self::VonNeumann(radius)),
+            ('H', _) if suffix.len() == 1 => Some(Self::Hexagonal),
+            _ => None
+        }
+    }
+
+    /// Parses a custom neighbourhood from a picture of a mask.
+    /// 
+    /// The mask is a square with an odd number of rows of equal, odd length.
+    /// The centre of the square is the cell itself - any other `*`, `1`,
+    /// `x` or `o` character is a neighbour, and any `.`, `0` or space is not.
+    /// Lines starting with `#` are comments.
+    pub fn parse_mask(mask: &str) -> Result<Self, String> {
+        let rows: Vec<&str> = mask.lines()
+                                .map(|row| row.trim_end_matches('\r'))
+                                .filter(|row| !row.trim().is_empty() && !row.trim_start().starts_with('#'))
+                                .collect();
+        let size = rows.len();
+
+        if size.is_multiple_of(2) {
+            return Err(format!("mask should have an odd number of rows, not {} rows", size));
+        }
+
+        if let Some((y, row)) = rows.iter().enumerate().find(|(_, row)| row.chars().count() != size) {
+            return Err(format!("mask should be square, but row {} is {} characters long rather than {}", y + 1, row.chars().count(), size));
+        }
+
+        let centre = (size / 2) as isize;
+        let mut offsets = Vec::new();
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                let offset = (x as isize - centre, y as isize - centre);
+                match c {
+                    '*' | '1' | 'x' | 'X' | 'o' | 'O' if offset != (0, 0) => offsets.push(offset),
+                    '*' | '1' | 'x' | 'X' | 'o' | 'O' | '.' | '0' | ' ' => {},
+                    _ => return Err(format!("mask has an unrecognised character '{}'", c))
+                }
+            }
+        }
+
+        Ok(Self::Custom(offsets))
+    }
+
+    /// Parses a custom neighbourhood given as hex digits - the bits of the
+    /// square of the mask, row by row, with the first bit the highest.
+    /// 
+    /// The size of the square is the smallest with that many digits.
+    fn parse_hex(hex: &str) -> Option<Self> {
+        let bits: Vec<bool> = hex.chars()
+            .map(|c| c.to_digit(16))
+            .collect::<Option<Vec<u32>>>()?
+            .into_iter()
+            .flat_map(|digit| (0..4).rev().map(move |bit| digit & (1 << bit) != 0))
+            .collect();
+
+        let size = (1usize..).step_by(2).find(|size| (size * size).div_ceil(4) * 4 >= bits.len())?;
+        if (size * size).div_ceil(4) * 4 != bits.len() {
+            return None;
+        }
+
+        let centre = (size / 2) as isize;
+        let offsets: Vec<Offset> = (0..size * size)
+            .filter(|index| bits[*index])
+            .map(|index| ((index % size) as isize - centre, (index / size) as isize - centre))
+            .collect();
+
+        if offsets.contains(&(0, 0)) { None } else { Some(Self::Custom(offsets)) }
+    }
+
+    /// Gives the offsets of all of the neighbours.
+    pub fn get_offsets(&self) -> Vec<Offset> {
+        match self {
+            Self::Moore(radius) => Self::square(*radius, |_, _| true),
+            Self::VonNeumann(radius) => Self::square(*radius, |dx, dy| dx.unsigned_abs() + dy.unsigned_abs() <= *radius),
+            Self::Hexagonal => Self::square(1, |dx, dy| (dx, dy) != (1, -1) && (dx, dy) != (-1, 1)),
+            Self::Custom(offsets) => offsets.clone()
+        }
+    }
+
+    /// Gives the offsets of a square of the given radius, excluding the
+    /// centre, that pass the given filter.
+    fn square(radius: usize, filter: impl Fn(isize, isize) -> bool) -> Vec<Offset> {
+        let r = radius as isize;
+        let mut offsets = Vec::new();
+        for dy in -r..=r {
+            for dx in -r..=r {
+                if (dx, dy) != (0, 0) && filter(dx, dy) {
+                    offsets.push((dx, dy));
+                }
+            }
+        }
+
+        offsets
+    }
+
+    /// Gives the number of neighbours - i.e. the highest possible count.
+    pub fn get_size(&self) -> usize {
+        self.get_offsets().len()
+    }
+
+    /// Gives the greatest distance, in either direction, of any neighbour.
+    pub fn get_radius(&self) -> usize {
+        self.get_offsets()
+            .iter()
+            .map(|(dx, dy)| dx.unsigned_abs().max(dy.unsigned_abs()))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl Default for Neighbourhood {
+    /// The Moore neighbourhood, as used by Conway's Game of Life.
+    fn default() -> Self {
+        Self::Moore(1)
+    }
+}
+
+impl Display for Neighbourhood {
+    /// Writes the neighbourhood as a rulestring suffix - empty for the
+    /// default Moore neighbourhood, and `@` followed by the bits of its mask
+    /// in hex for a custom neighbourhood (see `parse_suffix()`).
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Moore(1) => Ok(()),
+            Self::Moore(radius) => write!(f, "M{}", radius),
+            Self::VonNeumann(1) => write!(f, "V"),
+            Self::VonNeumann(radius) => write!(f, "V{}", radius),
+            Self::Hexagonal => write!(f, "H"),
+            Self::Custom(offsets) => {
+                let radius = self.get_radius() as isize;
+                let bits: Vec<bool> = (-radius..=radius)
+                    .flat_map(|dy| (-radius..=radius).map(move |dx| offsets.contains(&(dx, dy))))
+                    .collect();
+
+                write!(f, "@")?;
+                for nibble in bits.chunks(4) {
+                    let digit = nibble.iter().enumerate().fold(0, |digit, (bit, is_set)| digit | ((*is_set as u32) << (3 - bit)));
+                    write!(f, "{:x}", digit)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,278 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+
+use crate::grid::Grid;
+use crate::life::{LifeGridType, LifeState};
+use crate::universe::Universe;
+
+
+/// How a pattern has stabilised.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stabilisation {
+    /// Every cell is 'dead' from the given generation onwards.
+    Extinct { generation: usize },
+
+    /// The pattern repeats from the given generation onwards, with the given
+    /// period - a period of 1 is a still life.
+    Periodic { generation: usize, period: usize },
+
+    /// The pattern repeats from the given generation onwards, displaced by
+    /// the given offset every period - a spaceship.
+    Moving { generation: usize, period: usize, dx: isize, dy: isize }
+}
+
+impl Stabilisation {
+    /// Gives the generation from which the pattern is stable.
+    pub fn get_generation(&self) -> usize {
+        match self {
+            Self::Extinct { generation } => *generation,
+            Self::Periodic { generation, .. } => *generation,
+            Self::Moving { generation, .. } => *generation
+        }
+    }
+
+    /// Gives the period - 1 for extinction or a still life.
+    pub fn get_period(&self) -> usize {
+        match self {
+            Self::Extinct { .. } => 1,
+            Self::Periodic { period, .. } => *period,
+            Self::Moving { period, .. } => *period
+        }
+    }
+
+    /// Gives the offset by which the pattern is displaced every period.
+    pub fn get_displacement(&self) -> (isize, isize) {
+        match self {
+            Self::Moving { dx, dy, .. } => (*dx, *dy),
+            _ => (0, 0)
+        }
+    }
+
+    /// Gives the speed of the pattern in 'c' notation, where 'c' is one
+    /// cell per generation.
+    /// 
+    /// Orthogonal and diagonal speeds are reduced, e.g. `c/4 diagonal` for a
+    /// glider and `c/2 orthogonal` for a lightweight spaceship. Oblique
+    /// speeds give both displacements, e.g. `(2,1)c/6 oblique`.
+    pub fn get_speed(&self) -> String {
+        let (dx, dy) = self.get_displacement();
+        let (major, minor) = (dx.unsigned_abs().max(dy.unsigned_abs()), dx.unsigned_abs().min(dy.unsigned_abs()));
+        let period = self.get_period();
+
+        if major == 0 {
+            return String::from("0");
+        }
+
+        if (minor != 0) && (minor != major) {
+            return format!("({},{})c/{} oblique", major, minor, period);
+        }
+
+        let divisor = gcd(major, period);
+        let (distance, period) = (major / divisor, period / divisor);
+        let speed = match (distance, period) {
+            (1, 1) => String::from("c"),
+            (1, _) => format!("c/{}", period),
+            (_, 1) => format!("{}c", distance),
+            _ => format!("{}c/{}", distance, period)
+        };
+
+        format!("{} {}", speed, if minor == 0 { "orthogonal" } else { "diagonal" })
+    }
+}
+
+impl Display for Stabilisation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Extinct { generation } => write!(f, "died out at generation {}", generation),
+            Self::Periodic { generation, period: 1 } => write!(f, "stabilised at generation {} as a still life (period 1)", generation),
+            Self::Periodic { generation, period } => write!(f, "stabilised at generation {} with period {}", generation, period),
+            Self::Moving { generation, period, dx, dy } => write!(f, "moving from generation {} with period {}, displaced by ({}, {}) at {}", generation, period, dx, dy, self.get_speed())
+        }
+    }
+}
+
+
+//---------------------------------------------------------------------------//
+
+
+/// Detects when a pattern enters a cycle.
+/// 
+/// Each generation's grid is hashed twice (see `hash_grid()` and
+/// `check_grid()`) and the pattern has stabilised when both hashes are seen
+/// for a second time - so a collision of one hash isn't taken for a cycle,
+/// without keeping the earlier grids.
+pub struct PeriodDetector {
+    generations: HashMap<(u64, u64), usize>,
+    result: Option<Stabilisation>
+}
+
+impl PeriodDetector {
+    pub fn new() -> Self {
+        Self {
+            generations: HashMap::new(),
+            result: None
+        }
+    }
+
+    /// Records the grid of the given generation.
+    /// 
+    /// Gives how the pattern has stabilised, once it has - subsequent calls
+    /// give the same result.
+    pub fn push(&mut self, generation: usize, grid: &LifeGridType) -> Option<Stabilisation> {
+        self.push_hashed(generation, grid, hash_grid(grid))
+    }
+
+    /// Records the grid of the given generation, with the given hash of the
+    /// grid.
+    /// 
+    /// See `push()`.
+    pub fn push_hashed(&mut self, generation: usize, grid: &LifeGridType, hash: u64) -> Option<Stabilisation> {
+        if self.result.is_some() {
+            return self.result;
+        }
+
+        let key = (hash, check_grid(grid));
+        if let Some(first) = self.generations.get(&key) {
+            self.result = if is_empty(grid) {
+                Some(Stabilisation::Extinct { generation: *first })
+            }
+            else {
+                Some(Stabilisation::Periodic { generation: *first, period: generation - first })
+            };
+        }
+        else {
+            self.generations.insert(key, generation);
+        }
+
+        self.result
+    }
+
+    /// Gives how the pattern has stabilised, if it has.
+    pub fn get_result(&self) -> Option<Stabilisation> {
+        self.result
+    }
+}
+
+impl Default for PeriodDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Detects when a pattern in an unbounded universe enters a cycle, whether
+/// or not it moves.
+/// 
+/// Each generation's grid is trimmed to the pattern's bounding box (see
+/// `Universe`), so its hash doesn't depend on the pattern's position. When a
+/// trimmed grid is seen for a second time (see `PeriodDetector`), the
+/// pattern has stabilised and the difference between the two positions
+/// gives its displacement.
+pub struct MotionDetector {
+    generations: HashMap<(u64, u64), (usize, (isize, isize))>,
+    result: Option<Stabilisation>
+}
+
+impl MotionDetector {
+    pub fn new() -> Self {
+        Self {
+            generations: HashMap::new(),
+            result: None
+        }
+    }
+
+    /// Records the universe of the given generation.
+    /// 
+    /// Gives how the pattern has stabilised, once it has - subsequent calls
+    /// give the same result.
+    pub fn push(&mut self, generation: usize, universe: &Universe) -> Option<Stabilisation> {
+        if self.result.is_some() {
+            return self.result;
+        }
+
+        let grid = universe.get_grid();
+        let (x, y) = universe.get_origin();
+        let key = (hash_grid(grid), check_grid(grid));
+        if let Some((first, (first_x, first_y))) = self.generations.get(&key) {
+            let (period, dx, dy) = (generation - first, x - first_x, y - first_y);
+            self.result = if universe.is_empty() {
+                Some(Stabilisation::Extinct { generation: *first })
+            }
+            else if (dx == 0) && (dy == 0) {
+                Some(Stabilisation::Periodic { generation: *first, period })
+            }
+            else {
+                Some(Stabilisation::Moving { generation: *first, period, dx, dy })
+            };
+        }
+        else {
+            self.generations.insert(key, (generation, (x, y)));
+        }
+
+        self.result
+    }
+
+    /// Gives how the pattern has stabilised, if it has.
+    pub fn get_result(&self) -> Option<Stabilisation> {
+        self.result
+    }
+}
+
+impl Default for MotionDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Gives the greatest common divisor of two numbers.
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Gives a hash of the dimensions & contents of a grid.
+pub fn hash_grid(grid: &LifeGridType) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    grid.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Gives a second hash of a grid, independent of `hash_grid()` - used to
+/// confirm a match of the first.
+fn check_grid(grid: &LifeGridType) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    "check".hash(&mut hasher);
+    grid.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Indicates if every cell of the grid is 'dead'.
+pub fn is_empty(grid: &LifeGridType) -> bool {
+    grid.iter().all(|cell| cell.get() == &LifeState::DEAD)
+}
@@ -0,0 +1,270 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::io::Write;
+
+use crate::grid::{BoundingBox, Grid, SimpleGrid, SizedGrid};
+use crate::heatmap::HeatMap;
+use crate::life::{LifeGrid, LifeGridType, LifeState};
+
+
+/// How cells are drawn as characters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RenderStyle {
+    /// A character per cell, with an ASCII border.
+    #[default]
+    Ascii,
+
+    /// A character per cell, with a box-drawing border.
+    Box,
+
+    /// A Unicode braille character per 2x4 cells.
+    Braille,
+
+    /// A Unicode half-block character per 1x2 cells.
+    HalfBlock
+}
+
+impl RenderStyle {
+    /// Parses a style's name - `ascii`, `box`, `braille` or `halfblock`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "ascii" => Some(Self::Ascii),
+            "box" => Some(Self::Box),
+            "braille" => Some(Self::Braille),
+            "halfblock" | "half-block" => Some(Self::HalfBlock),
+            _ => None
+        }
+    }
+
+    /// Gives the number of columns & rows of cells drawn as one character.
+    pub fn get_cell_size(&self) -> (usize, usize) {
+        match self {
+            Self::Ascii | Self::Box => (1, 1),
+            Self::Braille => (2, 4),
+            Self::HalfBlock => (1, 2)
+        }
+    }
+}
+
+
+//---------------------------------------------------------------------------//
+
+
+/// Renders the part of a grid to be shown as characters.
+/// 
+/// By default the whole grid is shown. A viewport shows just that region,
+/// which may extend beyond the grid, and following shows the region around
+/// the pattern - with a viewport, of the viewport's size and centred on the
+/// pattern. Zooming out by N shows each NxN block of cells as one character,
+/// shaded by the proportion of the block that isn't 'dead'. Otherwise, the
+/// cells are drawn in the given style (see `RenderStyle`).
+#[derive(Clone, Debug)]
+pub struct Renderer {
+    viewport: Option<BoundingBox>,
+    is_following: bool,
+    zoom: usize,
+    style: RenderStyle,
+    chars: (char, char)     // <--- The 'live' & 'dead' characters of the
+                            //      ASCII & box styles.
+}
+
+impl Renderer {
+    /// Initialises a renderer of the whole grid.
+    pub fn new() -> Self {
+        Self {
+            viewport: None,
+            is_following: false,
+            zoom: 1,
+            style: RenderStyle::Ascii,
+            chars: (LifeState::LIVE.to_char(), LifeState::DEAD.to_char())
+        }
+    }
+
+    /// Sets the region shown.
+    pub fn set_viewport(&mut self, viewport: BoundingBox) {
+        self.viewport = Some(viewport);
+    }
+
+    /// Sets whether the region shown follows the pattern.
+    pub fn set_following(&mut self, is_following: bool) {
+        self.is_following = is_following;
+    }
+
+    /// Sets the size of the blocks of cells shown as one character.
+    pub fn set_zoom(&mut self, zoom: usize) {
+        self.zoom = zoom.max(1);
+    }
+
+    /// Sets how cells are drawn.
+    pub fn set_style(&mut self, style: RenderStyle) {
+        self.style = style;
+    }
+
+    /// Sets the characters of 'live' & 'dead' cells, for the ASCII & box
+    /// styles - other states keep their own characters.
+    pub fn set_chars(&mut self, live: char, dead: char) {
+        self.chars = (live, dead);
+    }
+
+    /// Parses a viewport given as `<x>,<y>,<width>x<height>`.
+    pub fn parse_viewport(text: &str) -> Option<BoundingBox> {
+        let mut parts = text.split(',');
+        let x = parts.next()?.trim().parse::<isize>().ok()?;
+        let y = parts.next()?.trim().parse::<isize>().ok()?;
+        let (width, height) = parts.next()?.trim().split_once('x')?;
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(BoundingBox::new(x, y, width.parse::<usize>().ok()?, height.parse::<usize>().ok()?))
+    }
+
+    /// Gives the region of the grid shown, relative to the grid.
+    /// 
+    /// The grid's top-left cell is at the given origin, which the viewport
+    /// is relative to (see `Universe::get_origin()`).
+    pub fn get_view(&self, grid: &LifeGridType, origin: (isize, isize)) -> BoundingBox {
+        let whole = BoundingBox::new(0, 0, grid.get_width(), grid.get_height());
+        let viewport = self.viewport.map(|viewport| viewport.translate(-origin.0, -origin.1));
+
+        match (self.is_following, grid.get_bounding_box(), viewport) {
+            (true, Some(bounds), Some(viewport)) => {
+                let centre_x = bounds.x + (bounds.width / 2) as isize;
+                let centre_y = bounds.y + (bounds.height / 2) as isize;
+                BoundingBox::new(centre_x - (viewport.width / 2) as isize, centre_y - (viewport.height / 2) as isize, viewport.width, viewport.height)
+            },
+            (true, Some(bounds), None) => bounds,
+            (_, _, Some(viewport)) => viewport,
+            (_, _, None) => whole
+        }
+    }
+
+    /// Gives the characters shown for the grid, whose top-left cell is at
+    /// the given origin.
+    pub fn render(&self, grid: &LifeGridType, origin: (isize, isize)) -> SimpleGrid<char> {
+        let view = grid.crop(&self.get_view(grid, origin), 0);
+        if self.zoom > 1 {
+            return Self::zoom_out(&view, self.zoom);
+        }
+
+        match self.style {
+            RenderStyle::Ascii | RenderStyle::Box => {
+                let (live, dead) = self.chars;
+                let mut chars = SimpleGrid::init(view.get_width(), view.get_height(), ' ');
+                for cell in &view {
+                    let c = match *cell.get() {
+                        LifeState::LIVE => live,
+                        LifeState::DEAD => dead,
+                        state => state.to_char()
+                    };
+                    chars.set(cell.get_x(), cell.get_y(), c);
+                }
+
+                chars
+            },
+            RenderStyle::Braille => Self::draw_blocks(&view, self.style, |bits| {
+                // Braille dots 1-3 & 4-6 are the top three rows of the left &
+                // right columns, and dots 7 & 8 the bottom row...
+                let dots = [0x01, 0x08, 0x02, 0x10, 0x04, 0x20, 0x40, 0x80];
+                let pattern = (0..8).filter(|bit| bits & (1 << bit) != 0).fold(0, |pattern, bit| pattern | dots[bit]);
+                if pattern == 0 { ' ' } else { char::from_u32(0x2800 + pattern).unwrap_or(' ') }
+            }),
+            RenderStyle::HalfBlock => Self::draw_blocks(&view, self.style, |bits| match bits {
+                0b00 => ' ',
+                0b01 => '\u{2580}',
+                0b10 => '\u{2584}',
+                _ => '\u{2588}'
+            })
+        }
+    }
+
+    /// Writes the characters shown for the grid, with a border.
+    pub fn write(&self, grid: &LifeGridType, origin: (isize, isize), w: &mut dyn Write) -> std::io::Result<()> {
+        self.write_chars(&self.render(grid, origin), w)
+    }
+
+    /// Writes rendered characters (see `render()`) with a border - ASCII for
+    /// the ASCII style, otherwise box-drawing.
+    pub fn write_chars(&self, chars: &SimpleGrid<char>, w: &mut dyn Write) -> std::io::Result<()> {
+        if self.style == RenderStyle::Ascii {
+            return chars.write(w);
+        }
+
+        let line = "\u{2500}".repeat(chars.get_width());
+        let mut text = format!("\u{250c}{}\u{2510}\n", line);
+        for y in 0..chars.get_height() {
+            let row: String = (0..chars.get_width()).map(|x| *chars.get(x, y)).collect();
+            text += &format!("\u{2502}{}\u{2502}\n", row);
+        }
+        text += &format!("\u{2514}{}\u{2518}\n", line);
+
+        w.write_all(text.as_bytes())
+    }
+
+    /// Draws each block of cells of the style's size as one character,
+    /// given by the bits of the block's cells that aren't 'dead' - row by
+    /// row, from the least significant bit.
+    fn draw_blocks(grid: &LifeGridType, style: RenderStyle, draw: impl Fn(u32) -> char) -> SimpleGrid<char> {
+        let (columns, rows) = style.get_cell_size();
+        let (width, height) = (grid.get_width().div_ceil(columns), grid.get_height().div_ceil(rows));
+
+        let mut bits = SimpleGrid::init(width, height, 0u32);
+        for cell in grid.iter().filter(|cell| cell.get() != &LifeState::DEAD) {
+            let (x, y) = (cell.get_x() / columns, cell.get_y() / rows);
+            let bit = ((cell.get_y() % rows) * columns) + (cell.get_x() % columns);
+            bits.set(x, y, bits.get(x, y) | (1 << bit));
+        }
+
+        let mut chars = SimpleGrid::init(width, height, ' ');
+        for cell in &bits {
+            chars.set(cell.get_x(), cell.get_y(), draw(*cell.get()));
+        }
+
+        chars
+    }
+
+    /// Shows each NxN block of cells as one character, shaded by the
+    /// proportion of the block that isn't 'dead'.
+    fn zoom_out(grid: &LifeGridType, zoom: usize) -> SimpleGrid<char> {
+        let (width, height) = (grid.get_width().div_ceil(zoom), grid.get_height().div_ceil(zoom));
+        let shades: Vec<char> = HeatMap::SHADES.chars().collect();
+        let levels = shades.len() - 1;
+
+        let mut counts = SimpleGrid::init(width, height, 0);
+        for cell in grid.iter().filter(|cell| cell.get() != &LifeState::DEAD) {
+            let (x, y) = (cell.get_x() / zoom, cell.get_y() / zoom);
+            counts.set(x, y, counts.get(x, y) + 1);
+        }
+
+        let mut chars = SimpleGrid::init(width, height, ' ');
+        for cell in &counts {
+            chars.set(cell.get_x(), cell.get_y(), shades[(cell.get() * levels).div_ceil(zoom * zoom)]);
+        }
+
+        chars
+    }
+}
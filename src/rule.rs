@@ -0,0 +1,381 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::fmt::Display;
+
+use crate::grid::{Grid, SimpleGrid, SizedGrid};
+use crate::life::{LifeCell, LifeGrid, LifeGridType, LifeState};
+use crate::hensel::HenselRule;
+use crate::ltl::LargerThanLifeRule;
+use crate::neighbourhood::Neighbourhood;
+use crate::table::TableRule;
+
+
+/// A rule for a cellular automaton.
+/// 
+/// Each generation is found by first counting every cell's neighbours (see
+/// `count_neighbours()`) and then giving each cell its next state from its
+/// current state and its count (see `next_state()`).
+pub trait Rule : Display {
+    /// Gives the number of cell states - 2 for a Life-like rule.
+    fn get_states(&self) -> u8;
+
+    /// Gives the next state of a cell with the given state and neighbour
+    /// count.
+    fn next_state(&self, state: LifeState, count: i32) -> LifeState;
+
+    /// Gives a grid of the count of 'live' neighbours of every cell in the
+    /// given grid.
+    fn count_neighbours(&self, grid: &LifeGridType) -> SimpleGrid<i32>;
+
+    /// Gives the furthest distance (horizontally or vertically) at which a
+    /// cell can affect another cell's next state.
+    fn get_radius(&self) -> usize {
+        1
+    }
+
+    /// Gives the next generation of the given grid using a grid of
+    /// neighbour counts from `count_neighbours()`.
+    fn apply(&self, grid: &LifeGridType, neighbours_grid: &SimpleGrid<i32>) -> LifeGridType {
+        let mut new_grid = LifeGridType::init_life(grid.get_width(), grid.get_height());
+        for cell in grid {
+            let (x, y) = (cell.get_x(), cell.get_y());
+            new_grid.set(x, y, self.next_state(*cell.get(), neighbours_grid[(x, y)]));
+        }
+
+        new_grid
+    }
+
+    /// Gives the next generation of the given grid.
+    fn step(&self, grid: &LifeGridType) -> LifeGridType {
+        self.apply(grid, &self.count_neighbours(grid))
+    }
+
+    /// Gives the rule as it's given on the command line or in a pattern
+    /// file, so that it's read back as the same rule - a rulestring (see
+    /// `parse_rule()`) by default.
+    fn get_rulestring(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Parses a rulestring of any of the supported rule families.
+/// 
+/// Larger than Life rules start with `R` (see `LargerThanLifeRule::parse()`),
+/// rules with Hensel letters are isotropic non-totalistic rules (see
+/// `HenselRule::parse()`), the names of built-in rule tables give those
+/// tables (see `TableRule::builtin()`), and anything else is a Life-like or
+/// Generations rule (see `LifeRule::parse_with_neighbourhood()`), for which
+/// the given neighbourhood is used unless the rulestring has a suffix.
+pub fn parse_rule(rulestring: &str, neighbourhood: Neighbourhood) -> Result<Box<dyn Rule>, String> {
+    let text = rulestring.trim();
+    if is_larger_than_life(text) {
+        Ok(Box::new(LargerThanLifeRule::parse(text)?))
+    }
+    else if HenselRule::is_hensel(text) {
+        Ok(Box::new(HenselRule::parse(text)?))
+    }
+    else if let Some(table) = TableRule::builtin(text) {
+        Ok(Box::new(table))
+    }
+    else {
+        Ok(Box::new(LifeRule::parse_with_neighbourhood(text, neighbourhood)?))
+    }
+}
+
+/// Gives whether the rule of a rulestring takes the neighbourhood given to
+/// `parse_rule()` - i.e. it's a Life-like or Generations rule, rather than a
+/// rule with a neighbourhood of its own.
+pub fn takes_neighbourhood(rulestring: &str) -> bool {
+    let text = rulestring.trim();
+    !(is_larger_than_life(text) || HenselRule::is_hensel(text) || TableRule::builtin(text).is_some())
+}
+
+fn is_larger_than_life(text: &str) -> bool {
+    text.starts_with(['R', 'r']) && text.contains(',')
+}
+
+/// Gives the next state of a cell under a Generations rule (or a two-state
+/// rule, when `states` is 2).
+/// 
+/// A 'dead' cell is born if `is_birth`, a 'live' cell stays 'live' if
+/// `is_survival` and otherwise starts 'dying', and a 'dying' cell moves on
+/// to the next state until it is 'dead'.
+pub fn next_generations_state(state: LifeState, states: u8, is_birth: bool, is_survival: bool) -> LifeState {
+    match state.get() {
+        0 if is_birth => LifeState::LIVE,
+        0 => LifeState::DEAD,
+        1 if is_survival => LifeState::LIVE,
+        n if n + 1 < states => LifeState(n + 1),
+        _ => LifeState::DEAD
+    }
+}
+
+
+//---------------------------------------------------------------------------//
+
+
+/// A Life-like or Generations rule.
+/// 
+/// A cell is born when it is 'dead' and its count of 'live' neighbours is
+/// in the birth set, and survives when it is 'live' and its count is in the
+/// survival set. With more than 2 states (a Generations rule), a 'live'
+/// cell that does not survive passes through the 'dying' states before
+/// becoming 'dead'.
+/// 
+/// Neighbours are counted over the rule's `Neighbourhood` - the Moore
+/// neighbourhood unless otherwise set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LifeRule {
+    birth: Vec<bool>,
+    survival: Vec<bool>,
+    states: u8,
+    neighbourhood: Neighbourhood
+}
+
+impl LifeRule {
+    /// Initialises a rule from the given birth & survival counts and the
+    /// number of states (2 for a Life-like rule).
+    /// 
+    /// Uses the Moore neighbourhood - see `set_neighbourhood()`.
+    pub fn init(birth: &[usize], survival: &[usize], states: u8) -> Self {
+        let mut rule = Self {
+            birth: Vec::new(),
+            survival: Vec::new(),
+            states: states.max(2),
+            neighbourhood: Neighbourhood::default()
+        };
+
+        for count in birth {
+            Self::insert(&mut rule.birth, *count);
+        }
+
+        for count in survival {
+            Self::insert(&mut rule.survival, *count);
+        }
+
+        rule
+    }
+
+    fn insert(counts: &mut Vec<bool>, count: usize) {
+        if counts.len() <= count {
+            counts.resize(count + 1, false);
+        }
+
+        counts[count] = true;
+    }
+
+    /// Sets the neighbourhood over which neighbours are counted.
+    pub fn set_neighbourhood(&mut self, neighbourhood: Neighbourhood) {
+        self.neighbourhood = neighbourhood;
+    }
+
+    /// Gives the neighbourhood over which neighbours are counted.
+    pub fn get_neighbourhood(&self) -> &Neighbourhood {
+        &self.neighbourhood
+    }
+
+    /// Parses a rulestring.
+    /// 
+    /// Accepts B/S notation (`B3/S23`), S/B notation (`23/3`) and the
+    /// Generations forms of each (`B2/S/C3` and `345/2/4`).
+    /// 
+    /// A suffix selects a neighbourhood other than Moore - e.g. `B2/S013V`
+    /// or `B2/S34H`, or `@` and hex digits for a custom neighbourhood (see
+    /// `Neighbourhood::parse_suffix()`). Where counts can be higher than 9
+    /// they are always separated by commas, e.g. `B3,10/S2,3M2`.
+    pub fn parse(rulestring: &str) -> Result<Self, String> {
+        Self::parse_with_neighbourhood(rulestring, Neighbourhood::default())
+    }
+
+    /// Parses a rulestring, using the given neighbourhood unless the
+    /// rulestring has a neighbourhood suffix.
+    /// 
+    /// See `parse()`.
+    pub fn parse_with_neighbourhood(rulestring: &str, neighbourhood: Neighbourhood) -> Result<Self, String> {
+        let text = rulestring.trim().to_uppercase();
+        let (text, neighbourhood) = Self::split_suffix(&text, neighbourhood, rulestring)?;
+        let max_count = neighbourhood.get_size();
+        let parts: Vec<&str> = text.split('/').map(|part| part.trim()).collect();
+
+        if parts.len() < 2 || parts.len() > 3 {
+            return Err(format!("rule '{}' should have 2 or 3 parts", rulestring));
+        }
+
+        let mut birth = None;
+        let mut survival = None;
+        let mut states = None;
+
+        let is_prefixed = parts.iter().any(|part| part.starts_with(['B', 'S', 'C', 'G']));
+        if is_prefixed {
+            for part in &parts {
+                let (prefix, value) = part.split_at(part.chars().next().map(|c| c.len_utf8()).unwrap_or(0));
+                match prefix {
+                    "B" if birth.is_none() => birth = Some(Self::parse_counts(value, max_count, rulestring)?),
+                    "S" if survival.is_none() => survival = Some(Self::parse_counts(value, max_count, rulestring)?),
+                    "C" | "G" if states.is_none() => states = Some(Self::parse_states(value, rulestring)?),
+                    _ => return Err(format!("rule '{}' has an unexpected part '{}'", rulestring, part))
+                }
+            }
+        }
+        else {
+            survival = Some(Self::parse_counts(parts[0], max_count, rulestring)?);
+            birth = Some(Self::parse_counts(parts[1], max_count, rulestring)?);
+            if parts.len() == 3 {
+                states = Some(Self::parse_states(parts[2], rulestring)?);
+            }
+        }
+
+        match (birth, survival) {
+            (Some(birth), Some(survival)) => {
+                let mut rule = Self::init(&birth, &survival, states.unwrap_or(2));
+                rule.set_neighbourhood(neighbourhood);
+                Ok(rule)
+            },
+            _ => Err(format!("rule '{}' should have both a birth and a survival part", rulestring))
+        }
+    }
+
+    /// Splits a neighbourhood suffix (if any) from the end of a rulestring.
+    fn split_suffix<'t>(text: &'t str, default: Neighbourhood, rulestring: &str) -> Result<(&'t str, Neighbourhood), String> {
+        if let Some(index) = text.rfind('@') {
+            return match Neighbourhood::parse_suffix(&text[index..]) {
+                Some(neighbourhood) => Ok((&text[..index], neighbourhood)),
+                None => Err(format!("rule '{}' has a bad neighbourhood '{}'", rulestring, &text[index..]))
+            };
+        }
+
+        let start = text.trim_end_matches(|c: char| c.is_ascii_digit());
+        match start.chars().last() {
+            Some('M' | 'V' | 'H') => {
+                let index = start.len() - 1;
+                match Neighbourhood::parse_suffix(&text[index..]) {
+                    Some(neighbourhood) => Ok((&text[..index], neighbourhood)),
+                    None => Err(format!("rule '{}' has a bad neighbourhood '{}'", rulestring, &text[index..]))
+                }
+            },
+            _ => Ok((text, default))
+        }
+    }
+
+    fn parse_counts(digits: &str, max_count: usize, rulestring: &str) -> Result<Vec<usize>, String> {
+        let bad_count = |count: &str| format!("rule '{}' has a bad neighbour count '{}'", rulestring, count);
+
+        if max_count > 9 {
+            digits.split(',')
+                .filter(|count| !count.trim().is_empty())
+                .map(|count| match count.trim().parse::<usize>() {
+                    Ok(n) if n <= max_count => Ok(n),
+                    _ => Err(bad_count(count))
+                })
+                .collect()
+        }
+        else {
+            digits.chars()
+                .map(|c| match c.to_digit(10) {
+                    Some(n) if n as usize <= max_count => Ok(n as usize),
+                    _ => Err(bad_count(&c.to_string()))
+                })
+                .collect()
+        }
+    }
+
+    fn parse_states(digits: &str, rulestring: &str) -> Result<u8, String> {
+        match digits.parse::<u8>() {
+            Ok(states) if states >= 2 => Ok(states),
+            _ => Err(format!("rule '{}' has a bad number of states '{}'", rulestring, digits))
+        }
+    }
+
+    /// Indicates if a 'dead' cell with the given neighbour count is born.
+    pub fn is_birth(&self, count: i32) -> bool {
+        Self::contains(&self.birth, count)
+    }
+
+    /// Indicates if a 'live' cell with the given neighbour count survives.
+    pub fn is_survival(&self, count: i32) -> bool {
+        Self::contains(&self.survival, count)
+    }
+
+    fn contains(counts: &[bool], count: i32) -> bool {
+        count >= 0 && counts.get(count as usize).copied().unwrap_or(false)
+    }
+}
+
+impl Rule for LifeRule {
+    fn get_states(&self) -> u8 {
+        self.states
+    }
+
+    fn next_state(&self, state: LifeState, count: i32) -> LifeState {
+        next_generations_state(state, self.states, self.is_birth(count), self.is_survival(count))
+    }
+
+    fn count_neighbours(&self, grid: &LifeGridType) -> SimpleGrid<i32> {
+        let offsets = self.neighbourhood.get_offsets();
+
+        let mut neighbours_grid = SimpleGrid::init(grid.get_width(), grid.get_height(), 0);
+        for cell in grid {
+            neighbours_grid.set(cell.get_x(), cell.get_y(), cell.count_neighbours_in(&offsets));
+        }
+
+        neighbours_grid
+    }
+
+    fn get_radius(&self) -> usize {
+        self.neighbourhood.get_radius()
+    }
+}
+
+impl Default for LifeRule {
+    /// Conway's Game of Life - `B3/S23`.
+    fn default() -> Self {
+        Self::init(&[3], &[2, 3], 2)
+    }
+}
+
+impl Display for LifeRule {
+    /// Writes the rule in B/S notation, with a `/C` part for Generations
+    /// rules and a suffix for the neighbourhood.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let separator = if self.neighbourhood.get_size() > 9 { "," } else { "" };
+        let digits = |counts: &[bool]| -> String {
+            counts.iter()
+                .enumerate()
+                .filter(|(_, is_set)| **is_set)
+                .map(|(count, _)| count.to_string())
+                .collect::<Vec<String>>()
+                .join(separator)
+        };
+
+        write!(f, "B{}/S{}", digits(&self.birth), digits(&self.survival))?;
+        if self.states > 2 {
+            write!(f, "/C{}", self.states)?;
+        }
+
+        write!(f, "{}", self.neighbourhood)
+    }
+}
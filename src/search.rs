@@ -0,0 +1,245 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::census::{Catalogue, Census};
+use crate::life::{LifeGrid, LifeGridType};
+use crate::methuselah::{Methuselah, MethuselahReport};
+use crate::rule::Rule;
+
+
+/// A pseudo-random number generator (SplitMix64).
+/// 
+/// The same seed always gives the same sequence, on any platform, so that
+/// searches can be reproduced.
+pub struct Random {
+    state: u64
+}
+
+impl Random {
+    const GOLDEN_GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+    pub fn from_seed(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Gives the next number in the sequence.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(Self::GOLDEN_GAMMA);
+        Self::mix(self.state)
+    }
+
+    /// Gives the next number in the sequence, in the range [0, 1).
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Scrambles the bits of a number.
+    fn mix(value: u64) -> u64 {
+        let value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        let value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        value ^ (value >> 31)
+    }
+}
+
+
+//---------------------------------------------------------------------------//
+
+
+/// A search of random starting patterns ('soups').
+/// 
+/// Each soup is run in an unbounded universe until it stabilises (see
+/// `Methuselah`), and the objects it leaves are counted (see `Census`).
+/// Soup `n` of a search depends only on the seed and `n`, so any soup can
+/// be reproduced.
+pub struct SoupSearch {
+    width: usize,
+    height: usize,
+    density: f64,
+    seed: u64,
+    max_generations: usize,
+    gap: usize
+}
+
+impl SoupSearch {
+    /// Initialises a search of soups of the given size, with a density of
+    /// 0.5, a seed of 0 and a limit of 10,000 generations.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            density: 0.5,
+            seed: 0,
+            max_generations: 10_000,
+            gap: 0
+        }
+    }
+
+    /// Sets the proportion of cells of a soup that are 'live'.
+    pub fn set_density(&mut self, density: f64) {
+        self.density = density;
+    }
+
+    /// Sets the seed from which the soups are generated.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    /// Sets the number of generations after which a soup that hasn't
+    /// stabilised is abandoned.
+    pub fn set_max_generations(&mut self, max_generations: usize) {
+        self.max_generations = max_generations;
+    }
+
+    /// Sets the gap used to separate objects (see `separate()`).
+    pub fn set_gap(&mut self, gap: usize) {
+        self.gap = gap;
+    }
+
+    /// Generates the soup with the given number.
+    pub fn generate(&self, soup: usize) -> LifeGridType {
+        let mut random = Random::from_seed(Random::mix(self.seed) ^ Random::mix(soup as u64));
+
+        let mut grid = LifeGridType::init_life(self.width, self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if random.next_f64() < self.density {
+                    grid.set_live(x, y);
+                }
+            }
+        }
+
+        grid
+    }
+
+    /// Runs a soup until it stabilises, or for the maximum number of
+    /// generations.
+    /// 
+    /// See `Methuselah::run()`.
+    pub fn run_soup(&self, grid: &LifeGridType, rule: &dyn Rule, catalogue: &Catalogue) -> MethuselahReport {
+        let mut methuselah = Methuselah::new();
+        methuselah.set_max_generations(self.max_generations);
+        methuselah.set_gap(self.gap);
+
+        methuselah.run(grid, rule, catalogue)
+    }
+
+    /// Searches the given number of soups, starting from soup 0.
+    pub fn run(&self, soups: usize, rule: &dyn Rule, catalogue: &Catalogue) -> SearchReport {
+        let mut report = SearchReport {
+            soups,
+            width: self.width,
+            height: self.height,
+            density: self.density,
+            seed: self.seed,
+            max_generations: self.max_generations,
+            stabilised: 0,
+            generations: 0,
+            census: Census::new(),
+            samples: HashMap::new()
+        };
+
+        for soup in 0..soups {
+            let result = self.run_soup(&self.generate(soup), rule, catalogue);
+            if let Some(lifespan) = result.lifespan {
+                report.stabilised += 1;
+                report.generations += lifespan;
+            }
+
+            // Note the first soup to leave each object that isn't in the
+            // catalogue...
+            for (name, _) in result.census.get_entries() {
+                if !catalogue.has_name(name) {
+                    report.samples.entry(String::from(name)).or_insert(soup);
+                }
+            }
+
+            report.census.merge(&result.census);
+        }
+
+        report
+    }
+}
+
+
+//---------------------------------------------------------------------------//
+
+
+/// The results of a `SoupSearch`.
+pub struct SearchReport {
+    soups: usize,
+    width: usize,
+    height: usize,
+    density: f64,
+    seed: u64,
+    max_generations: usize,
+    stabilised: usize,
+    generations: usize,
+    census: Census,
+    samples: HashMap<String, usize>
+}
+
+impl SearchReport {
+    /// Gives the number of soups searched.
+    pub fn get_soups(&self) -> usize {
+        self.soups
+    }
+
+    /// Gives the number of soups that stabilised.
+    pub fn get_stabilised(&self) -> usize {
+        self.stabilised
+    }
+
+    /// Gives the census of the objects left by all the soups.
+    pub fn get_census(&self) -> &Census {
+        &self.census
+    }
+
+    /// Gives the number of the first soup that left an object that isn't in
+    /// the catalogue, from the object's code.
+    pub fn get_sample(&self, code: &str) -> Option<usize> {
+        self.samples.get(code).copied()
+    }
+}
+
+impl Display for SearchReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Searched {} soups of {}x{} at density {} (seed {}):", self.soups, self.width, self.height, self.density, self.seed)?;
+        writeln!(f, "{:>6} stabilised, after {} generations on average", self.stabilised, self.generations.checked_div(self.stabilised).unwrap_or(0))?;
+        writeln!(f, "{:>6} not stabilised after {} generations", self.soups - self.stabilised, self.max_generations)?;
+        writeln!(f, "Census ({} objects):", self.census.get_total())?;
+        for (name, count) in self.census.get_entries() {
+            match self.samples.get(name) {
+                Some(soup) => writeln!(f, "{:>6} {} (first in soup {})", count, name, soup)?,
+                None => writeln!(f, "{:>6} {}", count, name)?
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,198 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::diff::GridDiff;
+use crate::grid::{BoundingBox, Grid, SizedGrid};
+use crate::life::{LifeGrid, LifeGridType, LifeState};
+use crate::universe::Universe;
+
+
+/// Statistics of a generation, compared with the previous generation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GenerationStats {
+    pub generation: usize,
+
+    /// The number of 'live' cells.
+    pub population: usize,
+
+    /// The number of cells that have become 'live'.
+    pub births: usize,
+
+    /// The number of cells that were 'live' and aren't any more.
+    pub deaths: usize,
+
+    /// The number of cells whose state has changed.
+    pub changed: usize,
+
+    /// The smallest region containing every cell that isn't 'dead'.
+    pub bounds: Option<BoundingBox>,
+
+    /// The proportion of the cells in the bounding box that are 'live'.
+    pub density: f64
+}
+
+impl GenerationStats {
+    /// Gives the statistics of a generation on the given grid, compared with
+    /// the previous generation on the given grid of the same size.
+    pub fn from_grids(generation: usize, previous: &LifeGridType, current: &LifeGridType) -> Self {
+        let diff = GridDiff::between(previous, current);
+        let births = diff.get_births().len();
+        let deaths = diff.get_deaths().len();
+        let changed = diff.get_changes().len();
+
+        let population = current.get_population();
+        let bounds = current.get_bounding_box();
+        let density = match bounds {
+            Some(bounds) => population as f64 / (bounds.width * bounds.height) as f64,
+            None => 0.0
+        };
+
+        Self {
+            generation,
+            population,
+            births,
+            deaths,
+            changed,
+            bounds,
+            density
+        }
+    }
+
+    /// Gives the statistics of a generation on its own - with no births,
+    /// deaths or changes.
+    pub fn from_grid(generation: usize, grid: &LifeGridType) -> Self {
+        Self::from_grids(generation, grid, grid)
+    }
+
+    /// Gives the statistics of a generation in an unbounded universe,
+    /// compared with the previous generation.
+    /// 
+    /// The bounding box is given in the universe's co-ordinates.
+    pub fn from_universes(generation: usize, previous: &Universe, current: &Universe) -> Self {
+        let region = match (previous.get_bounding_box(), current.get_bounding_box()) {
+            (Some(before), Some(after)) => before.union(&after),
+            (before, after) => match before.or(after) {
+                Some(bounds) => bounds,
+                None => BoundingBox::new(0, 0, 0, 0)
+            }
+        };
+
+        let stats = Self::from_grids(generation, &previous.get_region(&region), &current.get_region(&region));
+        Self {
+            bounds: stats.bounds.map(|bounds| bounds.translate(region.x, region.y)),
+            ..stats
+        }
+    }
+}
+
+
+//---------------------------------------------------------------------------//
+
+
+/// The format in which statistics are written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatsFormat {
+    /// Comma-separated values, with a header line.
+    Csv,
+
+    /// A JSON object on each line.
+    JsonLines
+}
+
+impl StatsFormat {
+    /// Gives the format for a file path - JSON Lines for a `.jsonl` or
+    /// `.json` extension, otherwise CSV.
+    pub fn from_path(path: &str) -> Self {
+        let path = path.to_lowercase();
+        if path.ends_with(".jsonl") || path.ends_with(".json") {
+            Self::JsonLines
+        }
+        else {
+            Self::Csv
+        }
+    }
+}
+
+/// Writes a stream of `GenerationStats`, a line for each generation.
+pub struct StatsWriter {
+    out: Box<dyn Write>,
+    format: StatsFormat,
+    has_header: bool
+}
+
+impl StatsWriter {
+    /// Initialises a writer to the given output stream.
+    pub fn new(out: Box<dyn Write>, format: StatsFormat) -> Self {
+        Self {
+            out,
+            format,
+            has_header: false
+        }
+    }
+
+    /// Initialises a writer to a new file at the given path, with the format
+    /// given by the path's extension (see `StatsFormat::from_path()`).
+    pub fn from_path(path: &str) -> std::io::Result<Self> {
+        let file = File::create(path)?;
+        Ok(Self::new(Box::new(BufWriter::new(file)), StatsFormat::from_path(path)))
+    }
+
+    /// Writes the statistics of a generation.
+    pub fn write(&mut self, stats: &GenerationStats) -> std::io::Result<()> {
+        match self.format {
+            StatsFormat::Csv => {
+                if !self.has_header {
+                    writeln!(self.out, "generation,population,births,deaths,changed,x,y,width,height,density")?;
+                    self.has_header = true;
+                }
+
+                let bounds = match stats.bounds {
+                    Some(bounds) => format!("{},{},{},{}", bounds.x, bounds.y, bounds.width, bounds.height),
+                    None => String::from(",,,")
+                };
+
+                writeln!(self.out, "{},{},{},{},{},{},{}", stats.generation, stats.population, stats.births, stats.deaths, stats.changed, bounds, stats.density)
+            },
+            StatsFormat::JsonLines => {
+                let bounds = match stats.bounds {
+                    Some(bounds) => format!("{{\"x\":{},\"y\":{},\"width\":{},\"height\":{}}}", bounds.x, bounds.y, bounds.width, bounds.height),
+                    None => String::from("null")
+                };
+
+                writeln!(self.out, "{{\"generation\":{},\"population\":{},\"births\":{},\"deaths\":{},\"changed\":{},\"bounding_box\":{},\"density\":{}}}",
+                    stats.generation, stats.population, stats.births, stats.deaths, stats.changed, bounds, stats.density)
+            }
+        }
+    }
+
+    /// Flushes any buffered output.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.out.flush()
+    }
+}
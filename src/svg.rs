@@ -0,0 +1,262 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::io::Write;
+
+use crate::diff::{CellChange, GridDiff};
+use crate::grid::SizedGrid;
+use crate::image::{self, Colour, Palette};
+use crate::life::LifeGridType;
+
+
+/// Draws grids as SVG images, for figures.
+/// 
+/// A single grid, or a strip of successive generations side by side, each
+/// drawn as a square per cell in the colour of its state, with optional
+/// grid lines, coordinate labels and highlighting of the cells born and
+/// dying in each generation.
+#[derive(Clone, Debug)]
+pub struct SvgRenderer {
+    cell_size: usize,
+    palette: Palette,
+    grid_lines: Option<Colour>,
+    is_labelled: bool,
+    is_highlighting: bool
+}
+
+impl SvgRenderer {
+    /// The colour of cells that have become 'live'.
+    pub const BIRTH_COLOUR: Colour = (0, 160, 0);
+
+    /// The colour of cells that were 'live' and aren't any more.
+    pub const DEATH_COLOUR: Colour = (240, 150, 150);
+
+    /// The colour of labels.
+    const LABEL_COLOUR: Colour = (96, 96, 96);
+
+    /// The space around & between the grids, in pixels.
+    const SPACING: usize = 10;
+
+    /// The space for the coordinate labels, in pixels.
+    const LABEL_WIDTH: usize = 30;
+    const LABEL_HEIGHT: usize = 14;
+
+    /// The space for the generation captions of a strip, in pixels.
+    const CAPTION_HEIGHT: usize = 18;
+
+    /// Initialises a renderer of 10-pixel cells in the default colours
+    /// (see `Palette::default_states()`).
+    pub fn new() -> Self {
+        Self {
+            cell_size: 10,
+            palette: Palette::default_states(2),
+            grid_lines: None,
+            is_labelled: false,
+            is_highlighting: false
+        }
+    }
+
+    /// Sets the size of each cell, in pixels.
+    pub fn set_cell_size(&mut self, cell_size: usize) {
+        self.cell_size = cell_size.max(1);
+    }
+
+    /// Sets the colours of the states of cells.
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
+    }
+
+    /// Sets the colour of lines between the cells, if any.
+    pub fn set_grid_lines(&mut self, grid_lines: Option<Colour>) {
+        self.grid_lines = grid_lines;
+    }
+
+    /// Sets the renderer to label the rows & columns with their
+    /// coordinates.
+    pub fn set_labelled(&mut self, is_labelled: bool) {
+        self.is_labelled = is_labelled;
+    }
+
+    /// Sets the renderer to highlight the cells born and dying in each
+    /// generation - see `GridDiff`.
+    pub fn set_highlighting(&mut self, is_highlighting: bool) {
+        self.is_highlighting = is_highlighting;
+    }
+
+    /// Writes a grid as an SVG image.
+    /// 
+    /// The grid's top-left cell is at the given origin, which the labels
+    /// are relative to (see `Universe::get_origin()`).
+    pub fn write(&self, grid: &LifeGridType, origin: (isize, isize), w: &mut dyn Write) -> std::io::Result<()> {
+        self.write_strip(0, std::slice::from_ref(grid), None, origin, w)
+    }
+
+    /// Writes successive generations side by side as an SVG image, each
+    /// captioned with its generation number, starting from the given
+    /// generation.
+    /// 
+    /// The grids must all be the same size, with their top-left cells at
+    /// the given origin. When highlighting, the changes to the first grid
+    /// are from the previous grid, if given.
+    pub fn write_strip(&self, generation: usize, grids: &[LifeGridType], previous: Option<&LifeGridType>, origin: (isize, isize), w: &mut dyn Write) -> std::io::Result<()> {
+        let (columns, rows) = grids.first().map(|grid| (grid.get_width(), grid.get_height())).unwrap_or((0, 0));
+        let is_captioned = grids.len() > 1;
+
+        // Each grid sits in a panel, after its labels & caption...
+        let (left, top) = self.get_panel_offset(is_captioned);
+        let panel_width = left + (columns * self.cell_size);
+        let width = Self::SPACING + (grids.len() * (panel_width + Self::SPACING));
+        let height = (2 * Self::SPACING) + top + (rows * self.cell_size);
+
+        writeln!(w, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(w, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">", width, height, width, height)?;
+        writeln!(w, "<rect width=\"100%\" height=\"100%\" fill=\"#ffffff\"/>")?;
+
+        let mut previous = previous;
+        for (index, grid) in grids.iter().enumerate() {
+            let panel_x = Self::SPACING + (index * (panel_width + Self::SPACING));
+            writeln!(w, "<g transform=\"translate({},{})\">", panel_x, Self::SPACING)?;
+            if is_captioned {
+                writeln!(w, "{}", self.get_text(left, 12, "start", &format!("Generation {}", generation + index)))?;
+            }
+
+            if self.is_labelled {
+                self.write_labels(columns, rows, origin, (left, top), w)?;
+            }
+
+            writeln!(w, "<g transform=\"translate({},{})\">", left, top)?;
+            self.write_cells(grid, previous.filter(|_| self.is_highlighting), w)?;
+            writeln!(w, "</g>")?;
+            writeln!(w, "</g>")?;
+
+            previous = Some(grid);
+        }
+
+        writeln!(w, "</svg>")
+    }
+
+    /// Writes an SVG image to a file.
+    /// 
+    /// See `write_strip()`.
+    pub fn write_path(&self, path: &str, generation: usize, grids: &[LifeGridType], previous: Option<&LifeGridType>, origin: (isize, isize)) -> Result<(), String> {
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path).map_err(|_| format!("cannot create file '{}'", path))?);
+        self.write_strip(generation, grids, previous, origin, &mut file)
+            .and_then(|_| file.flush())
+            .map_err(|error| format!("cannot write file '{}': {}", path, error))
+    }
+
+    /// Gives the offset of each grid within its panel, after its labels &
+    /// caption.
+    fn get_panel_offset(&self, is_captioned: bool) -> (usize, usize) {
+        let left = if self.is_labelled { Self::LABEL_WIDTH } else { 0 };
+        let top = if self.is_labelled { Self::LABEL_HEIGHT } else { 0 } + if is_captioned { Self::CAPTION_HEIGHT } else { 0 };
+        (left, top)
+    }
+
+    /// Writes the cells of a grid, with their changes from the previous
+    /// grid if given, and the grid lines.
+    fn write_cells(&self, grid: &LifeGridType, previous: Option<&LifeGridType>, w: &mut dyn Write) -> std::io::Result<()> {
+        let size = self.cell_size;
+        let (width, height) = (grid.get_width() * size, grid.get_height() * size);
+        writeln!(w, "<rect width=\"{}\" height=\"{}\" fill=\"{}\"/>", width, height, image::format_colour(self.palette.get(0)))?;
+
+        for cell in grid.iter().filter(|cell| cell.get().get() != 0) {
+            let colour = self.palette.get(cell.get().get() as usize);
+            writeln!(w, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>", cell.get_x() * size, cell.get_y() * size, size, size, image::format_colour(colour))?;
+        }
+
+        // Mark the births & deaths over the cells...
+        if let Some(previous) = previous {
+            for (x, y, change) in GridDiff::between(previous, grid).get_changes() {
+                let (class, colour) = match change {
+                    CellChange::Birth => ("birth", Self::BIRTH_COLOUR),
+                    CellChange::Death => ("death", Self::DEATH_COLOUR),
+                    CellChange::Other => continue
+                };
+
+                writeln!(w, "<rect class=\"{}\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>", class, x * size, y * size, size, size, image::format_colour(colour))?;
+            }
+        }
+
+        if let Some(colour) = self.grid_lines {
+            let mut path = String::new();
+            for column in 0..=grid.get_width() {
+                path += &format!("M{} 0V{}", column * size, height);
+            }
+
+            for row in 0..=grid.get_height() {
+                path += &format!("M0 {}H{}", row * size, width);
+            }
+
+            writeln!(w, "<path d=\"{}\" stroke=\"{}\" stroke-width=\"1\" fill=\"none\"/>", path, image::format_colour(colour))?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the coordinates of the columns above a grid and of the rows
+    /// to its left, at an interval that keeps them apart.
+    fn write_labels(&self, columns: usize, rows: usize, origin: (isize, isize), (left, top): (usize, usize), w: &mut dyn Write) -> std::io::Result<()> {
+        let step = self.get_label_step();
+        let half = self.cell_size / 2;
+
+        for column in (0..columns).filter(|column| (origin.0 + *column as isize).rem_euclid(step as isize) == 0) {
+            writeln!(w, "{}", self.get_text(left + (column * self.cell_size) + half, top - 3, "middle", &(origin.0 + column as isize).to_string()))?;
+        }
+
+        for row in (0..rows).filter(|row| (origin.1 + *row as isize).rem_euclid(step as isize) == 0) {
+            writeln!(w, "{}", self.get_text(left - 4, top + (row * self.cell_size) + half + 4, "end", &(origin.1 + row as isize).to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Gives the interval between labels - 1, 2, 5, 10, 20, 50, ... cells,
+    /// so that the labels are at least 20 pixels apart.
+    fn get_label_step(&self) -> usize {
+        let mut scale = 1;
+        loop {
+            for step in [scale, scale * 2, scale * 5] {
+                if step * self.cell_size >= 20 {
+                    return step;
+                }
+            }
+
+            scale *= 10;
+        }
+    }
+
+    /// Gives the SVG element of a label.
+    fn get_text(&self, x: usize, y: usize, anchor: &str, text: &str) -> String {
+        format!("<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"10\" text-anchor=\"{}\" fill=\"{}\">{}</text>", x, y, anchor, image::format_colour(Self::LABEL_COLOUR), text)
+    }
+}
+
+impl Default for SvgRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,179 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::fmt::Display;
+
+use crate::grid::{BoundingBox, Symmetry};
+use crate::life::{LifeGrid, LifeGridType};
+use crate::period::MotionDetector;
+use crate::rule::Rule;
+use crate::universe::Universe;
+
+
+/// A glide symmetry - a later generation of a pattern is a rotation or
+/// reflection of the pattern, displaced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Glide {
+    pub symmetry: Symmetry,
+
+    /// The number of generations after which the pattern is transformed.
+    pub generations: usize,
+
+    /// The displacement of the transformed pattern's bounding box.
+    pub dx: isize,
+    pub dy: isize
+}
+
+impl Display for Glide {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} after {} generations, displaced by ({}, {})", self.symmetry, self.generations, self.dx, self.dy)
+    }
+}
+
+/// The symmetries of a pattern.
+/// 
+/// See `analyse()`.
+pub struct SymmetryReport {
+    /// The smallest region containing the pattern.
+    pub bounds: Option<BoundingBox>,
+
+    /// The number of 'live' cells.
+    pub population: usize,
+
+    /// The rotations & reflections (other than the identity) under which
+    /// the pattern is unchanged.
+    pub symmetries: Vec<Symmetry>,
+
+    /// The glide symmetries, each with the fewest generations.
+    pub glides: Vec<Glide>
+}
+
+impl SymmetryReport {
+    /// Gives the name of the pattern's symmetry group, from its order -
+    /// `C1` (no symmetry), `C2`/`C4` (rotations only), `D2` (a single
+    /// reflection), `D4` (two reflections) or `D8` (every symmetry).
+    pub fn get_group(&self) -> &'static str {
+        let has_reflection = self.symmetries.iter().any(Symmetry::is_reflection);
+        let order = self.symmetries.len() + 1;
+
+        match (order, has_reflection) {
+            (8, _) => "D8",
+            (4, true) => "D4",
+            (4, false) => "C4",
+            (2, true) => "D2",
+            (2, false) => "C2",
+            _ => "C1"
+        }
+    }
+}
+
+impl Display for SymmetryReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.bounds {
+            Some(bounds) => writeln!(f, "Bounding box: {}x{} at ({}, {})", bounds.width, bounds.height, bounds.x, bounds.y)?,
+            None => writeln!(f, "Bounding box: none")?
+        }
+
+        writeln!(f, "Population: {}", self.population)?;
+
+        let symmetries: Vec<String> = self.symmetries.iter().map(Symmetry::to_string).collect();
+        if symmetries.is_empty() {
+            writeln!(f, "Symmetry: {}", self.get_group())?;
+        }
+        else {
+            writeln!(f, "Symmetry: {} ({})", self.get_group(), symmetries.join(", "))?;
+        }
+
+        if self.glides.is_empty() {
+            writeln!(f, "Glide symmetries: none")?;
+        }
+        else {
+            writeln!(f, "Glide symmetries:")?;
+            for glide in &self.glides {
+                writeln!(f, "  {}", glide)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Analyses the symmetries of the pattern in a grid.
+/// 
+/// The pattern is trimmed to its bounding box, so a symmetry is about the
+/// centre of the bounding box. Glide symmetries are found by running the
+/// given rule in an unbounded universe for up to the given number of
+/// generations, stopping early once the pattern repeats.
+pub fn analyse(grid: &LifeGridType, rule: &dyn Rule, max_generations: usize) -> SymmetryReport {
+    let bounds = grid.get_bounding_box();
+    let pattern = match bounds {
+        Some(bounds) => grid.crop(&bounds, 0),
+        None => LifeGridType::init_life(0, 0)
+    };
+
+    let symmetries: Vec<Symmetry> = Symmetry::ALL.iter()
+        .filter(|symmetry| **symmetry != Symmetry::Identity)
+        .filter(|symmetry| pattern.transform(**symmetry) == pattern)
+        .copied()
+        .collect();
+
+    // Look for later generations that are transformations of the pattern,
+    // other than those under which the pattern is unchanged anyway...
+    let transforms: Vec<(Symmetry, LifeGridType)> = Symmetry::ALL.iter()
+        .filter(|symmetry| (**symmetry != Symmetry::Identity) && !symmetries.contains(symmetry))
+        .map(|symmetry| (*symmetry, pattern.transform(*symmetry)))
+        .collect();
+
+    let mut glides: Vec<Glide> = Vec::new();
+    let mut universe = Universe::from_grid(grid);
+    let mut detector = MotionDetector::new();
+    let origin = universe.get_origin();
+    detector.push(0, &universe);
+    for generation in 1..=max_generations {
+        universe.step(rule);
+        if universe.is_empty() {
+            break;
+        }
+
+        for (symmetry, transformed) in &transforms {
+            if (universe.get_grid() == transformed) && !glides.iter().any(|glide| glide.symmetry == *symmetry) {
+                let (x, y) = universe.get_origin();
+                glides.push(Glide { symmetry: *symmetry, generations: generation, dx: x - origin.0, dy: y - origin.1 });
+            }
+        }
+
+        if detector.push(generation, &universe).is_some() {
+            break;
+        }
+    }
+
+    SymmetryReport {
+        bounds,
+        population: grid.get_population(),
+        symmetries,
+        glides
+    }
+}
@@ -0,0 +1,507 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::grid::{Grid, SimpleGrid, SizedGrid};
+use crate::life::{LifeGrid, LifeGridType, LifeState};
+use crate::rule::Rule;
+
+
+/// The rule table for WireWorld - states are 0 (empty), 1 (electron head),
+/// 2 (electron tail) & 3 (conductor).
+pub const WIREWORLD_TABLE: &str = "\
+@RULE WireWorld
+@TABLE
+n_states:4
+neighborhood:Moore
+symmetries:permute
+var a={0,1,2,3}
+var b={0,1,2,3}
+var c={0,1,2,3}
+var d={0,1,2,3}
+var e={0,1,2,3}
+var f={0,1,2,3}
+var g={0,1,2,3}
+var h={0,1,2,3}
+var i={0,2,3}
+var j={0,2,3}
+var k={0,2,3}
+var l={0,2,3}
+var m={0,2,3}
+var n={0,2,3}
+var o={0,2,3}
+# An electron head becomes a tail, and a tail becomes conductor...
+1,a,b,c,d,e,f,g,h,2
+2,a,b,c,d,e,f,g,h,3
+# Conductor becomes a head with exactly 1 or 2 neighbouring heads...
+3,1,i,j,k,l,m,n,o,1
+3,1,1,i,j,k,l,m,n,1
+";
+
+/// The offsets of the neighbours, in the order that they are given in
+/// a transition, for each of the supported neighbourhoods.
+const MOORE_OFFSETS: &[(isize, isize)] = &[(0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1)];
+const VON_NEUMANN_OFFSETS: &[(isize, isize)] = &[(0, -1), (1, 0), (0, 1), (-1, 0)];
+const HEXAGONAL_OFFSETS: &[(isize, isize)] = &[(0, -1), (1, 0), (1, 1), (0, 1), (-1, 0), (-1, -1)];
+
+/// One of the values of a transition - the value of a cell must match it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    /// A single state.
+    State(u8),
+
+    /// A named variable - the same variable must have the same value
+    /// wherever it appears in a transition.
+    Variable(String),
+
+    /// An unnamed set of states.
+    Set(Vec<u8>)
+}
+
+/// A transition of a rule table - if a cell and its neighbours match
+/// the inputs, the cell's next state is the output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Transition {
+    centre: Token,
+    neighbours: Vec<Token>,
+    output: Token
+}
+
+/// A rule given by a rule table, as a subset of Golly's `.rule` format.
+/// 
+/// The `@TABLE` section gives the number of states (`n_states`), the
+/// neighbourhood (`Moore`, `vonNeumann` or `hexagonal`), the symmetries
+/// (`none`, `rotateN`, `reflect_horizontal`, `rotateNreflect` or
+/// `permute`), variables (`var a={0,1,2}`) and the transitions. Each
+/// transition gives the cell's state, its neighbours' states (clockwise from
+/// north) and the cell's next state. The first transition that matches, in
+/// any of its symmetries, is used - if none match the state is unchanged.
+pub struct TableRule {
+    name: String,
+    path: Option<String>,
+    states: u8,
+    offsets: &'static [(isize, isize)],
+    neighbourhood_name: String,
+    symmetry_name: String,
+    symmetries: Vec<Vec<usize>>,        // <--- Permutations of the neighbour
+                                        //      positions, or empty for
+                                        //      'permute'.
+    variables: HashMap<String, Vec<u8>>,
+    transitions: Vec<Transition>,
+    cache: RefCell<HashMap<Vec<u8>, u8>>
+}
+
+impl TableRule {
+    /// Gives the built-in WireWorld rule.
+    pub fn wireworld() -> Self {
+        Self::parse(WIREWORLD_TABLE).expect("built-in WireWorld table should be valid")
+    }
+
+    /// Gives a built-in rule by name, if there is one.
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "wireworld" => Some(Self::wireworld()),
+            _ => None
+        }
+    }
+
+    /// Parses the contents of a rule table file.
+    /// 
+    /// Sections other than `@RULE` & `@TABLE` (e.g. `@COLORS`) are ignored.
+    /// If there are no sections at all, the whole contents are the table.
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut rule = Self {
+            name: String::from("table"),
+            path: None,
+            states: 0,
+            offsets: MOORE_OFFSETS,
+            neighbourhood_name: String::from("Moore"),
+            symmetry_name: String::from("none"),
+            symmetries: Vec::new(),
+            variables: HashMap::new(),
+            transitions: Vec::new(),
+            cache: RefCell::new(HashMap::new())
+        };
+
+        let has_sections = contents.lines().any(|line| line.trim_start().starts_with('@'));
+        let mut is_table = !has_sections;
+        let mut symmetry = String::from("none");
+
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let error = |message: String| format!("{}, at line {} of rule table", message, number + 1);
+
+            if let Some(section) = line.strip_prefix('@') {
+                let mut words = section.split_whitespace();
+                match words.next() {
+                    Some("RULE") => {
+                        rule.name = words.next().map(String::from).unwrap_or(rule.name);
+                        is_table = false;
+                    },
+                    Some("TABLE") => is_table = true,
+                    _ => is_table = false
+                }
+            }
+            else if !is_table {
+                continue;
+            }
+            else if let Some((key, value)) = line.split_once(':') {
+                let value = value.trim();
+                match key.trim() {
+                    "n_states" => rule.states = match value.parse::<u8>() {
+                        Ok(n) if n >= 2 => n,
+                        _ => return Err(error(format!("bad number of states '{}'", value)))
+                    },
+                    "neighborhood" | "neighbourhood" => {
+                        rule.offsets = match value.to_lowercase().as_str() {
+                            "moore" => MOORE_OFFSETS,
+                            "vonneumann" => VON_NEUMANN_OFFSETS,
+                            "hexagonal" => HEXAGONAL_OFFSETS,
+                            _ => return Err(error(format!("unsupported neighbourhood '{}'", value)))
+                        };
+                        rule.neighbourhood_name = String::from(value);
+                    },
+                    "symmetries" => symmetry = String::from(value),
+                    _ => return Err(error(format!("unknown setting '{}'", key.trim())))
+                }
+            }
+            else if let Some(definition) = line.strip_prefix("var ") {
+                let (name, values) = definition.split_once('=').ok_or_else(|| error(format!("bad variable '{}'", line)))?;
+                let values = rule.parse_set(values.trim()).map_err(error)?;
+                rule.variables.insert(String::from(name.trim()), values);
+            }
+            else {
+                let transition = rule.parse_transition(line).map_err(error)?;
+                rule.transitions.push(transition);
+            }
+        }
+
+        if rule.states < 2 {
+            return Err(String::from("rule table has no 'n_states'"));
+        }
+
+        rule.symmetries = Self::parse_symmetries(&symmetry, rule.offsets.len())?;
+        rule.symmetry_name = symmetry;
+
+        Ok(rule)
+    }
+
+    /// Parses a set of states such as `{0,1,2}`, which may include the
+    /// values of previously defined variables.
+    fn parse_set(&self, text: &str) -> Result<Vec<u8>, String> {
+        let inner = text.strip_prefix('{')
+                        .and_then(|text| text.strip_suffix('}'))
+                        .ok_or_else(|| format!("bad set '{}'", text))?;
+
+        let mut values = Vec::new();
+        for item in inner.split(',').map(|item| item.trim()) {
+            match self.parse_token(item)? {
+                Token::State(state) => values.push(state),
+                Token::Variable(name) => values.extend(self.variables[&name].iter()),
+                Token::Set(set) => values.extend(set)
+            }
+        }
+
+        Ok(values)
+    }
+
+    fn parse_token(&self, text: &str) -> Result<Token, String> {
+        if text.starts_with('{') {
+            Ok(Token::Set(self.parse_set(text)?))
+        }
+        else if let Ok(state) = text.parse::<u8>() {
+            if (self.states > 0) && (state >= self.states) {
+                return Err(format!("state '{}' is out of range", state));
+            }
+            Ok(Token::State(state))
+        }
+        else if self.variables.contains_key(text) {
+            Ok(Token::Variable(String::from(text)))
+        }
+        else {
+            Err(format!("unknown variable '{}'", text))
+        }
+    }
+
+    /// Parses a transition - either comma-separated, or without commas
+    /// when every value is a single character.
+    fn parse_transition(&self, line: &str) -> Result<Transition, String> {
+        let items: Vec<String> = if line.contains(',') {
+            // Split on the commas that aren't within a set...
+            let mut items = vec![String::new()];
+            let mut depth = 0;
+            for c in line.chars() {
+                match c {
+                    '{' => depth += 1,
+                    '}' => depth -= 1,
+                    ',' if depth == 0 => {
+                        items.push(String::new());
+                        continue;
+                    },
+                    _ => {}
+                }
+                items.last_mut().unwrap().push(c);
+            }
+            items
+        }
+        else {
+            line.chars().filter(|c| !c.is_whitespace()).map(String::from).collect()
+        };
+
+        let count = self.offsets.len();
+        if items.len() != count + 2 {
+            return Err(format!("transition '{}' should have {} values", line, count + 2));
+        }
+
+        let tokens = items.iter()
+                        .map(|item| self.parse_token(item.trim()))
+                        .collect::<Result<Vec<Token>, String>>()?;
+
+        match &tokens[count + 1] {
+            Token::Variable(name) if !tokens[..=count].contains(&tokens[count + 1]) => {
+                return Err(format!("output variable '{}' is not an input", name));
+            },
+            Token::Set(_) => return Err(format!("transition '{}' has a set as its output", line)),
+            _ => {}
+        }
+
+        Ok(Transition {
+            centre: tokens[0].clone(),
+            neighbours: tokens[1..=count].to_vec(),
+            output: tokens[count + 1].clone()
+        })
+    }
+
+    /// Gives the permutations of the neighbour positions for the given
+    /// symmetries - empty for `permute`, which allows any permutation.
+    fn parse_symmetries(symmetry: &str, count: usize) -> Result<Vec<Vec<usize>>, String> {
+        let rotation = |step: usize| -> Vec<usize> { (0..count).map(|i| (i + step) % count).collect() };
+        let reflection = |permutation: &Vec<usize>| -> Vec<usize> { permutation.iter().map(|i| (count - i) % count).collect() };
+        let rotations = |n: usize| -> Result<Vec<Vec<usize>>, String> {
+            if (n == 0) || (n > count) || !count.is_multiple_of(n) {
+                return Err(format!("unsupported symmetries '{}'", symmetry));
+            }
+            Ok((0..n).map(|i| rotation(i * count / n)).collect())
+        };
+
+        let symmetry = symmetry.trim();
+        match symmetry {
+            "none" => Ok(vec![rotation(0)]),
+            "permute" => Ok(Vec::new()),
+            "reflect_horizontal" => Ok(vec![rotation(0), reflection(&rotation(0))]),
+            _ => {
+                let (n, is_reflected) = match symmetry.strip_prefix("rotate") {
+                    Some(n) => match n.strip_suffix("reflect") {
+                        Some(n) => (n, true),
+                        None => (n, false)
+                    },
+                    None => return Err(format!("unsupported symmetries '{}'", symmetry))
+                };
+
+                let mut permutations = rotations(n.parse::<usize>().unwrap_or(0))?;
+                if is_reflected {
+                    let reflected: Vec<Vec<usize>> = permutations.iter().map(reflection).collect();
+                    permutations.extend(reflected);
+                }
+
+                Ok(permutations)
+            }
+        }
+    }
+
+    /// Gives the name of the rule, from its `@RULE` section.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets the path of the file the table was read from.
+    pub fn set_path(&mut self, path: &str) {
+        self.path = Some(String::from(path));
+    }
+
+    /// Gives the next state of a cell given its state and its neighbours'
+    /// states, in the order of the table's neighbourhood.
+    pub fn next_table_state(&self, centre: u8, neighbours: &[u8]) -> u8 {
+        let mut key = Vec::with_capacity(neighbours.len() + 1);
+        key.push(centre);
+        key.extend_from_slice(neighbours);
+
+        if let Some(state) = self.cache.borrow().get(&key) {
+            return *state;
+        }
+
+        let state = self.transitions
+                        .iter()
+                        .find_map(|transition| self.apply_transition(transition, centre, neighbours))
+                        .unwrap_or(centre);
+
+        self.cache.borrow_mut().insert(key, state);
+
+        state
+    }
+
+    /// Gives the output of the transition if it matches, in any of its
+    /// symmetries.
+    fn apply_transition(&self, transition: &Transition, centre: u8, neighbours: &[u8]) -> Option<u8> {
+        let mut bindings: HashMap<&str, u8> = HashMap::new();
+        if !self.matches(&transition.centre, centre, &mut bindings) {
+            return None;
+        }
+
+        let bindings = if self.symmetries.is_empty() {
+            let mut is_used = vec![false; neighbours.len()];
+            if !self.matches_permuted(&transition.neighbours, neighbours, &mut is_used, &mut bindings) {
+                return None;
+            }
+            bindings
+        }
+        else {
+            self.symmetries.iter().find_map(|permutation| {
+                let mut trial = bindings.clone();
+                transition.neighbours
+                    .iter()
+                    .zip(permutation)
+                    .all(|(token, position)| self.matches(token, neighbours[*position], &mut trial))
+                    .then_some(trial)
+            })?
+        };
+
+        match &transition.output {
+            Token::State(state) => Some(*state),
+            Token::Variable(name) => bindings.get(name.as_str()).copied(),
+            Token::Set(_) => None
+        }
+    }
+
+    /// Matches the neighbour tokens against the neighbours in any order,
+    /// trying each unused neighbour for each token in turn.
+    fn matches_permuted<'t>(&self, tokens: &'t [Token], neighbours: &[u8], is_used: &mut [bool], bindings: &mut HashMap<&'t str, u8>) -> bool {
+        let Some((token, rest)) = tokens.split_first() else {
+            return true;
+        };
+
+        for position in 0..neighbours.len() {
+            if is_used[position] {
+                continue;
+            }
+
+            let mut trial = bindings.clone();
+            if self.matches(token, neighbours[position], &mut trial) {
+                is_used[position] = true;
+                if self.matches_permuted(rest, neighbours, is_used, &mut trial) {
+                    *bindings = trial;
+                    return true;
+                }
+                is_used[position] = false;
+            }
+        }
+
+        false
+    }
+
+    /// Indicates if the given value matches the token, binding the token's
+    /// variable (if any) to the value.
+    fn matches<'t>(&self, token: &'t Token, value: u8, bindings: &mut HashMap<&'t str, u8>) -> bool {
+        match token {
+            Token::State(state) => *state == value,
+            Token::Set(values) => values.contains(&value),
+            Token::Variable(name) => match bindings.get(name.as_str()) {
+                Some(bound) => *bound == value,
+                None if self.variables[name].contains(&value) => {
+                    bindings.insert(name, value);
+                    true
+                },
+                None => false
+            }
+        }
+    }
+}
+
+impl Rule for TableRule {
+    fn get_states(&self) -> u8 {
+        self.states
+    }
+
+    /// Table rules depend on the states of all of the neighbours rather than
+    /// a count, so the state is unchanged - see `apply()`.
+    fn next_state(&self, state: LifeState, _count: i32) -> LifeState {
+        state
+    }
+
+    /// Gives a grid of the count of non-empty neighbours of every cell - for
+    /// information only, as the next generation depends on the neighbours'
+    /// states (see `apply()`).
+    fn count_neighbours(&self, grid: &LifeGridType) -> SimpleGrid<i32> {
+        let mut neighbours_grid = SimpleGrid::init(grid.get_width(), grid.get_height(), 0);
+        for cell in grid {
+            let count = self.offsets
+                            .iter()
+                            .filter(|(dx, dy)| matches!(cell.get_relative(*dx, *dy), Some(state) if state.get() != 0))
+                            .count();
+            neighbours_grid.set(cell.get_x(), cell.get_y(), count as i32);
+        }
+
+        neighbours_grid
+    }
+
+    /// Gives the path of the file the table was read from or, for a
+    /// built-in table, its name (see `builtin()`).
+    fn get_rulestring(&self) -> String {
+        self.path.clone().unwrap_or_else(|| self.name.clone())
+    }
+
+    /// Gives the next generation by matching each cell & its neighbours
+    /// against the table's transitions.
+    /// 
+    /// Any neighbours that are out of bounds are in state 0.
+    fn apply(&self, grid: &LifeGridType, _neighbours_grid: &SimpleGrid<i32>) -> LifeGridType {
+        let mut new_grid = LifeGridType::init_life(grid.get_width(), grid.get_height());
+        let mut neighbours = vec![0; self.offsets.len()];
+
+        for cell in grid {
+            for (i, (dx, dy)) in self.offsets.iter().enumerate() {
+                neighbours[i] = cell.get_relative(*dx, *dy).map(|state| state.get()).unwrap_or(0);
+            }
+
+            new_grid.set(cell.get_x(), cell.get_y(), LifeState(self.next_table_state(cell.get().get(), &neighbours)));
+        }
+
+        new_grid
+    }
+}
+
+impl Display for TableRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({} states, {}, {})", self.name, self.states, self.neighbourhood_name, self.symmetry_name)
+    }
+}
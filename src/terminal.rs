@@ -0,0 +1,228 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::io::{IsTerminal, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+
+/// ANSI escape sequences for controlling the terminal.
+pub const CLEAR_SCREEN: &str = "\x1b[2J";
+pub const CLEAR_TO_END: &str = "\x1b[J";
+pub const CLEAR_LINE: &str = "\x1b[K";
+pub const CURSOR_HOME: &str = "\x1b[H";
+pub const HIDE_CURSOR: &str = "\x1b[?25l";
+pub const SHOW_CURSOR: &str = "\x1b[?25h";
+pub const REVERSE_VIDEO: &str = "\x1b[7m";
+pub const RESET_ATTRIBUTES: &str = "\x1b[0m";
+
+/// Gives the escape sequence that moves the cursor to the given (1-based)
+/// row & column.
+pub fn move_cursor(row: usize, column: usize) -> String {
+    format!("\x1b[{};{}H", row, column)
+}
+
+/// Indicates if both stdin and stdout are a terminal, so that the terminal
+/// can be controlled and keys read from it.
+pub fn is_interactive() -> bool {
+    std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
+
+
+//---------------------------------------------------------------------------//
+
+
+/// Puts the terminal into 'raw' mode - keys are read as they're pressed,
+/// without being echoed, and Ctrl-C is read as a key rather than ending the
+/// program.
+/// 
+/// A read waits for at most a tenth of a second, giving nothing if no key
+/// has been pressed, so that a `KeyReader` can be stopped.
+/// 
+/// The terminal's settings are changed with `stty`, and restored when this
+/// is dropped.
+pub struct RawMode {
+    saved: String
+}
+
+impl RawMode {
+    /// Enables raw mode, if stdin is a terminal and `stty` is available.
+    pub fn enable() -> Option<Self> {
+        if !std::io::stdin().is_terminal() {
+            return None;
+        }
+
+        let saved = stty(&["-g"])?;
+        stty(&["-icanon", "-echo", "-isig", "min", "0", "time", "1"])?;
+
+        Some(Self { saved: String::from(saved.trim()) })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        stty(&[&self.saved]);
+    }
+}
+
+/// Runs `stty` on the terminal, giving its output if successful.
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    if output.status.success() { Some(String::from_utf8_lossy(&output.stdout).into_owned()) } else { None }
+}
+
+
+//---------------------------------------------------------------------------//
+
+
+/// A key read from the terminal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Up,
+    Down,
+    Left,
+    Right,
+    Enter,
+    Escape,
+    Backspace,
+    CtrlC
+}
+
+impl Key {
+    /// Decodes the key at the start of the given bytes, giving the number
+    /// of bytes used.
+    pub fn decode(bytes: &[u8]) -> Option<(Key, usize)> {
+        let key = match bytes {
+            [] => return None,
+            [0x1b, b'[', b'A', ..] => return Some((Key::Up, 3)),
+            [0x1b, b'[', b'B', ..] => return Some((Key::Down, 3)),
+            [0x1b, b'[', b'C', ..] => return Some((Key::Right, 3)),
+            [0x1b, b'[', b'D', ..] => return Some((Key::Left, 3)),
+            [0x1b, ..] => Key::Escape,
+            [0x03, ..] => Key::CtrlC,
+            [b'\r' | b'\n', ..] => Key::Enter,
+            [0x7f | 0x08, ..] => Key::Backspace,
+            [byte, ..] if byte.is_ascii() => Key::Char(*byte as char),
+            [_, ..] => {
+                // A multi-byte character...
+                let length = (1..=bytes.len().min(4)).find(|length| std::str::from_utf8(&bytes[..*length]).is_ok())?;
+                let c = std::str::from_utf8(&bytes[..length]).ok()?.chars().next()?;
+                return Some((Key::Char(c), length));
+            }
+        };
+
+        Some((key, 1))
+    }
+}
+
+/// Reads keys from stdin in the background, so that they can be waited for
+/// with a timeout.
+/// 
+/// The reading thread is stopped when this is dropped - which must be while
+/// the terminal is still in raw mode (see `RawMode`), or the last read won't
+/// time out.
+pub struct KeyReader {
+    receiver: Receiver<Key>,
+    is_stopping: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>
+}
+
+impl KeyReader {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let is_stopping = Arc::new(AtomicBool::new(false));
+
+        let is_thread_stopping = Arc::clone(&is_stopping);
+        let thread = thread::spawn(move || {
+            let mut stdin = std::io::stdin();
+            let is_terminal = stdin.is_terminal();
+            let mut buffer = [0u8; 16];
+            while !is_thread_stopping.load(Ordering::Relaxed) {
+                // Nothing read from a terminal is a timeout, otherwise the
+                // end of the input...
+                let count = match stdin.read(&mut buffer) {
+                    Ok(0) if is_terminal => continue,
+                    Ok(0) | Err(_) => return,
+                    Ok(count) => count
+                };
+
+                let mut bytes = &buffer[..count];
+                while let Some((key, length)) = Key::decode(bytes) {
+                    if sender.send(key).is_err() {
+                        return;
+                    }
+                    bytes = &bytes[length..];
+                }
+            }
+        });
+
+        Self { receiver, is_stopping, thread: Some(thread) }
+    }
+
+    /// Waits for a key for up to the given time.
+    pub fn read_timeout(&self, timeout: Duration) -> Option<Key> {
+        match self.receiver.recv_timeout(timeout) {
+            Ok(key) => Some(key),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => {
+                // No more keys, but still wait...
+                thread::sleep(timeout);
+                None
+            }
+        }
+    }
+
+    /// Waits for a key.
+    pub fn read(&self) -> Option<Key> {
+        self.receiver.recv().ok()
+    }
+}
+
+impl Default for KeyReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for KeyReader {
+    fn drop(&mut self) {
+        self.is_stopping.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+    }
+}
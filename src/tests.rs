@@ -24,10 +24,45 @@
 
 ---------------------------------------------------------------------------- */
 
+// The test modules share the name of their files, and separate `#[cfg(test)]`
+// from the module with a blank line...
+#![allow(clippy::module_inception, clippy::empty_line_after_outer_attr)]
+
+#[cfg(test)]
+mod common;
+
+mod test_animation;
+mod test_census;
+mod test_config;
+mod test_diff;
+mod test_editor;
 mod test_env;
+// Predates these lints...
+#[allow(clippy::needless_lifetimes)]
 mod test_file;
+mod test_gif;
 mod test_gridcell;
+mod test_heatmap;
+mod test_hensel;
+mod test_html;
+mod test_image;
 mod test_life;
+mod test_ltl;
+mod test_methuselah;
+mod test_neighbourhood;
+// Predates these lints...
+#[allow(clippy::needless_borrow, mismatched_lifetime_syntaxes)]
 mod test_parser;
+mod test_period;
+mod test_render;
+mod test_rle;
+mod test_rule;
+mod test_search;
 mod test_simplegrid;
-mod test_sparsegrid;
\ No newline at end of file
+mod test_sparsegrid;
+mod test_stats;
+mod test_svg;
+mod test_symmetry;
+mod test_table;
+mod test_terminal;
+mod test_universe;
\ No newline at end of file
@@ -0,0 +1,79 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::grid::*;
+use crate::life::*;
+
+/// Builds a grid from rows of text, with `*` a live cell, `o` a cell in state
+/// 2 and anything else dead...
+pub fn create_grid(rows: &[&str]) -> LifeGridType {
+    let mut grid = SimpleGrid::init_life(rows[0].len(), rows.len());
+    for (y, row) in rows.iter().enumerate() {
+        for (x, c) in row.chars().enumerate() {
+            match c {
+                '*' => grid.set_live(x, y),
+                'o' => grid.set_state(x, y, 2),
+                _ => ()
+            }
+        }
+    }
+
+    grid
+}
+
+/// Builds a grid from rows of digits, each the state of its cell...
+pub fn create_state_grid(rows: &[&str]) -> LifeGridType {
+    let mut grid = SimpleGrid::init_life(rows[0].len(), rows.len());
+    for (y, row) in rows.iter().enumerate() {
+        for (x, c) in row.chars().enumerate() {
+            grid.set_state(x, y, c.to_digit(10).unwrap() as u8);
+        }
+    }
+
+    grid
+}
+
+/// Builds a grid of the characters of rows of text...
+pub fn create_char_grid(rows: &[&str]) -> SimpleGrid<char> {
+    let mut grid = SimpleGrid::init(rows[0].len(), rows.len(), ' ');
+    for (y, row) in rows.iter().enumerate() {
+        for (x, c) in row.chars().enumerate() {
+            grid.set(x, y, c);
+        }
+    }
+
+    grid
+}
+
+/// Gives the hash of a value, e.g. to check that equal grids hash equally...
+pub fn hash_of<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,63 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_animation {
+    use crate::animation::*;
+    use crate::render::*;
+    use crate::tests::common::*;
+
+    #[test]
+    fn animation_show_not_terminal() {
+        // Frames written anywhere but a terminal simply follow each other,
+        // without escape sequences or waiting...
+        let grid = create_grid(&["   ", "***", "   "]);
+        let mut animation = Animation::new(0.001, Renderer::new(), false);
+        let mut frame = Vec::new();
+        Renderer::new().write(&grid, (0, 0), &mut frame).unwrap();
+
+        let mut output = Vec::new();
+        assert!(!animation.is_terminal());
+        assert!(animation.show(0, &grid, (0, 0), &mut output).unwrap());
+        assert!(animation.show(1, &grid, (0, 0), &mut output).unwrap());
+        animation.finish(&mut output).unwrap();
+
+        let frame = String::from_utf8(frame).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), format!("iteration: 0\n{}iteration: 1\n{}", frame, frame));
+    }
+
+    #[test]
+    fn animation_show_closed_output() {
+        // A frame that can't be written - e.g. to a closed pipe - is an
+        // error, rather than the animation carrying on regardless...
+        let grid = create_grid(&["   ", "***", "   "]);
+        let mut animation = Animation::new(0.001, Renderer::new(), false);
+        let mut output = [0u8; 4];
+
+        assert!(animation.show(0, &grid, (0, 0), &mut &mut output[..]).is_err());
+    }
+}
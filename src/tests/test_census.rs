@@ -0,0 +1,246 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_census {
+    use crate::census::*;
+    use crate::grid::*;
+    use crate::life::*;
+    use crate::rule::*;
+    use crate::tests::common::*;
+    use crate::universe::*;
+
+    #[test]
+    fn census_separate() {
+        let grid = create_grid(&[
+            "**    ",
+            "**   *",
+            "     *",
+            "  *  *",
+            "   *  "
+        ]);
+
+        let objects = separate(&grid.get_occupied_cells(), 0);
+
+        assert_eq!(objects.len(), 3);
+        assert_eq!(objects[0].get_population(), 4);
+        assert_eq!(objects[0].get_bounding_box(), Some(BoundingBox::new(0, 0, 2, 2)));
+        assert_eq!(objects[1].get_cells(), &[(5, 1, 1), (5, 2, 1), (5, 3, 1)]);
+        assert_eq!(objects[2].get_cells(), &[(2, 3, 1), (3, 4, 1)]);
+    }
+
+    #[test]
+    fn census_separate_gap() {
+        let grid = create_grid(&["* *   *"]);
+
+        assert_eq!(separate(&grid.get_occupied_cells(), 0).len(), 3);
+        assert_eq!(separate(&grid.get_occupied_cells(), 1).len(), 2);
+        assert_eq!(separate(&grid.get_occupied_cells(), 3).len(), 1);
+    }
+
+    #[test]
+    fn census_separate_sparse() {
+        let mut grid: SparseGrid<LifeState> = SparseGrid::new();
+        grid.set(100, 100, LifeState::LIVE);
+        grid.set(101, 101, LifeState::LIVE);
+        grid.set(500, 3, LifeState::LIVE);
+        grid.set(50, 50, LifeState::DEAD);
+
+        let objects = separate(&grid.get_occupied_cells(), 0);
+
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[0].get_cells(), &[(500, 3, 1)]);
+    }
+
+    #[test]
+    fn census_canonical() {
+        // The four orientations of a boat are the same object...
+        let boats = [
+            create_grid(&["** ", "* *", " * "]),
+            create_grid(&[" **", "* *", " * "]),
+            create_grid(&[" * ", "* *", " **"]),
+            create_grid(&[" * ", "* *", "** "])
+        ];
+
+        for boat in &boats {
+            assert_eq!(Object::from_grid(boat).get_code(), "**.$*.*$.*.");
+        }
+
+        assert_ne!(Object::from_grid(&create_grid(&["** ", "* *", " **"])).get_code(), "**.$*.*$.*.");
+    }
+
+    #[test]
+    fn census_canonical_translated() {
+        let near = Object::from_cells(vec![(0, 0, 1), (1, 0, 1), (2, 0, 1)]);
+        let far = Object::from_cells(vec![(-50, 7, 1), (-50, 8, 1), (-50, 9, 1)]);
+
+        assert_eq!(near.get_canonical(), far.get_canonical());
+        assert_eq!(near.get_code(), "***");
+    }
+
+    #[test]
+    fn census_catalogue_phases() {
+        // Every phase of an object in the catalogue is identified...
+        let catalogue = Catalogue::builtin();
+        let rule = LifeRule::default();
+        let mut universe = Universe::from_grid(&create_grid(&[" * ", "  *", "***"]));
+
+        for _ in 0..8 {
+            let object = Object::from_grid(universe.get_grid());
+            assert_eq!(catalogue.identify(&object), Some("glider"));
+            universe.step(&rule);
+        }
+
+        assert_eq!(catalogue.identify(&Object::from_grid(&create_grid(&["*", "*", "*"]))), Some("blinker"));
+        assert_eq!(catalogue.identify(&Object::from_grid(&create_grid(&["**", "* "]))), None);
+    }
+
+    #[test]
+    fn census_catalogue_periods() {
+        // Each object in the catalogue repeats with its period, so its
+        // pictures are correct...
+        let rule = LifeRule::default();
+        let objects = [
+            (vec![" ** ", "*  *", " * *", "  * "], 1),
+            (vec!["**  ", "* * ", " * *", "  * "], 1),
+            (vec![" *  ", "* * ", " * *", "  * "], 1),
+            (vec![" ***", "*** "], 2),
+            (vec!["**  ", "**  ", "  **", "  **"], 2),
+            (vec!["  ***   ***  ", "             ", "*    * *    *", "*    * *    *", "*    * *    *", "  ***   ***  ", "             ",
+                  "  ***   ***  ", "*    * *    *", "*    * *    *", "*    * *    *", "             ", "  ***   ***  "], 3)
+        ];
+
+        for (rows, period) in objects {
+            let object = Object::from_grid(&create_grid(&rows));
+            let mut universe = Universe::from_grid(&create_grid(&rows));
+            for _ in 0..period {
+                universe.step(&rule);
+            }
+
+            assert_eq!(Object::from_grid(universe.get_grid()).get_code(), object.get_code());
+        }
+    }
+
+    #[test]
+    fn census_take() {
+        let grid = create_grid(&[
+            "**    *** ",
+            "**        ",
+            "          ",
+            " *    **  ",
+            "  *   **  ",
+            "***       ",
+            "          ",
+            "*      ** ",
+            "*      * *",
+            "*       * "
+        ]);
+
+        let census = Census::take(&grid.get_occupied_cells(), &Catalogue::builtin(), 0);
+
+        assert_eq!(census.get_total(), 6);
+        assert_eq!(census.get_count("block"), 2);
+        assert_eq!(census.get_count("blinker"), 2);
+        assert_eq!(census.get_count("glider"), 1);
+        assert_eq!(census.get_count("boat"), 1);
+        assert_eq!(census.to_string(), "     2 blinker\n     2 block\n     1 boat\n     1 glider\n");
+    }
+
+    #[test]
+    fn census_take_pulsar() {
+        // The quarters of a pulsar are two cells apart, but it's still named
+        // in every phase, without a gap...
+        let rule = LifeRule::default();
+        let mut universe = Universe::from_grid(&create_grid(&[
+            "  ***   ***  ", "             ", "*    * *    *", "*    * *    *", "*    * *    *", "  ***   ***  ", "             ",
+            "  ***   ***  ", "*    * *    *", "*    * *    *", "*    * *    *", "             ", "  ***   ***  "
+        ]));
+
+        for _ in 0..3 {
+            let census = Census::take(&universe.get_occupied_cells(), &Catalogue::builtin(), 0);
+
+            assert_eq!(census.get_entries(), vec![("pulsar", 1)]);
+            universe.step(&rule);
+        }
+    }
+
+    #[test]
+    fn census_take_near_objects() {
+        // Objects a cell apart stay separate with no gap, even though the
+        // catalogue's pulsar needs a gap of 1...
+        let grid = create_grid(&["** *", "** *", "   *"]);
+
+        assert_eq!(Catalogue::builtin().get_gap(), 1);
+        assert_eq!(Census::take(&grid.get_occupied_cells(), &Catalogue::builtin(), 0).get_entries(), vec![("blinker", 1), ("block", 1)]);
+        assert_eq!(Census::take(&grid.get_occupied_cells(), &Catalogue::builtin(), 1).get_total(), 1);
+    }
+
+    #[test]
+    fn census_unknown_and_merge() {
+        let grid = create_grid(&["**", "* "]);
+        let mut census = Census::take(&grid.get_occupied_cells(), &Catalogue::new(), 0);
+
+        assert_eq!(census.get_count("**$*."), 1);
+
+        let mut other = Census::new();
+        other.add("**$*.", 2);
+        other.add("block", 1);
+        census.merge(&other);
+
+        assert_eq!(census.get_count("**$*."), 3);
+        assert_eq!(census.get_entries(), vec![("**$*.", 3), ("block", 1)]);
+    }
+
+    #[test]
+    fn census_escapees() {
+        let catalogue = Catalogue::builtin();
+        let grid = create_grid(&[
+            "**                  ",
+            "**                  ",
+            "                    ",
+            "                 *  ",
+            "                  * ",
+            "                *** "
+        ]);
+
+        let escapees = find_escapees(&grid.get_occupied_cells(), &catalogue, 0, 10);
+
+        assert_eq!(escapees.len(), 1);
+        assert_eq!(escapees[0].1, "glider");
+        assert!(find_escapees(&grid.get_occupied_cells(), &catalogue, 0, 14).is_empty());
+        assert!(catalogue.is_spaceship("glider"));
+        assert!(!catalogue.is_spaceship("blinker"));
+    }
+
+    #[test]
+    fn census_escapees_alone() {
+        // Spaceships with nothing to escape from aren't escaping...
+        let grid = create_grid(&[" *          * ", "  *        *  ", "***        *** "]);
+
+        assert!(find_escapees(&grid.get_occupied_cells(), &Catalogue::builtin(), 0, 0).is_empty());
+    }
+}
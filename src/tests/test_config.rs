@@ -0,0 +1,101 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_config {
+    use crate::config::*;
+
+    static CONFIG: &str = "\
+# Settings for every run...
+rule = \"B3/S23\"
+census = true
+
+[profile.bench]
+rule = 'B36/S23'    # HighLife
+max = 500
+until_stable = true
+
+[profile.quiet]
+census = false
+";
+
+    fn setting(key: &str, value: ConfigValue) -> ConfigSetting {
+        (String::from(key), value)
+    }
+
+    #[test]
+    fn config_defaults() {
+        let config = Config::parse(CONFIG, None).unwrap();
+
+        assert_eq!(config.get_profiles(), vec!["bench", "quiet"]);
+        assert_eq!(config.get_settings(None).unwrap(), vec![
+            setting("rule", ConfigValue::String(String::from("B3/S23"))),
+            setting("census", ConfigValue::Boolean(true))
+        ]);
+    }
+
+    #[test]
+    fn config_profile_over_defaults() {
+        let config = Config::parse(CONFIG, None).unwrap();
+
+        assert_eq!(config.get_settings(Some("bench")).unwrap(), vec![
+            setting("rule", ConfigValue::String(String::from("B36/S23"))),
+            setting("census", ConfigValue::Boolean(true)),
+            setting("max", ConfigValue::Number(String::from("500"))),
+            setting("until-stable", ConfigValue::Boolean(true))
+        ]);
+        assert_eq!(config.get_settings(Some("quiet")).unwrap()[1], setting("census", ConfigValue::Boolean(false)));
+    }
+
+    #[test]
+    fn config_missing_profile() {
+        let config = Config::parse(CONFIG, Some("life.toml")).unwrap();
+
+        assert_eq!(config.get_settings(Some("nothing")).unwrap_err(), "profile 'nothing' not found in file 'life.toml'");
+    }
+
+    #[test]
+    fn config_values() {
+        let config = Config::parse("a = \"x # y \\\"z\\\"\"\nb = -0.25\nc = 1_000\n", None).unwrap();
+
+        assert_eq!(config.get_settings(None).unwrap(), vec![
+            setting("a", ConfigValue::String(String::from("x # y \"z\""))),
+            setting("b", ConfigValue::Number(String::from("-0.25"))),
+            setting("c", ConfigValue::Number(String::from("1000")))
+        ]);
+    }
+
+    #[test]
+    fn config_parse_bad() {
+        assert!(Config::parse("[bench]\n", None).unwrap_err().contains("bad section '[bench]'"));
+        assert!(Config::parse("rule\n", None).unwrap_err().contains("at line 1"));
+        assert!(Config::parse("\n\nrule = B3/S23\n", Some("life.toml")).unwrap_err().ends_with("at line 3 of file 'life.toml'"));
+        assert!(Config::parse("rule = \"B3/S23\n", None).is_err());
+        assert!(Config::parse("max = inf\n", None).is_err());
+        assert!(Config::parse("bad key = 1\n", None).is_err());
+    }
+}
@@ -0,0 +1,91 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_diff {
+    use crate::diff::*;
+    use crate::grid::*;
+    use crate::life::*;
+    use crate::rule::*;
+    use crate::tests::common::*;
+
+    #[test]
+    fn diff_blinker() {
+        let previous = create_grid(&["   ", "***", "   "]);
+        let current = LifeRule::default().step(&previous);
+
+        let diff = GridDiff::between(&previous, &current);
+
+        assert_eq!(diff.get_births(), vec![(1, 0), (1, 2)]);
+        assert_eq!(diff.get_deaths(), vec![(0, 1), (2, 1)]);
+        assert_eq!(diff.get_changes().len(), 4);
+        assert_eq!(diff.get_change(1, 0), Some(CellChange::Birth));
+        assert_eq!(diff.get_change(1, 1), None);
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn diff_unchanged() {
+        let grid = create_grid(&["**", "**"]);
+
+        assert!(GridDiff::between(&grid, &grid).is_empty());
+    }
+
+    #[test]
+    fn diff_dying() {
+        let previous = create_grid(&["*o", "  "]);
+        let current = create_grid(&["o ", " *"]);
+
+        let diff = GridDiff::between(&previous, &current);
+
+        assert_eq!(diff.get_change(0, 0), Some(CellChange::Death));
+        assert_eq!(diff.get_change(1, 0), Some(CellChange::Other));
+        assert_eq!(diff.get_change(1, 1), Some(CellChange::Birth));
+    }
+
+    #[test]
+    fn diff_write_characters() {
+        let previous = create_grid(&["   ", "***", "   "]);
+        let current = LifeRule::default().step(&previous);
+        let mut output: Vec<u8> = Vec::new();
+
+        GridDiff::between(&previous, &current).write(&current, DiffStyle::Characters, &mut output);
+
+        assert_eq!(String::from_utf8(output).unwrap(), "/---\\\n| + |\n|-*-|\n| + |\n\\---/\n");
+    }
+
+    #[test]
+    fn diff_write_colour() {
+        let previous = create_grid(&["* "]);
+        let current = create_grid(&[" *"]);
+        let mut output: Vec<u8> = Vec::new();
+
+        GridDiff::between(&previous, &current).write(&current, DiffStyle::Colour, &mut output);
+
+        assert_eq!(String::from_utf8(output).unwrap(), "/--\\\n|\x1b[31m*\x1b[0m\x1b[32m*\x1b[0m|\n\\--/\n");
+    }
+}
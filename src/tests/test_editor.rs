@@ -0,0 +1,178 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_editor {
+    use crate::editor::*;
+    use crate::file::*;
+    use crate::grid::*;
+    use crate::life::*;
+    use crate::neighbourhood::*;
+    use crate::rule::*;
+    use crate::terminal::*;
+
+    fn rows(grid: &LifeGridType) -> Vec<String> {
+        (0..grid.get_height())
+            .map(|y| (0..grid.get_width()).map(|x| grid.get(x, y).to_char()).collect())
+            .collect()
+    }
+
+    fn press(editor: &mut Editor, keys: &[Key]) -> bool {
+        let rule = LifeRule::default();
+        keys.iter().all(|key| editor.handle_key(*key, &rule))
+    }
+
+    #[test]
+    fn editor_move_and_toggle() {
+        let mut editor = Editor::new(SimpleGrid::init_life(3, 2), "test.life");
+
+        press(&mut editor, &[Key::Right, Key::Char('l'), Key::Right, Key::Down, Key::Down, Key::Char(' ')]);
+
+        assert_eq!(editor.get_cursor(), (2, 1));
+        assert_eq!(rows(editor.get_grid()), vec!["   ", "  *"]);
+
+        press(&mut editor, &[Key::Left, Key::Up, Key::Up, Key::Char('h'), Key::Left, Key::Enter]);
+
+        assert_eq!(editor.get_cursor(), (0, 0));
+        assert_eq!(rows(editor.get_grid()), vec!["*  ", "  *"]);
+    }
+
+    #[test]
+    fn editor_step_and_undo() {
+        let mut grid = SimpleGrid::init_life(3, 3);
+        for y in 0..3 {
+            grid.set_live(1, y);
+        }
+        let mut editor = Editor::new(grid, "test.life");
+
+        press(&mut editor, &[Key::Char('n')]);
+
+        assert_eq!(editor.get_generation(), 1);
+        assert_eq!(rows(editor.get_grid()), vec!["   ", "***", "   "]);
+
+        press(&mut editor, &[Key::Char('u')]);
+
+        assert_eq!(editor.get_generation(), 0);
+        assert_eq!(rows(editor.get_grid()), vec![" * ", " * ", " * "]);
+    }
+
+    #[test]
+    fn editor_run_pause() {
+        let mut editor = Editor::new(SimpleGrid::init_life(3, 3), "test.life");
+
+        press(&mut editor, &[Key::Char('r')]);
+        assert!(editor.is_running());
+
+        press(&mut editor, &[Key::Char('r')]);
+        assert!(!editor.is_running());
+    }
+
+    #[test]
+    fn editor_stamp_symbols() {
+        let mut parser = FileParser::from_string(":GLIDER\n1,0\n2,1\n0,2\n1,2\n2,2\n;\n:BLOCK\n0,0\n1,0\n0,1\n1,1\n;\n");
+        parser.set_test();
+        let mut editor = Editor::new(SimpleGrid::init_life(4, 3), "test.life");
+        editor.set_symbols(parser.parse_symbols());
+
+        assert_eq!(editor.get_symbol_name(), Some("BLOCK"));
+
+        // The glider is clipped at the edge of the grid...
+        press(&mut editor, &[Key::Char(']'), Key::Right, Key::Right, Key::Char('t')]);
+
+        assert_eq!(editor.get_symbol_name(), Some("GLIDER"));
+        assert_eq!(rows(editor.get_grid()), vec!["   *", "    ", "  **"]);
+
+        press(&mut editor, &[Key::Char('['), Key::Char('['), Key::Char('c'), Key::Char('t')]);
+
+        assert_eq!(editor.get_symbol_name(), Some("GLIDER"));
+        assert_eq!(rows(editor.get_grid()), vec!["   *", "    ", "  **"]);
+    }
+
+    #[test]
+    fn editor_quit_when_modified() {
+        let mut editor = Editor::new(SimpleGrid::init_life(2, 2), "test.life");
+
+        assert!(!press(&mut editor, &[Key::Char('q')]));
+
+        press(&mut editor, &[Key::Char(' ')]);
+
+        assert!(press(&mut editor, &[Key::Char('q')]));
+        assert!(!press(&mut editor, &[Key::Char('q')]));
+    }
+
+    #[test]
+    fn editor_save() {
+        let path = std::env::temp_dir().join("conway-life-editor-test.rle");
+        let path = path.to_string_lossy();
+        let mut editor = Editor::new(SimpleGrid::init_life(2, 2), &path);
+
+        press(&mut editor, &[Key::Right, Key::Char(' '), Key::Char('w')]);
+        let contents = std::fs::read_to_string(path.as_ref()).unwrap();
+        std::fs::remove_file(path.as_ref()).ok();
+
+        assert_eq!(contents, "x = 2, y = 2, rule = B3/S23\nbo!\n");
+        assert!(!press(&mut editor, &[Key::Char('q')]));
+    }
+
+    #[test]
+    fn editor_save_rule() {
+        // The rule saved in an RLE file is read back as the same rule...
+        let path = std::env::temp_dir().join("conway-life-editor-rule-test.rle");
+        let path = path.to_string_lossy();
+        let knight = Neighbourhood::parse_mask(".*.*.\n*...*\n.....\n*...*\n.*.*.\n").unwrap();
+        let rules: Vec<Box<dyn Rule>> = vec![
+            parse_rule("WireWorld", Neighbourhood::default()).unwrap(),
+            parse_rule("B2/S/C3", Neighbourhood::default()).unwrap(),
+            parse_rule("B2-a/S12", Neighbourhood::default()).unwrap(),
+            parse_rule("R2,C0,M1,S2..3,B3..3,NM", Neighbourhood::default()).unwrap(),
+            parse_rule("B3/S23", knight).unwrap()
+        ];
+
+        for rule in rules {
+            let mut editor = Editor::new(SimpleGrid::init_life(2, 2), &path);
+            assert!(editor.handle_key(Key::Char('w'), rule.as_ref()));
+            let contents = std::fs::read_to_string(path.as_ref()).unwrap();
+            std::fs::remove_file(path.as_ref()).ok();
+
+            let rulestring = RleParser::from_string(&contents).parse().get_rule().unwrap();
+            let reloaded = parse_rule(&rulestring, Neighbourhood::default()).unwrap();
+            assert_eq!(reloaded.get_rulestring(), rule.get_rulestring());
+            assert_eq!(reloaded.to_string(), rule.to_string());
+        }
+    }
+
+    #[test]
+    fn editor_render() {
+        let mut editor = Editor::new(SimpleGrid::init_life(2, 1), "test.life");
+        press(&mut editor, &[Key::Char(' '), Key::Right]);
+
+        let frame = editor.render();
+
+        assert!(frame.starts_with(&format!("/--\\{}\r\n|*{} {}|", CLEAR_LINE, REVERSE_VIDEO, RESET_ATTRIBUTES)));
+        assert!(frame.contains("generation 0 | population 1 | cursor (1,0) | symbol: none | paused | modified"));
+    }
+}
@@ -27,8 +27,36 @@
 #[cfg(test)]
 
 mod test_env {
+    use crate::config::*;
     use crate::env::*;
 
+    static COMMANDS: &[CommandSpec] = &[
+        CommandSpec {
+            name: "run",
+            summary: "Run a pattern",
+            args: &["path", "iterations"],
+            options: &[
+                OptionSpec { long: "verbose", short: Some('v'), value: OptionValue::None, value_name: "", help: "Print every generation" },
+                OptionSpec { long: "debug", short: Some('d'), value: OptionValue::None, value_name: "", help: "Print neighbour counts" },
+                OptionSpec { long: "rule", short: Some('r'), value: OptionValue::Required, value_name: "<rule>", help: "The rule" },
+                OptionSpec { long: "diff", short: None, value: OptionValue::Optional, value_name: "<style>", help: "Mark changes" }
+            ]
+        },
+        CommandSpec {
+            name: "search",
+            summary: "Search soups",
+            args: &["soups"],
+            options: &[
+                OptionSpec { long: "seed", short: None, value: OptionValue::Required, value_name: "<n>", help: "The seed" }
+            ]
+        }
+    ];
+
+    fn parse(args: &[&str]) -> Result<ArgsHelper, String> {
+        let args: Vec<String> = args.iter().map(|arg| String::from(*arg)).collect();
+        ArgsHelper::parse("life", &args, COMMANDS)
+    }
+
     #[test]
     fn unwrap_exit_some() {
         let opt = Some(99);
@@ -86,4 +114,103 @@ mod test_env {
 
         assert_eq!(opt.unwrap_display_or("hello, world!"), "hello, world!");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn args_default_command() {
+        let args = parse(&["grid.life", "10"]).unwrap();
+
+        assert_eq!(args.get_command(), "run");
+        assert_eq!(args.len(), 2);
+        assert_eq!((args[0].as_str(), args[1].as_str()), ("grid.life", "10"));
+    }
+
+    #[test]
+    fn args_named_command() {
+        let args = parse(&["search", "--seed", "7", "100"]).unwrap();
+
+        assert_eq!(args.get_command(), "search");
+        assert_eq!(args[0], "100");
+        assert_eq!(args.get_option_value("seed"), Some("7"));
+    }
+
+    #[test]
+    fn args_option_values() {
+        let args = parse(&["--rule=B36/S23", "grid.life", "-r", "B3/S23", "10", "--diff=colour"]).unwrap();
+
+        assert_eq!(args.get_option_value("rule"), Some("B3/S23"));
+        assert_eq!(args.get_option_value("diff"), Some("colour"));
+        assert_eq!(args.len(), 2);
+    }
+
+    #[test]
+    fn args_short_options() {
+        let args = parse(&["-vdrB3/S23", "grid.life", "10"]).unwrap();
+
+        assert!(args.has_option("verbose"));
+        assert!(args.has_option("debug"));
+        assert_eq!(args.get_option_value("rule"), Some("B3/S23"));
+    }
+
+    #[test]
+    fn args_optional_value() {
+        // An optional value is only taken with '='...
+        let args = parse(&["--diff", "grid.life", "10"]).unwrap();
+
+        assert!(args.has_option("diff"));
+        assert_eq!(args.get_option_value("diff"), None);
+        assert_eq!(args[0], "grid.life");
+    }
+
+    #[test]
+    fn args_terminator() {
+        let args = parse(&["-v", "--", "-grid.life", "10"]).unwrap();
+
+        assert!(args.has_option("verbose"));
+        assert_eq!(args[0], "-grid.life");
+    }
+
+    #[test]
+    fn args_errors() {
+        assert!(parse(&["--bogus", "grid.life", "10"]).unwrap_err().contains("unknown option '--bogus'"));
+        assert!(parse(&["-x", "grid.life", "10"]).unwrap_err().contains("unknown option '-x'"));
+        assert!(parse(&["grid.life", "10", "--rule"]).unwrap_err().contains("needs a value"));
+        assert!(parse(&["--verbose=yes", "grid.life", "10"]).unwrap_err().contains("doesn't take a value"));
+        assert!(parse(&["grid.life"]).unwrap_err().contains("expects 2 argument(s), but 1 given"));
+        assert!(parse(&["search", "--verbose", "100"]).is_err());
+    }
+
+    #[test]
+    fn args_help() {
+        let args = parse(&["search", "--help"]).unwrap();
+
+        assert!(args.has_option("help"));
+        assert!(COMMANDS[1].get_help("life").contains("usage: life search [options] <soups>"));
+        assert!(COMMANDS[0].get_help("life").contains("  -r, --rule <rule>     The rule\n"));
+        assert!(COMMANDS[0].get_help("life").contains("      --diff[=<style>]  Mark changes\n"));
+        assert!(get_commands_help("life", COMMANDS).contains("  run     Run a pattern (default)\n"));
+    }
+
+    #[test]
+    fn args_settings_under_options() {
+        let mut args = parse(&["-r", "B3/S23", "grid.life", "10"]).unwrap();
+        let config = Config::parse("rule = \"B36/S23\"\nverbose = true\ndebug = false\ndiff = \"colour\"\nseed = 3\n", None).unwrap();
+
+        args.apply_settings(&config.get_settings(None).unwrap()).unwrap();
+
+        assert_eq!(args.get_option_value("rule"), Some("B3/S23"));
+        assert!(args.has_option("verbose"));
+        assert!(!args.has_option("debug"));
+        assert_eq!(args.get_option_value("diff"), Some("colour"));
+        assert!(!args.has_option("seed"));
+    }
+
+    #[test]
+    fn args_settings_bad() {
+        let mut args = parse(&["grid.life", "10"]).unwrap();
+
+        for text in ["verbose = 1\n", "rule = true\n", "profile = \"bench\"\n"] {
+            let config = Config::parse(text, None).unwrap();
+            assert!(args.apply_settings(&config.get_settings(None).unwrap()).is_err());
+        }
+    }
+}
@@ -0,0 +1,187 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_gif {
+    use crate::gif::*;
+    use crate::grid::*;
+    use crate::image::*;
+    use crate::life::*;
+    use crate::search::Random;
+    use crate::tests::common::*;
+
+    /// Decompresses GIF LZW data, as a GIF decoder would.
+    fn decode_lzw(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let (clear, end) = (1usize << min_code_size, (1usize << min_code_size) + 1);
+        let reset = || -> Vec<Vec<u8>> { (0..clear).map(|index| vec![index as u8]).chain([vec![], vec![]]).collect() };
+
+        let (mut table, mut width) = (reset(), min_code_size + 1);
+        let (mut position, mut previous): (usize, Option<usize>) = (0, None);
+        let mut indices = Vec::new();
+        loop {
+            let code = (0..width as usize).fold(0, |code, bit| {
+                let index = position + bit;
+                code | ((((bytes[index / 8] >> (index % 8)) & 1) as usize) << bit)
+            });
+            position += width as usize;
+
+            if code == clear {
+                (table, width, previous) = (reset(), min_code_size + 1, None);
+                continue;
+            }
+
+            if code == end {
+                return indices;
+            }
+
+            let entry = match (table.get(code), previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => [table[previous].clone(), vec![table[previous][0]]].concat(),
+                (None, None) => panic!("code {} is not in the table", code)
+            };
+
+            if let Some(previous) = previous {
+                if table.len() < 4096 {
+                    table.push([table[previous].clone(), vec![entry[0]]].concat());
+                    if (table.len() == (1 << width)) && (width < 12) {
+                        width += 1;
+                    }
+                }
+            }
+
+            indices.extend(&entry);
+            previous = Some(code);
+        }
+    }
+
+    #[test]
+    fn lzw_round_trip() {
+        let indices = vec![0, 0, 0, 1, 1, 0, 0, 0, 1, 1, 1, 1, 0, 0, 0, 0];
+
+        assert_eq!(decode_lzw(&encode_lzw(&indices, 2), 2), indices);
+        assert_eq!(decode_lzw(&encode_lzw(&[], 2), 2), Vec::<u8>::new());
+        assert_eq!(decode_lzw(&encode_lzw(&[3], 2), 2), vec![3]);
+    }
+
+    #[test]
+    fn lzw_round_trip_table_full() {
+        // Enough varied data to fill the table of codes several times...
+        let mut random = Random::from_seed(1);
+        for min_code_size in [2, 4, 8] {
+            let indices: Vec<u8> = (0..50_000).map(|_| (random.next_u64() % (1 << min_code_size)) as u8).collect();
+
+            assert_eq!(decode_lzw(&encode_lzw(&indices, min_code_size), min_code_size), indices);
+        }
+    }
+
+    #[test]
+    fn lzw_round_trip_widths() {
+        // Every length around each change of code width...
+        for length in 0..600 {
+            let indices: Vec<u8> = (0..length).map(|index| ((index * 7) % 5) as u8).collect();
+
+            assert_eq!(decode_lzw(&encode_lzw(&indices, 3), 3), indices);
+        }
+    }
+
+    #[test]
+    fn encoder_header() {
+        let mut bytes = Vec::new();
+        let encoder = GifEncoder::new(&mut bytes, 300, 2, &[(255, 255, 255), (0, 0, 0), (255, 0, 0)]).unwrap();
+        encoder.finish(&mut bytes).unwrap();
+
+        assert_eq!(&bytes[0..6], b"GIF89a");
+        assert_eq!(&bytes[6..10], &[44, 1, 2, 0]);
+        assert_eq!(bytes[10], 0xF1);
+        assert_eq!(&bytes[13..25], &[255, 255, 255, 0, 0, 0, 255, 0, 0, 0, 0, 0]);
+        assert_eq!(&bytes[28..39], b"NETSCAPE2.0");
+        assert_eq!(bytes.last(), Some(&0x3B));
+        assert!(GifEncoder::new(&mut Vec::new(), 70_000, 1, &[(0, 0, 0)]).is_err());
+    }
+
+    #[test]
+    fn recorder_every() {
+        let grid = create_grid(&["*  "]);
+        let mut gif = GifRecorder::new();
+        gif.set_every(3);
+
+        for generation in 0..10 {
+            gif.add(generation, &grid, (0, 0));
+        }
+
+        assert_eq!(gif.get_frame_count(), 4);
+    }
+
+    #[test]
+    fn recorder_bounds() {
+        let mut gif = GifRecorder::new();
+        gif.add(0, &create_grid(&["    ", " *  ", "    "]), (0, 0));
+        gif.add(1, &create_grid(&["    ", "  * ", "    "]), (5, -2));
+
+        assert_eq!(gif.get_bounds(), Some(BoundingBox::new(0, -2, 9, 5)));
+
+        let mut cropped = GifRecorder::new();
+        cropped.set_cropped(true);
+        cropped.add(0, &create_grid(&["    ", " *  ", "    "]), (0, 0));
+        cropped.add(1, &create_grid(&["    ", "  * ", "    "]), (5, -2));
+        cropped.add(2, &create_grid(&["    "]), (0, 0));
+
+        assert_eq!(cropped.get_bounds(), Some(BoundingBox::new(1, -1, 7, 3)));
+    }
+
+    #[test]
+    fn recorder_write() {
+        let mut gif = GifRecorder::new();
+        gif.set_cropped(true);
+        gif.set_cell_size(2);
+        gif.set_delay(250);
+        gif.set_palette(Palette::parse("white,black").unwrap());
+        gif.add(0, &create_grid(&["     ", " **  ", "     "]), (0, 0));
+        gif.add(1, &create_grid(&["     ", "  ** ", "     "]), (0, 0));
+
+        let mut bytes = Vec::new();
+        gif.write(&mut bytes).unwrap();
+
+        // A 6x2 pixel image of a 3x1 region, with two frames of 25/100s...
+        assert_eq!(&bytes[6..10], &[6, 0, 2, 0]);
+        let frames: Vec<usize> = (0..bytes.len() - 4).filter(|index| bytes[*index..*index + 4] == [0x21, 0xF9, 0x04, 0x04]).collect();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(&bytes[frames[0] + 4..frames[0] + 6], &[25, 0]);
+
+        // The pixels of the second frame...
+        let data = frames[1] + 8 + 10;
+        assert_eq!(bytes[data], 2);
+        let length = bytes[data + 1] as usize;
+        assert_eq!(decode_lzw(&bytes[data + 2..data + 2 + length], 2), vec![0, 0, 1, 1, 1, 1, 0, 0, 1, 1, 1, 1]);
+        assert_eq!(bytes.last(), Some(&0x3B));
+    }
+
+    #[test]
+    fn recorder_write_empty() {
+        assert!(GifRecorder::new().write(&mut Vec::new()).is_err());
+    }
+}
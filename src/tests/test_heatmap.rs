@@ -0,0 +1,98 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_heatmap {
+    use crate::grid::*;
+    use crate::heatmap::*;
+    use crate::image::*;
+    use crate::life::*;
+    use crate::rule::*;
+    use crate::tests::common::*;
+
+    fn counts(grid: &SimpleGrid<u32>) -> Vec<Vec<u32>> {
+        (0..grid.get_height())
+            .map(|y| (0..grid.get_width()).map(|x| *grid.get(x, y)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn heatmap_blinker() {
+        let rule = LifeRule::default();
+        let mut grid = create_grid(&["   ", "***", "   "]);
+        let mut heatmap = HeatMap::new(3, 3);
+
+        heatmap.add(&grid);
+        for _ in 0..4 {
+            grid = rule.step(&grid);
+            heatmap.add(&grid);
+        }
+
+        assert_eq!(heatmap.get_generations(), 5);
+        assert_eq!(counts(heatmap.get_live()), vec![vec![0, 2, 0], vec![3, 5, 3], vec![0, 2, 0]]);
+        assert_eq!(counts(heatmap.get_changes()), vec![vec![0, 4, 0], vec![4, 0, 4], vec![0, 4, 0]]);
+    }
+
+    #[test]
+    fn heatmap_shade() {
+        let mut counts = SimpleGrid::new(4, 1);
+        counts.set(1, 0, 1);
+        counts.set(2, 0, 5);
+        counts.set(3, 0, 10);
+
+        let shaded = HeatMap::shade(&counts);
+
+        assert_eq!((0..4).map(|x| *shaded.get(x, 0)).collect::<String>(), " .+@");
+        assert_eq!(*HeatMap::shade(&SimpleGrid::new(1, 1)).get(0, 0), ' ');
+    }
+
+    #[test]
+    fn heatmap_write_pgm() {
+        // A heat-map is written as a PGM image of grey levels, through `Image`...
+        let mut counts = SimpleGrid::new(3, 2);
+        counts.set(0, 0, 4);
+        counts.set(1, 1, 1);
+
+        let mut pgm: Vec<u8> = Vec::new();
+        Image::from_counts(&counts, &Palette::grey(Palette::GREY_LEVELS), 1, None).write_pgm(&mut pgm).unwrap();
+
+        assert_eq!(pgm, [b"P5\n3 2\n255\n".as_slice(), &[255, 0, 0, 0, 64, 0]].concat());
+    }
+
+    #[test]
+    fn heatmap_write_pgm_large_counts() {
+        // Counts too large to scale as `u32`s are still shaded...
+        let mut counts = SimpleGrid::new(2, 1);
+        counts.set(0, 0, u32::MAX);
+        counts.set(1, 0, u32::MAX / 2);
+
+        let image = Image::from_counts(&counts, &Palette::grey(Palette::GREY_LEVELS), 1, None);
+
+        assert_eq!(image.get_colour(0, 0), (255, 255, 255));
+        assert_eq!(image.get_colour(1, 0), (127, 127, 127));
+    }
+}
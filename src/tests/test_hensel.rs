@@ -0,0 +1,268 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_hensel {
+    use crate::grid::*;
+    use crate::hensel::*;
+    use crate::life::*;
+    use crate::neighbourhood::*;
+    use crate::rule::*;
+    use crate::tests::common::*;
+
+    fn live_cells(grid: &LifeGridType) -> Vec<(usize, usize)> {
+        grid.iter()
+            .filter(|cell| cell.is_live())
+            .map(|cell| (cell.get_x(), cell.get_y()))
+            .collect()
+    }
+
+    #[test]
+    fn hensel_classify_sizes() {
+        let classes = classify_counts();
+
+        // The number of configurations for each count & letter...
+        assert_eq!(classes(0, ' '), 1);
+        assert_eq!(classes(1, 'c'), 4);
+        assert_eq!(classes(1, 'e'), 4);
+        assert_eq!(classes(2, 'a'), 8);
+        assert_eq!(classes(2, 'i'), 2);
+        assert_eq!(classes(3, 'j'), 8);
+        assert_eq!(classes(4, 'c'), 1);
+        assert_eq!(classes(4, 'e'), 1);
+        assert_eq!(classes(4, 't'), 4);
+        assert_eq!(classes(6, 'n'), 2);
+        assert_eq!(classes(7, 'e'), 4);
+        assert_eq!(classes(8, ' '), 1);
+    }
+
+    fn classify_counts() -> impl Fn(usize, char) -> usize {
+        let classes = HenselRule::classify();
+        move |count, letter| classes.iter().filter(|class| **class == (count, letter)).count()
+    }
+
+    #[test]
+    fn hensel_classify_letters() {
+        let classes = HenselRule::classify();
+
+        // Bits are NW, N, NE, W, E, SW, S, SE...
+        assert_eq!(classes[0b0000_0001], (1, 'c'));
+        assert_eq!(classes[0b0000_0010], (1, 'e'));
+        assert_eq!(classes[0b0000_0011], (2, 'a'));
+        assert_eq!(classes[0b0100_0010], (2, 'i'));
+        assert_eq!(classes[0b0010_0100], (2, 'n'));
+        assert_eq!(classes[0b0000_0111], (3, 'i'));
+        assert_eq!(classes[0b0000_1011], (3, 'a'));
+        assert_eq!(classes[0b0100_0111], (4, 't'));
+        assert_eq!(classes[0b1010_0101], (4, 'c'));
+        assert_eq!(classes[0b0101_1010], (4, 'e'));
+        assert_eq!(classes[0b1111_1000], (5, 'i'));
+    }
+
+    #[test]
+    fn hensel_is_hensel() {
+        assert!(HenselRule::is_hensel("B2-a/S12"));
+        assert!(HenselRule::is_hensel("B3/S2ae"));
+        assert!(!HenselRule::is_hensel("B3/S23"));
+        assert!(!HenselRule::is_hensel("B2/S/C3"));
+        assert!(!HenselRule::is_hensel("B2/S34H"));
+    }
+
+    #[test]
+    fn hensel_parse_bad() {
+        assert!(HenselRule::parse("B2-a").is_err());
+        assert!(HenselRule::parse("B1a/S2").is_err());
+        assert!(HenselRule::parse("B2-/S2").is_err());
+        assert!(HenselRule::parse("B9a/S2").is_err());
+        assert!(HenselRule::parse("B2a/S2/C1").is_err());
+    }
+
+    #[test]
+    fn hensel_display() {
+        assert_eq!(HenselRule::parse("B2-a/S12").unwrap().to_string(), "B2-a/S12");
+        assert_eq!(HenselRule::parse("b2ceikn/s1ce2").unwrap().to_string(), "B2-a/S12");
+        assert_eq!(HenselRule::parse("B3/S2-i34q").unwrap().to_string(), "B3/S2-i34q");
+        assert_eq!(HenselRule::parse("B2a7c/S/C3").unwrap().to_string(), "B2a7c/S/C3");
+    }
+
+    #[test]
+    fn hensel_parse_rule() {
+        assert_eq!(parse_rule("B2-a/S12", Neighbourhood::default()).unwrap().to_string(), "B2-a/S12");
+    }
+
+    #[test]
+    fn hensel_config_index() {
+        let grid = create_grid(&["** ", "   ", "  *"]);
+
+        assert_eq!(HenselRule::config_index(&grid, 1, 1), 0b1000_0011);
+        assert_eq!(HenselRule::config_index(&grid, 0, 0), 0b0001_0000);
+    }
+
+    #[test]
+    fn hensel_matches_life() {
+        // Every letter of every count in the rule is the same as Conway's
+        // Game of Life...
+        let hensel = HenselRule::parse("B3cekainyqjr/S2ceaikn3ceaiknjqry").unwrap();
+        let life = LifeRule::default();
+        let mut hensel_grid = create_grid(&[
+            " *          ",
+            "  *         ",
+            "***         ",
+            "            ",
+            "            ",
+            "            ",
+            "            ",
+            "       ***  ",
+            "            ",
+            "     **     ",
+            "            ",
+            "            "
+        ]);
+        let mut life_grid = hensel_grid.clone();
+
+        for _ in 0..12 {
+            hensel_grid = hensel.step(&hensel_grid);
+            life_grid = life.step(&life_grid);
+
+            assert_eq!(live_cells(&hensel_grid), live_cells(&life_grid));
+        }
+    }
+
+    #[test]
+    fn hensel_block() {
+        // Each cell of a block has a '3a' configuration of neighbours...
+        let block = create_grid(&["      ", "      ", "  **  ", "  **  ", "      ", "      "]);
+
+        assert_eq!(live_cells(&HenselRule::parse("B3/S23-c").unwrap().step(&block)), live_cells(&block));
+        assert!(live_cells(&HenselRule::parse("B3/S23-a").unwrap().step(&block)).is_empty());
+    }
+
+    #[test]
+    fn hensel_blinker() {
+        // The centre of a blinker has a '2i' configuration and the cells
+        // either side of the centre have a '3i' configuration...
+        let blinker = create_grid(&["     ", "  *  ", "  *  ", "  *  ", "     "]);
+
+        assert_eq!(live_cells(&HenselRule::parse("B3/S2-a").unwrap().step(&blinker)), vec![(1, 2), (2, 2), (3, 2)]);
+        assert_eq!(live_cells(&HenselRule::parse("B3-i/S23").unwrap().step(&blinker)), vec![(2, 2)]);
+        assert_eq!(live_cells(&HenselRule::parse("B3/S2-i3").unwrap().step(&blinker)), vec![(1, 2), (3, 2)]);
+    }
+
+    #[test]
+    fn hensel_isotropic() {
+        // Rotating the pattern by a quarter-turn rotates the result...
+        let rule = HenselRule::parse("B2-a3j/S1c2-in4w").unwrap();
+        let mut grid = create_grid(&[
+            "         ",
+            "         ",
+            "   *     ",
+            "    *    ",
+            "  ***    ",
+            "     *   ",
+            "         ",
+            "         ",
+            "         "
+        ]);
+        let mut rotated_grid = grid.transform(Symmetry::Rotate90);
+        for _ in 0..3 {
+            grid = rule.step(&grid);
+            rotated_grid = rule.step(&rotated_grid);
+        }
+
+        assert_eq!(rotated_grid, grid.transform(Symmetry::Rotate90));
+    }
+
+    /// Gives whether the centre of a 3x3 picture of its neighbours is born
+    /// under the given rule...
+    fn is_born(rulestring: &str, rows: &[&str]) -> bool {
+        let grid = create_grid(rows);
+
+        HenselRule::parse(rulestring).unwrap().step(&grid).get_cell(1, 1).is_live()
+    }
+
+    #[test]
+    fn hensel_letter_j() {
+        assert!(is_born("B3j/S", &[" **", "*  ", "   "]));
+        assert!(is_born("B3j/S", &["** ", "  *", "   "]));
+        assert!(!is_born("B3-j/S", &[" **", "*  ", "   "]));
+    }
+
+    #[test]
+    fn hensel_letter_k() {
+        assert!(is_born("B3k/S", &[" * ", "  *", "*  "]));
+        assert!(is_born("B3k/S", &["*  ", "  *", " * "]));
+        assert!(!is_born("B3-k/S", &[" * ", "  *", "*  "]));
+    }
+
+    #[test]
+    fn hensel_letter_q() {
+        assert!(is_born("B3q/S", &[" **", "   ", "*  "]));
+        assert!(is_born("B3q/S", &["*  ", "   ", " **"]));
+        assert!(!is_born("B3-q/S", &[" **", "   ", "*  "]));
+    }
+
+    #[test]
+    fn hensel_letter_r() {
+        assert!(is_born("B3r/S", &["*  ", "* *", "   "]));
+        assert!(is_born("B3r/S", &[" **", "   ", " * "]));
+        assert!(!is_born("B3-r/S", &["*  ", "* *", "   "]));
+    }
+
+    #[test]
+    fn hensel_letter_y() {
+        assert!(is_born("B3y/S", &["*  ", "  *", "*  "]));
+        assert!(is_born("B3y/S", &["* *", "   ", " * "]));
+        assert!(!is_born("B3-y/S", &["*  ", "  *", "*  "]));
+    }
+
+    #[test]
+    fn hensel_letter_w() {
+        assert!(is_born("B4w/S", &[" **", "*  ", "*  "]));
+        assert!(is_born("B4w/S", &["** ", "  *", "  *"]));
+        assert!(!is_born("B4-w/S", &[" **", "*  ", "*  "]));
+    }
+
+    #[test]
+    fn hensel_letter_z() {
+        assert!(is_born("B4z/S", &["  *", "* *", "*  "]));
+        assert!(is_born("B4z/S", &["*  ", "* *", "  *"]));
+        assert!(!is_born("B4-z/S", &["  *", "* *", "*  "]));
+    }
+
+    #[test]
+    fn hensel_generations() {
+        let rule = HenselRule::parse("B2a/S/C3").unwrap();
+        let grid = create_grid(&["    ", " ** ", "    ", "    "]);
+
+        let next = rule.step(&grid);
+
+        assert_eq!(rule.get_states(), 3);
+        assert!(next.get_cell(1, 1).is_dying());
+        assert!(next.get_cell(1, 0).is_live());
+        assert!(next.get_cell(1, 2).is_live());
+    }
+}
@@ -0,0 +1,73 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_html {
+    use crate::grid::*;
+    use crate::html::*;
+    use crate::image::*;
+    use crate::life::*;
+    use crate::tests::common::*;
+
+    fn write(player: &HtmlPlayer, generation: usize, grids: &[LifeGridType]) -> String {
+        let mut bytes = Vec::new();
+        player.write(generation, grids, &mut bytes).unwrap();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn player_write() {
+        let grids = [create_grid(&["   ", "***", "   "]), create_grid(&[" * ", " * ", " * "])];
+
+        let text = write(&HtmlPlayer::new("blinker <1>"), 5, &grids);
+
+        assert!(text.starts_with("<!DOCTYPE html>\n"));
+        assert!(text.contains("<title>blinker &lt;1&gt;</title>"));
+        assert!(text.contains("<canvas id=\"grid\" width=\"30\" height=\"30\"></canvas>"));
+        assert!(text.contains("max=\"1\""));
+        assert!(text.contains("    width: 3, height: 3, cellSize: 10, first: 5, fps: 10,\n"));
+        assert!(text.contains("    palette: [\"#ffffff\", \"#000000\"], gridLines: null,\n"));
+        assert!(text.contains("        \"$3o!\",\n        \"bo$bo$bo!\",\n"));
+        assert!(text.trim_end().ends_with("</html>"));
+    }
+
+    #[test]
+    fn player_options() {
+        let mut player = HtmlPlayer::new("pattern");
+        player.set_cell_size(4);
+        player.set_grid_lines(Some(GRID_LINE_COLOUR));
+        player.set_palette(Palette::parse("black,yellow").unwrap());
+        player.set_fps(2.5);
+
+        let text = write(&player, 0, &[create_grid(&["* ", "  "])]);
+
+        // Lines around & between the cells...
+        assert!(text.contains("width=\"11\" height=\"11\""));
+        assert!(text.contains("cellSize: 4, first: 0, fps: 2.5,"));
+        assert!(text.contains("palette: [\"#000000\", \"#ffff00\"], gridLines: \"#c0c0c0\","));
+    }
+}
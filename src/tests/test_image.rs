@@ -0,0 +1,172 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_image {
+    use crate::grid::*;
+    use crate::image::*;
+    use crate::life::*;
+    use crate::tests::common::*;
+
+    #[test]
+    fn palette_parse() {
+        let palette = Palette::parse("white, black,#FF8000").unwrap();
+
+        assert_eq!(palette.get_colours(), &[(255, 255, 255), (0, 0, 0), (255, 128, 0)]);
+        assert_eq!(palette.get(5), (255, 128, 0));
+        assert!(Palette::parse("white,#12345").is_none());
+        assert!(Palette::parse("white,mauve").is_none());
+    }
+
+    #[test]
+    fn palette_default_states() {
+        let palette = Palette::default_states(4);
+
+        assert_eq!(palette.len(), 4);
+        assert_eq!(palette.get(0), (255, 255, 255));
+        assert_eq!(palette.get(1), (0, 0, 0));
+        assert_ne!(palette.get(2), palette.get(3));
+    }
+
+    #[test]
+    fn palette_heat() {
+        let palette = Palette::heat(4);
+
+        assert_eq!(palette.get_colours(), &[(0, 0, 0), (255, 0, 0), (255, 255, 0), (255, 255, 255)]);
+    }
+
+    #[test]
+    fn palette_grey() {
+        assert_eq!(Palette::grey(3).get_colours(), &[(0, 0, 0), (127, 127, 127), (255, 255, 255)]);
+        assert_eq!(Palette::grey(256).get(64), (64, 64, 64));
+    }
+
+    #[test]
+    fn image_cell_size() {
+        let grid = create_grid(&["* ", " *"]);
+
+        let image = Image::from_grid(&grid, &Palette::default_states(2), 3, None);
+
+        assert_eq!((image.get_width(), image.get_height()), (6, 6));
+        assert_eq!(image.get_index(2, 2), 1);
+        assert_eq!(image.get_index(3, 2), 0);
+        assert_eq!(image.get_index(5, 5), 1);
+    }
+
+    #[test]
+    fn image_grid_lines() {
+        let grid = create_grid(&["* ", " *"]);
+
+        let image = Image::from_grid(&grid, &Palette::default_states(2), 2, Some(GRID_LINE_COLOUR));
+
+        // Lines around & between the cells...
+        assert_eq!((image.get_width(), image.get_height()), (7, 7));
+        assert_eq!(image.get_colour(0, 0), GRID_LINE_COLOUR);
+        assert_eq!(image.get_colour(3, 1), GRID_LINE_COLOUR);
+        assert_eq!(image.get_colour(1, 1), (0, 0, 0));
+        assert_eq!(image.get_colour(4, 1), (255, 255, 255));
+        assert_eq!(image.get_colour(5, 5), (0, 0, 0));
+    }
+
+    #[test]
+    fn image_grid_lines_palette() {
+        // States beyond the palette still take its last colour, rather than
+        // that of the lines...
+        let grid = create_grid(&["*o"]);
+
+        let image = Image::from_grid(&grid, &Palette::parse("white,black").unwrap(), 1, Some(GRID_LINE_COLOUR));
+
+        assert_eq!(image.get_colour(0, 0), GRID_LINE_COLOUR);
+        assert_eq!(image.get_colour(1, 1), (0, 0, 0));
+        assert_eq!(image.get_colour(3, 1), (0, 0, 0));
+    }
+
+    #[test]
+    fn image_from_counts() {
+        let mut counts: SimpleGrid<u32> = SimpleGrid::new(3, 1);
+        counts.set(1, 0, 1);
+        counts.set(2, 0, 2);
+
+        let image = Image::from_counts(&counts, &Palette::heat(3), 1, None);
+
+        assert_eq!(image.get_indices(), &[0, 1, 2]);
+        assert_eq!(image.get_colour(2, 0), (255, 255, 255));
+    }
+
+    #[test]
+    fn image_write_pbm() {
+        let grid = create_grid(&["*        *", " *        "]);
+        let image = Image::from_grid(&grid, &Palette::default_states(2), 1, None);
+
+        let mut bytes = Vec::new();
+        image.write_pbm(&mut bytes).unwrap();
+
+        assert_eq!(bytes, [b"P4\n10 2\n".as_slice(), &[0x80, 0x40, 0x40, 0x00]].concat());
+    }
+
+    #[test]
+    fn image_write_pbm_grid_lines() {
+        let grid = create_grid(&["* "]);
+        let image = Image::from_grid(&grid, &Palette::default_states(2), 1, Some(GRID_LINE_COLOUR));
+
+        let mut bytes = Vec::new();
+        image.write_pbm(&mut bytes).unwrap();
+
+        assert_eq!(bytes, [b"P4\n5 3\n".as_slice(), &[0x00, 0x40, 0x00]].concat());
+    }
+
+    #[test]
+    fn image_write_ppm() {
+        let grid = create_grid(&["*o"]);
+        let image = Image::from_grid(&grid, &Palette::parse("white,black,red").unwrap(), 1, None);
+
+        let mut bytes = Vec::new();
+        image.write_ppm(&mut bytes).unwrap();
+
+        assert_eq!(bytes, [b"P6\n2 1\n255\n".as_slice(), &[0, 0, 0, 255, 0, 0]].concat());
+    }
+
+    #[test]
+    fn image_write_pgm() {
+        let grid = create_grid(&["* "]);
+        let image = Image::from_grid(&grid, &Palette::default_states(2), 1, None);
+
+        let mut bytes = Vec::new();
+        image.write_pgm(&mut bytes).unwrap();
+
+        assert_eq!(bytes, [b"P5\n2 1\n255\n".as_slice(), &[0, 255]].concat());
+    }
+
+    #[test]
+    fn image_write_path_errors() {
+        let grid = create_grid(&["*o"]);
+        let image = Image::from_grid(&grid, &Palette::default_states(3), 1, None);
+
+        assert!(image.write_path("pattern.png").unwrap_err().contains("'.pbm', '.pgm' or '.ppm'"));
+        assert!(image.write_path("pattern.pbm").unwrap_err().contains("only have 'dead' and 'live' cells"));
+    }
+}
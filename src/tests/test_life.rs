@@ -34,7 +34,7 @@ mod test_life {
     fn lifegrid_init() {
         let mut grid = SimpleGrid::init_life(20, 12);    
         
-        assert_eq!(grid.get(5, 5), &' ');
+        assert_eq!(grid.get(5, 5), &LifeState::DEAD);
     }
 
     #[test]
@@ -43,7 +43,7 @@ mod test_life {
         
         grid.set_live(10, 6);
 
-        assert_eq!(grid.get(10, 6), &'*');
+        assert_eq!(grid.get(10, 6), &LifeState::LIVE);
     }
 
     #[test]
@@ -53,7 +53,7 @@ mod test_life {
         grid.set_live(10, 6);
         grid.set_dead(10, 6);
 
-        assert_eq!(grid.get(10, 6), &' ');
+        assert_eq!(grid.get(10, 6), &LifeState::DEAD);
     }
 
     #[test]
@@ -176,4 +176,106 @@ mod test_life {
         
         assert_eq!(grid.get_cell(15, 3).count_neighbours(), 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn lifegrid_set_state() {
+        let mut grid = SimpleGrid::init_life(20, 12);    
+        
+        grid.set_state(10, 6, 3);
+
+        assert_eq!(grid.get(10, 6), &LifeState(3));
+        assert_eq!(grid.get_cell(10, 6).get_state(), 3);
+    }
+
+    #[test]
+    fn lifecell_is_dying() {
+        let mut grid = SimpleGrid::init_life(20, 12);    
+        
+        grid.set_state(10, 6, 2);
+
+        assert!(grid.get_cell(10, 6).is_dying());
+        assert!(!grid.get_cell(10, 6).is_live());
+        assert!(!grid.get_cell(10, 6).is_dead());
+    }
+
+    #[test]
+    fn lifecell_count_neighbours_ignores_dying() {
+        let mut grid = SimpleGrid::init_life(20, 12);    
+        
+        grid.set_live(10, 6);
+        grid.set_state(11, 6, 2);
+        grid.set_state(12, 6, 3);
+        
+        assert_eq!(grid.get_cell(11, 7).count_neighbours(), 1);
+    }
+
+    #[test]
+    fn lifestate_to_char() {
+        assert_eq!(LifeState::DEAD.to_char(), ' ');
+        assert_eq!(LifeState::LIVE.to_char(), '*');
+        assert_ne!(LifeState(2).to_char(), LifeState(3).to_char());
+        assert_eq!(LifeState(2).to_string(), "o");
+    }
+
+    #[test]
+    fn lifegrid_bounding_box() {
+        let mut grid = SimpleGrid::init_life(20, 12);
+
+        assert_eq!(grid.get_bounding_box(), None);
+
+        grid.set_live(3, 4);
+        grid.set_state(7, 2, 2);
+
+        assert_eq!(grid.get_bounding_box(), Some(BoundingBox::new(3, 2, 5, 3)));
+        assert_eq!(grid.get_population(), 1);
+    }
+
+    #[test]
+    fn lifegrid_crop() {
+        let mut grid = SimpleGrid::init_life(4, 4);
+        grid.set_live(0, 0);
+        grid.set_live(2, 1);
+
+        let cropped = grid.crop(&BoundingBox::new(-1, 0, 4, 2), 1);
+
+        assert_eq!(cropped.get_width(), 6);
+        assert_eq!(cropped.get_height(), 4);
+        assert_eq!(cropped.get(2, 1), &LifeState::LIVE);
+        assert_eq!(cropped.get(4, 2), &LifeState::LIVE);
+        assert_eq!(cropped.get_population(), 2);
+    }
+
+    #[test]
+    fn boundingbox_union() {
+        let first = BoundingBox::new(0, 0, 2, 2);
+        let second = BoundingBox::new(-1, 3, 1, 1).translate(4, 0);
+
+        assert_eq!(second, BoundingBox::new(3, 3, 1, 1));
+        assert_eq!(first.union(&second), BoundingBox::new(0, 0, 4, 4));
+    }
+
+    #[test]
+    fn lifegrid_pattern_hash() {
+        let mut first = SimpleGrid::init_life(10, 10);
+        first.set_live(1, 1);
+        first.set_live(2, 1);
+        first.set_live(2, 2);
+
+        // The same pattern elsewhere in a grid of a different size...
+        let mut moved = SimpleGrid::init_life(6, 8);
+        moved.set_live(3, 5);
+        moved.set_live(4, 5);
+        moved.set_live(4, 6);
+
+        // The same pattern, reflected...
+        let mut reflected = SimpleGrid::init_life(10, 10);
+        reflected.set_live(2, 1);
+        reflected.set_live(1, 1);
+        reflected.set_live(1, 2);
+
+        assert_eq!(first.get_pattern_hash(false), moved.get_pattern_hash(false));
+        assert_ne!(first.get_pattern_hash(false), reflected.get_pattern_hash(false));
+        assert_eq!(first.get_pattern_hash(true), reflected.get_pattern_hash(true));
+        assert_ne!(first.get_pattern_hash(true), SimpleGrid::init_life(3, 3).get_pattern_hash(true));
+    }
+}
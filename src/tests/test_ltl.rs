@@ -0,0 +1,133 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_ltl {
+    use crate::grid::*;
+    use crate::life::*;
+    use crate::ltl::*;
+    use crate::neighbourhood::*;
+    use crate::rule::*;
+
+    /// Gives a grid with a scattering of 'live' cells.
+    fn create_scattered_grid(width: usize, height: usize) -> LifeGridType {
+        let mut grid = SimpleGrid::init_life(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                if (x * 7 + y * 13 + x * y) % 5 < 2 {
+                    grid.set_live(x, y);
+                }
+            }
+        }
+
+        grid
+    }
+
+    #[test]
+    fn ltl_parse() {
+        let rule = LargerThanLifeRule::parse("R5,C0,M1,S34..58,B34..45,NM").unwrap();
+
+        assert_eq!(rule.get_range(), 5);
+        assert_eq!(rule.get_states(), 2);
+        assert_eq!(rule.get_neighbourhood(), &Neighbourhood::Moore(5));
+    }
+
+    #[test]
+    fn ltl_parse_von_neumann_generations() {
+        let rule = LargerThanLifeRule::parse("r3,c4,m0,s2..5,b3..4,nn").unwrap();
+
+        assert_eq!(rule.get_states(), 4);
+        assert_eq!(rule.get_neighbourhood(), &Neighbourhood::VonNeumann(3));
+    }
+
+    #[test]
+    fn ltl_parse_bad() {
+        assert!(LargerThanLifeRule::parse("R5,C0,M1,S34..58").is_err());
+        assert!(LargerThanLifeRule::parse("R0,S1..2,B1..2").is_err());
+        assert!(LargerThanLifeRule::parse("R2,M2,S1..2,B1..2").is_err());
+        assert!(LargerThanLifeRule::parse("R2,S5..2,B1..2").is_err());
+        assert!(LargerThanLifeRule::parse("R2,S1..2,B1..2,NX").is_err());
+    }
+
+    #[test]
+    fn ltl_display() {
+        assert_eq!(LargerThanLifeRule::parse("R5,C0,M1,S34..58,B34..45,NM").unwrap().to_string(), "R5,C0,M1,S34..58,B34..45,NM");
+        assert_eq!(LargerThanLifeRule::parse("R2,B3..4,S2..5,NN").unwrap().to_string(), "R2,C0,M0,S2..5,B3..4,NN");
+    }
+
+    #[test]
+    fn ltl_parse_rule() {
+        let rule = parse_rule("R5,C0,M1,S34..58,B34..45,NM", Neighbourhood::default()).unwrap();
+
+        assert_eq!(rule.to_string(), "R5,C0,M1,S34..58,B34..45,NM");
+    }
+
+    #[test]
+    fn ltl_count_neighbours_moore() {
+        // The prefix-sum counts should match counting each neighbour...
+        let grid = create_scattered_grid(23, 17);
+        let rule = LargerThanLifeRule::parse("R3,C0,M0,S1..2,B1..2,NM").unwrap();
+        let offsets = Neighbourhood::Moore(3).get_offsets();
+
+        let counts = rule.count_neighbours(&grid);
+        for cell in &grid {
+            assert_eq!(counts[(cell.get_x(), cell.get_y())], cell.count_neighbours_in(&offsets));
+        }
+    }
+
+    #[test]
+    fn ltl_count_neighbours_von_neumann_middle() {
+        let grid = create_scattered_grid(19, 21);
+        let rule = LargerThanLifeRule::parse("R4,C0,M1,S1..2,B1..2,NN").unwrap();
+        let offsets = Neighbourhood::VonNeumann(4).get_offsets();
+
+        let counts = rule.count_neighbours(&grid);
+        for cell in &grid {
+            let middle = if cell.is_live() { 1 } else { 0 };
+            assert_eq!(counts[(cell.get_x(), cell.get_y())], cell.count_neighbours_in(&offsets) + middle);
+        }
+    }
+
+    #[test]
+    fn ltl_step_matches_life() {
+        // R1 with S2..3 & B3..3 is Conway's Game of Life...
+        let grid = create_scattered_grid(16, 12);
+        let ltl = LargerThanLifeRule::parse("R1,C0,M0,S2..3,B3..3,NM").unwrap();
+        let life = LifeRule::default();
+
+        let mut ltl_grid = ltl.step(&grid);
+        let mut life_grid = life.step(&grid);
+        for _ in 0..5 {
+            ltl_grid = ltl.step(&ltl_grid);
+            life_grid = life.step(&life_grid);
+        }
+
+        for cell in &ltl_grid {
+            assert_eq!(cell.get(), life_grid.get(cell.get_x(), cell.get_y()));
+        }
+    }
+}
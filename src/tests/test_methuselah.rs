@@ -0,0 +1,111 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_methuselah {
+    use crate::census::*;
+    use crate::grid::*;
+    use crate::life::*;
+    use crate::methuselah::*;
+    use crate::rule::*;
+    use crate::tests::common::*;
+
+    #[test]
+    fn methuselah_pre_block() {
+        let grid = create_grid(&["**", "* "]);
+
+        let report = Methuselah::new().run(&grid, &LifeRule::default(), &Catalogue::builtin());
+
+        assert_eq!(report.lifespan, Some(1));
+        assert_eq!((report.max_population, report.max_generation), (4, 1));
+        assert_eq!(report.final_population, 4);
+        assert_eq!(report.census.get_count("block"), 1);
+    }
+
+    #[test]
+    fn methuselah_extinct() {
+        let grid = create_grid(&["* ", " *"]);
+
+        let report = Methuselah::new().run(&grid, &LifeRule::default(), &Catalogue::builtin());
+
+        assert_eq!(report.lifespan, Some(1));
+        assert_eq!((report.max_population, report.max_generation), (2, 0));
+        assert_eq!(report.final_population, 0);
+        assert_eq!(report.census.get_total(), 0);
+    }
+
+    #[test]
+    fn methuselah_escaping_glider() {
+        // The glider counts towards the population after it is removed...
+        let grid = create_grid(&[
+            "**      ",
+            "**      ",
+            "        ",
+            "        ",
+            "     *  ",
+            "      * ",
+            "    *** "
+        ]);
+
+        let report = Methuselah::new().run(&grid, &LifeRule::default(), &Catalogue::builtin());
+
+        assert!(report.lifespan.is_some());
+        assert_eq!(report.escapees, vec!["glider"]);
+        assert_eq!((report.final_population, report.escaped_population), (9, 5));
+        assert_eq!(report.universe.get_population(), 4);
+        assert_eq!(report.census.get_count("glider"), 1);
+        assert_eq!(report.census.get_count("block"), 1);
+    }
+
+    #[test]
+    fn methuselah_limit() {
+        let mut methuselah = Methuselah::new();
+        methuselah.set_max_generations(10);
+        let grid = create_grid(&[" **", "** ", " * "]);
+
+        let report = methuselah.run(&grid, &LifeRule::default(), &Catalogue::builtin());
+
+        assert_eq!(report.lifespan, None);
+        assert_eq!(report.generations, 10);
+        assert!(report.to_string().starts_with("Lifespan: not stabilised after 10 generations\n"));
+    }
+
+    #[test]
+    fn methuselah_r_pentomino() {
+        let grid = create_grid(&[" **", "** ", " * "]);
+
+        let report = Methuselah::new().run(&grid, &LifeRule::default(), &Catalogue::builtin());
+
+        assert_eq!(report.lifespan, Some(1103));
+        assert_eq!((report.max_population, report.max_generation), (319, 821));
+        assert_eq!(report.final_population, 116);
+        assert_eq!(report.escapees.len(), 6);
+        assert_eq!(report.census.get_count("block"), 8);
+        assert_eq!(report.census.get_count("glider"), 6);
+        assert_eq!(report.census.get_total(), 25);
+    }
+}
@@ -0,0 +1,138 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_neighbourhood {
+    use crate::grid::*;
+    use crate::life::*;
+    use crate::neighbourhood::*;
+    use crate::rule::*;
+
+    fn create_full_grid() -> LifeGridType {
+        let mut grid = SimpleGrid::init_life(7, 7);
+        for y in 0..7 {
+            for x in 0..7 {
+                grid.set_live(x, y);
+            }
+        }
+
+        grid
+    }
+
+    #[test]
+    fn neighbourhood_sizes() {
+        assert_eq!(Neighbourhood::Moore(1).get_size(), 8);
+        assert_eq!(Neighbourhood::Moore(2).get_size(), 24);
+        assert_eq!(Neighbourhood::VonNeumann(1).get_size(), 4);
+        assert_eq!(Neighbourhood::VonNeumann(2).get_size(), 12);
+        assert_eq!(Neighbourhood::Hexagonal.get_size(), 6);
+    }
+
+    #[test]
+    fn neighbourhood_radius() {
+        assert_eq!(Neighbourhood::Moore(3).get_radius(), 3);
+        assert_eq!(Neighbourhood::Hexagonal.get_radius(), 1);
+        assert_eq!(Neighbourhood::Custom(vec![(-2, 1), (0, 1)]).get_radius(), 2);
+    }
+
+    #[test]
+    fn neighbourhood_hexagonal_offsets() {
+        let offsets = Neighbourhood::Hexagonal.get_offsets();
+
+        assert!(!offsets.contains(&(1, -1)));
+        assert!(!offsets.contains(&(-1, 1)));
+        assert!(offsets.contains(&(-1, -1)));
+        assert!(offsets.contains(&(1, 1)));
+    }
+
+    #[test]
+    fn neighbourhood_parse_suffix() {
+        assert_eq!(Neighbourhood::parse_suffix("V"), Some(Neighbourhood::VonNeumann(1)));
+        assert_eq!(Neighbourhood::parse_suffix("v3"), Some(Neighbourhood::VonNeumann(3)));
+        assert_eq!(Neighbourhood::parse_suffix("H"), Some(Neighbourhood::Hexagonal));
+        assert_eq!(Neighbourhood::parse_suffix("M2"), Some(Neighbourhood::Moore(2)));
+        assert_eq!(Neighbourhood::parse_suffix("H2"), None);
+        assert_eq!(Neighbourhood::parse_suffix("M0"), None);
+        assert_eq!(Neighbourhood::parse_suffix("X"), None);
+    }
+
+    #[test]
+    fn neighbourhood_parse_mask() {
+        let neighbourhood = Neighbourhood::parse_mask("*.*\n.*.\n*.*\n").unwrap();
+
+        assert_eq!(neighbourhood, Neighbourhood::Custom(vec![(-1, -1), (1, -1), (-1, 1), (1, 1)]));
+    }
+
+    #[test]
+    fn neighbourhood_parse_mask_bad() {
+        assert_eq!(Neighbourhood::parse_mask("**\n**\n").unwrap_err(), "mask should have an odd number of rows, not 2 rows");
+        assert_eq!(Neighbourhood::parse_mask("***\n**\n***\n").unwrap_err(), "mask should be square, but row 2 is 2 characters long rather than 3");
+        assert!(Neighbourhood::parse_mask("*?*\n...\n***\n").is_err());
+    }
+
+    #[test]
+    fn neighbourhood_display() {
+        assert_eq!(Neighbourhood::Moore(1).to_string(), "");
+        assert_eq!(Neighbourhood::VonNeumann(1).to_string(), "V");
+        assert_eq!(Neighbourhood::Moore(2).to_string(), "M2");
+        assert_eq!(Neighbourhood::Hexagonal.to_string(), "H");
+        assert_eq!(Neighbourhood::Custom(vec![(-1, 0), (1, 0)]).to_string(), "@140");
+    }
+
+    #[test]
+    fn neighbourhood_custom_round_trip() {
+        let knight = Neighbourhood::parse_mask(".*.*.\n*...*\n.....\n*...*\n.*.*.\n").unwrap();
+
+        assert_eq!(knight.to_string(), "@5441150");
+        assert_eq!(Neighbourhood::parse_suffix("@5441150"), Some(knight.clone()));
+        assert_eq!(Neighbourhood::parse_suffix("@140"), Some(Neighbourhood::Custom(vec![(-1, 0), (1, 0)])));
+        assert_eq!(Neighbourhood::parse_suffix("@1234"), None);
+        assert_eq!(Neighbourhood::parse_suffix("@080"), None);
+
+        let rule = LifeRule::parse_with_neighbourhood("B3/S23", knight.clone()).unwrap();
+        assert_eq!(rule.to_string(), "B3/S23@5441150");
+        assert_eq!(LifeRule::parse("B3/S23@5441150").unwrap().get_neighbourhood(), &knight);
+    }
+
+    #[test]
+    fn lifecell_count_neighbours_in() {
+        let grid = create_full_grid();
+        let cell = grid.get_cell(3, 3);
+
+        assert_eq!(cell.count_neighbours_in(&Neighbourhood::Moore(1).get_offsets()), 8);
+        assert_eq!(cell.count_neighbours_in(&Neighbourhood::VonNeumann(2).get_offsets()), 12);
+        assert_eq!(cell.count_neighbours_in(&Neighbourhood::Hexagonal.get_offsets()), 6);
+    }
+
+    #[test]
+    fn lifecell_count_neighbours_in_corner() {
+        let grid = create_full_grid();
+
+        assert_eq!(grid.get_cell(0, 0).count_neighbours_in(&Neighbourhood::Moore(2).get_offsets()), 8);
+        assert_eq!(grid.get_cell(0, 0).count_neighbours_in(&Neighbourhood::VonNeumann(1).get_offsets()), 2);
+    }
+}
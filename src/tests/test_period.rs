@@ -0,0 +1,195 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_period {
+    use crate::grid::*;
+    use crate::life::*;
+    use crate::period::*;
+    use crate::rule::*;
+    use crate::tests::common::*;
+    use crate::universe::*;
+
+    fn run(grid: LifeGridType, max: usize) -> Option<Stabilisation> {
+        let rule = LifeRule::default();
+        let mut detector = PeriodDetector::new();
+        let mut grid = grid;
+
+        detector.push(0, &grid);
+        for generation in 1..=max {
+            grid = rule.step(&grid);
+            if let Some(result) = detector.push(generation, &grid) {
+                return Some(result);
+            }
+        }
+
+        None
+    }
+
+    #[test]
+    fn period_still_life() {
+        let block = create_grid(&["    ", " ** ", " ** ", "    "]);
+
+        let result = run(block, 10).unwrap();
+
+        assert_eq!(result, Stabilisation::Periodic { generation: 0, period: 1 });
+        assert_eq!(result.to_string(), "stabilised at generation 0 as a still life (period 1)");
+    }
+
+    #[test]
+    fn period_hash_collision() {
+        // Different grids with the same hash aren't a cycle...
+        let mut detector = PeriodDetector::new();
+        let block = create_grid(&["    ", " ** ", " ** ", "    "]);
+        let other = create_grid(&["    ", " *  ", "  * ", "    "]);
+
+        assert_eq!(detector.push_hashed(0, &block, 42), None);
+        assert_eq!(detector.push_hashed(1, &other, 42), None);
+        assert_eq!(detector.push_hashed(2, &other, 42), Some(Stabilisation::Periodic { generation: 1, period: 1 }));
+    }
+
+    #[test]
+    fn period_blinker() {
+        let blinker = create_grid(&["     ", "     ", " *** ", "     ", "     "]);
+
+        let result = run(blinker, 10).unwrap();
+
+        assert_eq!(result.get_generation(), 0);
+        assert_eq!(result.get_period(), 2);
+        assert_eq!(result.to_string(), "stabilised at generation 0 with period 2");
+    }
+
+    #[test]
+    fn period_settles() {
+        // A 'pre-block' becomes a block after one generation...
+        let pre_block = create_grid(&["    ", " ** ", " *  ", "    "]);
+
+        let result = run(pre_block, 10).unwrap();
+
+        assert_eq!(result, Stabilisation::Periodic { generation: 1, period: 1 });
+    }
+
+    #[test]
+    fn period_extinct() {
+        let pair = create_grid(&["    ", " ** ", "    "]);
+
+        let result = run(pair, 10).unwrap();
+
+        assert_eq!(result, Stabilisation::Extinct { generation: 1 });
+        assert_eq!(result.get_period(), 1);
+        assert_eq!(result.to_string(), "died out at generation 1");
+    }
+
+    #[test]
+    fn period_not_stable() {
+        // A glider is still travelling after two generations...
+        let glider = create_grid(&[" *      ", "  *     ", "***     ", "        ", "        "]);
+
+        assert_eq!(run(glider, 2), None);
+    }
+
+    #[test]
+    fn period_result_kept() {
+        let pair = create_grid(&["    ", " ** ", "    "]);
+        let mut detector = PeriodDetector::new();
+
+        assert_eq!(detector.push(0, &pair), None);
+        assert_eq!(detector.push(1, &pair), Some(Stabilisation::Periodic { generation: 0, period: 1 }));
+        assert_eq!(detector.push(2, &SimpleGrid::init_life(4, 3)), detector.get_result());
+    }
+
+    #[test]
+    fn period_hash_grid() {
+        let first = create_grid(&["  ", "* "]);
+        let second = create_grid(&["  ", " *"]);
+
+        assert_eq!(hash_grid(&first), hash_grid(&create_grid(&["  ", "* "])));
+        assert_ne!(hash_grid(&first), hash_grid(&second));
+        assert_ne!(hash_grid(&SimpleGrid::init_life(2, 3)), hash_grid(&SimpleGrid::init_life(3, 2)));
+        assert!(is_empty(&SimpleGrid::init_life(2, 2)));
+        assert!(!is_empty(&first));
+    }
+
+    fn run_unbounded(grid: LifeGridType, max: usize) -> Option<Stabilisation> {
+        let rule = LifeRule::default();
+        let mut detector = MotionDetector::new();
+        let mut universe = Universe::from_grid(&grid);
+
+        detector.push(0, &universe);
+        for generation in 1..=max {
+            universe.step(&rule);
+            if let Some(result) = detector.push(generation, &universe) {
+                return Some(result);
+            }
+        }
+
+        None
+    }
+
+    #[test]
+    fn motion_glider() {
+        let glider = create_grid(&[" * ", "  *", "***"]);
+
+        let result = run_unbounded(glider, 10).unwrap();
+
+        assert_eq!(result, Stabilisation::Moving { generation: 0, period: 4, dx: 1, dy: 1 });
+        assert_eq!(result.get_displacement(), (1, 1));
+        assert_eq!(result.get_speed(), "c/4 diagonal");
+        assert_eq!(result.to_string(), "moving from generation 0 with period 4, displaced by (1, 1) at c/4 diagonal");
+    }
+
+    #[test]
+    fn motion_lightweight_spaceship() {
+        let lwss = create_grid(&[" *  *", "*    ", "*   *", "**** "]);
+
+        let result = run_unbounded(lwss, 10).unwrap();
+
+        assert_eq!(result, Stabilisation::Moving { generation: 0, period: 4, dx: -2, dy: 0 });
+        assert_eq!(result.get_speed(), "c/2 orthogonal");
+    }
+
+    #[test]
+    fn motion_oscillator() {
+        // A blinker at the edge of its grid still oscillates in place...
+        let blinker = create_grid(&["***"]);
+
+        assert_eq!(run_unbounded(blinker, 10), Some(Stabilisation::Periodic { generation: 0, period: 2 }));
+        assert_eq!(run_unbounded(create_grid(&["**"]), 10), Some(Stabilisation::Extinct { generation: 1 }));
+    }
+
+    #[test]
+    fn motion_speeds() {
+        let speed = |period, dx, dy| Stabilisation::Moving { generation: 0, period, dx, dy }.get_speed();
+
+        assert_eq!(speed(1, 1, 0), "c orthogonal");
+        assert_eq!(speed(5, 2, 0), "2c/5 orthogonal");
+        assert_eq!(speed(2, 0, -2), "c orthogonal");
+        assert_eq!(speed(12, -3, 3), "c/4 diagonal");
+        assert_eq!(speed(6, 1, -2), "(2,1)c/6 oblique");
+        assert_eq!(Stabilisation::Periodic { generation: 0, period: 3 }.get_speed(), "0");
+    }
+}
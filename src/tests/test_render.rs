@@ -0,0 +1,162 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_render {
+    use crate::grid::*;
+    use crate::life::*;
+    use crate::render::*;
+    use crate::tests::common::*;
+
+    fn chars(grid: &SimpleGrid<char>) -> Vec<String> {
+        (0..grid.get_height())
+            .map(|y| (0..grid.get_width()).map(|x| *grid.get(x, y)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn render_parse_viewport() {
+        assert_eq!(Renderer::parse_viewport("-2,3,10x5"), Some(BoundingBox::new(-2, 3, 10, 5)));
+        assert_eq!(Renderer::parse_viewport("0,0,10"), None);
+        assert_eq!(Renderer::parse_viewport("0,0,10x5,1"), None);
+        assert_eq!(Renderer::parse_viewport("a,0,10x5"), None);
+    }
+
+    #[test]
+    fn render_whole_grid() {
+        let grid = create_grid(&["*  ", " * "]);
+
+        assert_eq!(chars(&Renderer::new().render(&grid, (0, 0))), vec!["*  ", " * "]);
+    }
+
+    #[test]
+    fn render_viewport() {
+        // The viewport extends beyond the grid, where cells are 'dead'...
+        let grid = create_grid(&["*  ", " * "]);
+        let mut renderer = Renderer::new();
+        renderer.set_viewport(BoundingBox::new(-1, 1, 3, 2));
+
+        assert_eq!(chars(&renderer.render(&grid, (0, 0))), vec!["  *", "   "]);
+    }
+
+    #[test]
+    fn render_viewport_origin() {
+        // The viewport is relative to the origin of the grid's top-left
+        // cell...
+        let grid = create_grid(&["*  ", " * "]);
+        let mut renderer = Renderer::new();
+        renderer.set_viewport(BoundingBox::new(11, 21, 2, 1));
+
+        assert_eq!(chars(&renderer.render(&grid, (10, 20))), vec!["* "]);
+    }
+
+    #[test]
+    fn render_follow() {
+        let grid = create_grid(&["      ", "  *   ", "   ** ", "      "]);
+        let mut renderer = Renderer::new();
+        renderer.set_following(true);
+
+        assert_eq!(renderer.get_view(&grid, (0, 0)), BoundingBox::new(2, 1, 3, 2));
+        assert_eq!(chars(&renderer.render(&grid, (0, 0))), vec!["*  ", " **"]);
+
+        // With a viewport, its size is kept and centred on the pattern...
+        renderer.set_viewport(BoundingBox::new(0, 0, 5, 4));
+
+        assert_eq!(renderer.get_view(&grid, (0, 0)), BoundingBox::new(1, 0, 5, 4));
+    }
+
+    #[test]
+    fn render_follow_empty() {
+        let grid = create_grid(&["   ", "   "]);
+        let mut renderer = Renderer::new();
+        renderer.set_following(true);
+
+        assert_eq!(renderer.get_view(&grid, (0, 0)), BoundingBox::new(0, 0, 3, 2));
+    }
+
+    #[test]
+    fn render_zoom() {
+        let grid = create_grid(&["**  *", "**   ", "*    "]);
+        let mut renderer = Renderer::new();
+        renderer.set_zoom(2);
+
+        // Full, a quarter and empty blocks, with partial blocks at the
+        // edges...
+        assert_eq!(chars(&renderer.render(&grid, (0, 0))), vec!["@ -", "-  "]);
+    }
+
+    #[test]
+    fn render_style_parse() {
+        assert_eq!(RenderStyle::parse("Braille"), Some(RenderStyle::Braille));
+        assert_eq!(RenderStyle::parse("halfblock"), Some(RenderStyle::HalfBlock));
+        assert_eq!(RenderStyle::parse("box"), Some(RenderStyle::Box));
+        assert_eq!(RenderStyle::parse("ascii"), Some(RenderStyle::Ascii));
+        assert_eq!(RenderStyle::parse("sixel"), None);
+    }
+
+    #[test]
+    fn render_chars() {
+        let grid = create_grid(&["* ", " *"]);
+        let mut renderer = Renderer::new();
+        renderer.set_chars('#', '.');
+
+        assert_eq!(chars(&renderer.render(&grid, (0, 0))), vec!["#.", ".#"]);
+    }
+
+    #[test]
+    fn render_braille() {
+        // Each cell of a 2x4 block is one dot, and a partial block is
+        // padded with 'dead' cells...
+        let grid = create_grid(&["* *", " * ", "   ", "** "]);
+        let mut renderer = Renderer::new();
+        renderer.set_style(RenderStyle::Braille);
+
+        assert_eq!(chars(&renderer.render(&grid, (0, 0))), vec!["\u{28d1}\u{2801}"]);
+        assert_eq!(chars(&renderer.render(&create_grid(&["  ", "  "]), (0, 0))), vec![" "]);
+    }
+
+    #[test]
+    fn render_half_block() {
+        let grid = create_grid(&["* * ", "** *", "*   "]);
+        let mut renderer = Renderer::new();
+        renderer.set_style(RenderStyle::HalfBlock);
+
+        assert_eq!(chars(&renderer.render(&grid, (0, 0))), vec!["\u{2588}\u{2584}\u{2580}\u{2584}", "\u{2580}   "]);
+    }
+
+    #[test]
+    fn render_box_border() {
+        let grid = create_grid(&["* ", " *"]);
+        let mut renderer = Renderer::new();
+        renderer.set_style(RenderStyle::Box);
+
+        let mut output = Vec::new();
+        renderer.write(&grid, (0, 0), &mut output);
+
+        assert_eq!(String::from_utf8(output).unwrap(), "\u{250c}\u{2500}\u{2500}\u{2510}\n\u{2502}* \u{2502}\n\u{2502} *\u{2502}\n\u{2514}\u{2500}\u{2500}\u{2518}\n");
+    }
+}
@@ -0,0 +1,190 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_rle {
+    use crate::file::*;
+    use crate::grid::*;
+    use crate::life::*;
+
+    fn create_rleparser(contents: &str) -> RleParser<'_> {
+        let mut parser = RleParser::from_string(contents);
+        parser.set_test();  // <--- This causes a panic instead of process exit.
+
+        parser
+    }
+
+    #[test]
+    fn rleparser_glider() {
+        let pattern = create_rleparser("#N Glider\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!\n").parse();
+
+        assert_eq!(pattern.get_width(), 3);
+        assert_eq!(pattern.get_height(), 3);
+        assert_eq!(pattern.get_rule(), Some(String::from("B3/S23")));
+        assert_eq!(pattern.get_cells(), &[(1, 0, 1), (2, 1, 1), (0, 2, 1), (1, 2, 1), (2, 2, 1)]);
+    }
+
+    #[test]
+    fn rleparser_no_rule() {
+        let pattern = create_rleparser("x = 3, y = 1\n3o!").parse();
+
+        assert_eq!(pattern.get_rule(), None);
+        assert_eq!(pattern.get_cells().len(), 3);
+    }
+
+    #[test]
+    fn rleparser_multiple_lines_and_rows() {
+        let pattern = create_rleparser("x = 2, y = 4\no\n$\n2$o\nb!").parse();
+
+        assert_eq!(pattern.get_cells(), &[(0, 0, 1), (0, 3, 1)]);
+    }
+
+    #[test]
+    fn rleparser_multi_state() {
+        let pattern = create_rleparser("x = 4, y = 2, rule = B2/S/C3\n.AB$2B.pA!").parse();
+
+        assert_eq!(pattern.get_cells(), &[(1, 0, 1), (2, 0, 2), (0, 1, 2), (1, 1, 2), (3, 1, 25)]);
+    }
+
+    #[test]
+    fn rleparser_ignores_after_end() {
+        let pattern = create_rleparser("x = 3, y = 1\no!\nthis is ignored").parse();
+
+        assert_eq!(pattern.get_cells(), &[(0, 0, 1)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rleparser_no_header() {
+        create_rleparser("#C just a comment\n").parse();
+    }
+
+    #[test]
+    #[should_panic]
+    fn rleparser_bad_header() {
+        create_rleparser("x = 3\n3o!").parse();
+    }
+
+    #[test]
+    #[should_panic]
+    fn rleparser_out_of_bounds() {
+        create_rleparser("x = 2, y = 1\n3o!").parse();
+    }
+
+    #[test]
+    #[should_panic]
+    fn rleparser_bad_char() {
+        create_rleparser("x = 3, y = 1\no?o!").parse();
+    }
+
+    #[test]
+    fn rleparser_rule_with_commas() {
+        let pattern = create_rleparser("x = 1, y = 1, rule = R5,C0,M1,S34..58,B34..45,NM\no!").parse();
+
+        assert_eq!(pattern.get_rule(), Some(String::from("R5,C0,M1,S34..58,B34..45,NM")));
+    }
+
+    #[test]
+    fn write_rle_glider() {
+        let mut grid = SimpleGrid::init_life(3, 3);
+        for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            grid.set_live(x, y);
+        }
+
+        let mut output = Vec::new();
+        write_rle(&grid, Some("B3/S23"), &mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+    }
+
+    #[test]
+    fn write_rle_empty_rows() {
+        let mut grid = SimpleGrid::init_life(4, 5);
+        grid.set_live(0, 0);
+        grid.set_live(3, 3);
+
+        let mut output = Vec::new();
+        write_rle(&grid, None, &mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "x = 4, y = 5\no3$3bo!\n");
+    }
+
+    #[test]
+    fn write_rle_leading_empty_rows() {
+        let mut grid = SimpleGrid::init_life(3, 4);
+        grid.set_live(1, 2);
+
+        let mut output = Vec::new();
+        write_rle(&grid, None, &mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "x = 3, y = 4\n2$bo!\n");
+        assert_eq!(encode_rle(&grid), "2$bo!");
+    }
+
+    #[test]
+    fn write_rle_round_trip() {
+        // Multi-state, with a state needing a prefix, and long enough to be
+        // split across lines...
+        let mut grid = SimpleGrid::init_life(60, 2);
+        for x in (0..60).step_by(2) {
+            grid.set_state(x, 0, 1 + (x / 2) as u8);
+        }
+        grid.set_state(59, 1, 2);
+
+        let mut output = Vec::new();
+        write_rle(&grid, Some("WireWorld"), &mut output).unwrap();
+        let text = String::from_utf8(output).unwrap();
+        let pattern = create_rleparser(&text).parse();
+
+        assert!(text.lines().all(|line| line.len() <= 70));
+        assert_eq!(pattern.get_rule(), Some(String::from("WireWorld")));
+        assert_eq!(pattern.get_cells().len(), 31);
+        assert!(pattern.get_cells().iter().all(|(x, y, state)| grid.get(*x, *y).get() == *state));
+    }
+
+    #[test]
+    fn write_life_cells() {
+        let mut grid = SimpleGrid::init_life(3, 2);
+        grid.set_live(2, 0);
+        grid.set_live(0, 1);
+
+        let mut output = Vec::new();
+        write_life(&grid, &mut output).unwrap();
+        let text = String::from_utf8(output).unwrap();
+        let cells: Vec<(usize, usize)> = FileParser::from_string(&text).iter().collect();
+
+        assert_eq!(cells, vec![(3, 2), (2, 0), (0, 1)]);
+    }
+
+    #[test]
+    fn write_life_multi_state() {
+        let mut grid = SimpleGrid::init_life(2, 1);
+        grid.set_state(0, 0, 2);
+
+        assert!(write_life(&grid, &mut Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,228 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_rule {
+    use crate::grid::*;
+    use crate::life::*;
+    use crate::neighbourhood::*;
+    use crate::rule::*;
+
+    #[test]
+    fn rule_default_is_conway() {
+        assert_eq!(LifeRule::default(), LifeRule::parse("B3/S23").unwrap());
+    }
+
+    #[test]
+    fn rule_parse_bs() {
+        let rule = LifeRule::parse("b36/s23").unwrap();
+
+        assert!(rule.is_birth(3));
+        assert!(rule.is_birth(6));
+        assert!(!rule.is_birth(2));
+        assert!(rule.is_survival(2));
+        assert!(!rule.is_survival(6));
+        assert_eq!(rule.get_states(), 2);
+    }
+
+    #[test]
+    fn rule_parse_sb() {
+        assert_eq!(LifeRule::parse("23/36").unwrap(), LifeRule::parse("B36/S23").unwrap());
+    }
+
+    #[test]
+    fn rule_parse_generations_bsc() {
+        let rule = LifeRule::parse("B2/S/C3").unwrap();
+
+        assert!(rule.is_birth(2));
+        assert!(!rule.is_survival(2));
+        assert_eq!(rule.get_states(), 3);
+    }
+
+    #[test]
+    fn rule_parse_generations_sbc() {
+        assert_eq!(LifeRule::parse("345/2/4").unwrap(), LifeRule::parse("B2/S345/C4").unwrap());
+    }
+
+    #[test]
+    fn rule_parse_bad() {
+        assert!(LifeRule::parse("B3").is_err());
+        assert!(LifeRule::parse("B39/S23").is_err());
+        assert!(LifeRule::parse("B3/S23/C1").is_err());
+        assert!(LifeRule::parse("B3/X23").is_err());
+        assert!(LifeRule::parse("B3/B2").is_err());
+    }
+
+    #[test]
+    fn rule_display() {
+        assert_eq!(LifeRule::parse("23/3").unwrap().to_string(), "B3/S23");
+        assert_eq!(LifeRule::parse("345/2/4").unwrap().to_string(), "B2/S345/C4");
+    }
+
+    #[test]
+    fn rule_next_state_generations() {
+        let rule = LifeRule::parse("B2/S/C4").unwrap();
+
+        assert_eq!(rule.next_state(LifeState::DEAD, 2), LifeState::LIVE);
+        assert_eq!(rule.next_state(LifeState::LIVE, 2), LifeState(2));
+        assert_eq!(rule.next_state(LifeState(2), 2), LifeState(3));
+        assert_eq!(rule.next_state(LifeState(3), 2), LifeState::DEAD);
+    }
+
+    #[test]
+    fn rule_step_blinker() {
+        let mut grid = SimpleGrid::init_life(5, 5);
+        grid.set_live(1, 2);
+        grid.set_live(2, 2);
+        grid.set_live(3, 2);
+
+        let next = LifeRule::default().step(&grid);
+
+        assert!(next.get_cell(2, 1).is_live());
+        assert!(next.get_cell(2, 2).is_live());
+        assert!(next.get_cell(2, 3).is_live());
+        assert!(next.get_cell(1, 2).is_dead());
+        assert!(next.get_cell(3, 2).is_dead());
+    }
+
+    #[test]
+    fn rule_step_brians_brain() {
+        let rule = LifeRule::parse("B2/S/C3").unwrap();
+        let mut grid = SimpleGrid::init_life(6, 6);
+        grid.set_live(2, 2);
+        grid.set_live(2, 3);
+
+        let next = rule.step(&grid);
+
+        // The live cells start dying, and the four cells either side (each
+        // with exactly 2 live neighbours) are born...
+        assert!(next.get_cell(2, 2).is_dying());
+        assert!(next.get_cell(2, 3).is_dying());
+        assert!(next.get_cell(1, 2).is_live());
+        assert!(next.get_cell(3, 3).is_live());
+        assert!(next.get_cell(2, 1).is_dead());
+
+        let next = rule.step(&next);
+
+        assert!(next.get_cell(2, 2).is_dead());
+        assert!(next.get_cell(1, 2).is_dying());
+    }
+
+    #[test]
+    fn rule_parse_neighbourhood_suffix() {
+        assert_eq!(LifeRule::parse("B2/S013V").unwrap().get_neighbourhood(), &Neighbourhood::VonNeumann(1));
+        assert_eq!(LifeRule::parse("b2/s34h").unwrap().get_neighbourhood(), &Neighbourhood::Hexagonal);
+        assert_eq!(LifeRule::parse("B3/S23").unwrap().get_neighbourhood(), &Neighbourhood::Moore(1));
+        assert_eq!(LifeRule::parse("/2/3H").unwrap().get_states(), 3);
+    }
+
+    #[test]
+    fn rule_parse_neighbourhood_counts() {
+        // Counts are limited by the size of the neighbourhood...
+        assert!(LifeRule::parse("B5/S23V").is_err());
+        assert!(LifeRule::parse("B6/S23H").is_ok());
+        assert!(LifeRule::parse("B7/S23H").is_err());
+
+        // ...and are separated by commas for the larger neighbourhoods...
+        let rule = LifeRule::parse("B3,10/S2,3M2").unwrap();
+
+        assert!(rule.is_birth(10));
+        assert!(!rule.is_birth(1));
+        assert!(LifeRule::parse("B25/S2M2").is_err());
+    }
+
+    #[test]
+    fn rule_parse_with_neighbourhood() {
+        let mask = Neighbourhood::Custom(vec![(-1, 0), (1, 0)]);
+
+        assert_eq!(LifeRule::parse_with_neighbourhood("B1/S2", mask.clone()).unwrap().get_neighbourhood(), &mask);
+        assert!(LifeRule::parse_with_neighbourhood("B3/S2", mask.clone()).is_err());
+        assert_eq!(LifeRule::parse_with_neighbourhood("B1/S2V", mask).unwrap().get_neighbourhood(), &Neighbourhood::VonNeumann(1));
+    }
+
+    #[test]
+    fn rule_takes_neighbourhood() {
+        assert!(takes_neighbourhood("B3/S23"));
+        assert!(takes_neighbourhood("B2/S/C3"));
+        assert!(takes_neighbourhood("B2/S013V"));
+        assert!(!takes_neighbourhood("B2-a/S12"));
+        assert!(!takes_neighbourhood("R2,C0,M1,S2..3,B3..3,NM"));
+        assert!(!takes_neighbourhood("WireWorld"));
+    }
+
+    #[test]
+    fn rule_display_neighbourhood() {
+        assert_eq!(LifeRule::parse("B2/S013V").unwrap().to_string(), "B2/S013V");
+        assert_eq!(LifeRule::parse("B3,10/S2,3M2").unwrap().to_string(), "B3,10/S2,3M2");
+        assert_eq!(LifeRule::parse("/2/3H").unwrap().to_string(), "B2/S/C3H");
+    }
+
+    #[test]
+    fn rule_display_custom_reparse() {
+        // A rule over a custom mask of more than 9 neighbours parses again,
+        // mask and all...
+        let mask = Neighbourhood::parse_mask("*****\n*****\n**.**\n*****\n*****\n").unwrap();
+        let rule = LifeRule::parse_with_neighbourhood("B3,10/S2,3", mask.clone()).unwrap();
+        let text = rule.to_string();
+        let reparsed = LifeRule::parse(&text).unwrap();
+
+        assert_eq!(text, "B3,10/S2,3@fff7ff8");
+        assert_eq!(reparsed.to_string(), text);
+        assert_eq!(reparsed.get_neighbourhood(), &mask);
+    }
+
+    #[test]
+    fn rule_step_von_neumann() {
+        // With B1/S von Neumann, a single cell becomes a diamond ring...
+        let rule = LifeRule::parse("B1/SV").unwrap();
+        let mut grid = SimpleGrid::init_life(5, 5);
+        grid.set_live(2, 2);
+
+        let next = rule.step(&grid);
+
+        assert!(next.get_cell(2, 1).is_live());
+        assert!(next.get_cell(1, 2).is_live());
+        assert!(next.get_cell(3, 2).is_live());
+        assert!(next.get_cell(2, 3).is_live());
+        assert!(next.get_cell(1, 1).is_dead());
+        assert!(next.get_cell(2, 2).is_dead());
+    }
+
+    #[test]
+    fn rule_step_hexagonal() {
+        let rule = LifeRule::parse("B1/SH").unwrap();
+        let mut grid = SimpleGrid::init_life(5, 5);
+        grid.set_live(2, 2);
+
+        let next = rule.step(&grid);
+
+        assert!(next.get_cell(1, 1).is_live());
+        assert!(next.get_cell(3, 3).is_live());
+        assert!(next.get_cell(3, 1).is_dead());
+        assert!(next.get_cell(1, 3).is_dead());
+    }
+}
\ No newline at end of file
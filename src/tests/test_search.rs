@@ -0,0 +1,140 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_search {
+    use crate::census::*;
+    use crate::grid::*;
+    use crate::life::*;
+    use crate::rule::*;
+    use crate::search::*;
+    use crate::tests::common::*;
+
+    fn rows(grid: &LifeGridType) -> Vec<String> {
+        (0..grid.get_height())
+            .map(|y| (0..grid.get_width()).map(|x| grid.get(x, y).to_char()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn random_reproducible() {
+        let mut first = Random::from_seed(42);
+        let mut second = Random::from_seed(42);
+        let mut other = Random::from_seed(43);
+
+        let numbers: Vec<u64> = (0..5).map(|_| first.next_u64()).collect();
+
+        assert_eq!(numbers, (0..5).map(|_| second.next_u64()).collect::<Vec<u64>>());
+        assert_ne!(numbers, (0..5).map(|_| other.next_u64()).collect::<Vec<u64>>());
+        assert!((0..1000).map(|_| first.next_f64()).all(|value| (0.0..1.0).contains(&value)));
+    }
+
+    #[test]
+    fn search_generate() {
+        let mut search = SoupSearch::new(32, 32);
+        search.set_seed(7);
+
+        let soup = search.generate(3);
+
+        assert_eq!(soup.get_width(), 32);
+        assert_eq!(rows(&soup), rows(&search.generate(3)));
+        assert_ne!(rows(&soup), rows(&search.generate(4)));
+        assert!((400..624).contains(&soup.get_population()));
+
+        search.set_seed(8);
+        assert_ne!(rows(&soup), rows(&search.generate(3)));
+    }
+
+    #[test]
+    fn search_generate_density() {
+        let mut search = SoupSearch::new(8, 8);
+
+        search.set_density(0.0);
+        assert_eq!(search.generate(0).get_population(), 0);
+
+        search.set_density(1.0);
+        assert_eq!(search.generate(0).get_population(), 64);
+    }
+
+    #[test]
+    fn search_run_soup_stable() {
+        let search = SoupSearch::new(4, 4);
+        let block = create_grid(&["**", "**"]);
+
+        let result = search.run_soup(&block, &LifeRule::default(), &Catalogue::builtin());
+
+        assert_eq!(result.lifespan, Some(0));
+        assert!(result.escapees.is_empty());
+    }
+
+    #[test]
+    fn search_run_soup_escapee() {
+        // A glider leaving a block is removed once it has escaped...
+        let search = SoupSearch::new(4, 4);
+        let grid = create_grid(&[
+            "**      ",
+            "**      ",
+            "        ",
+            "        ",
+            "     *  ",
+            "      * ",
+            "    *** "
+        ]);
+
+        let result = search.run_soup(&grid, &LifeRule::default(), &Catalogue::builtin());
+
+        assert_eq!(result.escapees, vec!["glider"]);
+        assert_eq!(result.universe.get_population(), 4);
+        assert!(result.lifespan.is_some());
+    }
+
+    #[test]
+    fn search_run_soup_limit() {
+        let mut search = SoupSearch::new(4, 4);
+        search.set_max_generations(3);
+        let glider = create_grid(&[" * ", "  *", "***"]);
+
+        let result = search.run_soup(&glider, &LifeRule::default(), &Catalogue::builtin());
+
+        assert_eq!(result.lifespan, None);
+    }
+
+    #[test]
+    fn search_run() {
+        let mut search = SoupSearch::new(5, 5);
+        search.set_seed(1);
+        search.set_max_generations(500);
+
+        let report = search.run(3, &LifeRule::default(), &Catalogue::builtin());
+        let again = search.run(3, &LifeRule::default(), &Catalogue::builtin());
+
+        assert_eq!(report.get_soups(), 3);
+        assert_eq!(report.get_stabilised(), 3);
+        assert_eq!(report.get_census().get_entries(), again.get_census().get_entries());
+        assert!(report.to_string().starts_with("Searched 3 soups of 5x5 at density 0.5 (seed 1):\n"));
+    }
+}
@@ -28,6 +28,7 @@
 
 mod test_simplegrid {
     use crate::grid::*;
+    use crate::tests::common::*;
 
     #[test]
     fn simple_init_from() {
@@ -154,4 +155,45 @@ mod test_simplegrid {
 
         assert_eq!(grid[(3, 2)], 7);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn simple_eq() {
+        let mut first: SimpleGrid<i8> = SimpleGrid::new(3, 2);
+        let mut second: SimpleGrid<i8> = SimpleGrid::new(3, 2);
+
+        assert_eq!(first, second);
+        assert_eq!(hash_of(&first), hash_of(&second));
+
+        first.set(1, 1, 5);
+        assert_ne!(first, second);
+
+        second.set(1, 1, 5);
+        assert_eq!(first, second);
+        assert_eq!(hash_of(&first), hash_of(&second));
+
+        assert_ne!(SimpleGrid::<i8>::new(3, 2), SimpleGrid::<i8>::new(2, 3));
+    }
+
+    #[test]
+    fn simple_transform() {
+        let grid = create_char_grid(&["ab", "cd", "ef"]);
+
+        assert_eq!(grid.transform(Symmetry::Identity), grid);
+        assert_eq!(grid.transform(Symmetry::Rotate90), create_char_grid(&["eca", "fdb"]));
+        assert_eq!(grid.transform(Symmetry::Rotate180), create_char_grid(&["fe", "dc", "ba"]));
+        assert_eq!(grid.transform(Symmetry::Rotate270), create_char_grid(&["bdf", "ace"]));
+        assert_eq!(grid.transform(Symmetry::FlipHorizontal), create_char_grid(&["ba", "dc", "fe"]));
+        assert_eq!(grid.transform(Symmetry::FlipVertical), create_char_grid(&["ef", "cd", "ab"]));
+        assert_eq!(grid.transform(Symmetry::Transpose), create_char_grid(&["ace", "bdf"]));
+        assert_eq!(grid.transform(Symmetry::AntiTranspose), create_char_grid(&["fdb", "eca"]));
+    }
+
+    #[test]
+    fn simple_transform_compose() {
+        let grid = create_char_grid(&["abc", "def"]);
+
+        assert_eq!(grid.transform(Symmetry::Rotate90).transform(Symmetry::Rotate90), grid.transform(Symmetry::Rotate180));
+        assert_eq!(grid.transform(Symmetry::Rotate90).transform(Symmetry::FlipHorizontal), grid.transform(Symmetry::Transpose));
+        assert_eq!(SimpleGrid::<char>::new(0, 0).transform(Symmetry::Rotate90).get_width(), 0);
+    }
+}
@@ -28,6 +28,7 @@
 
 mod test_sparsegrid {
     use crate::grid::*;
+    use crate::tests::common::*;
 
     #[test]
     fn sparse_new() {
@@ -224,4 +225,36 @@ mod test_sparsegrid {
 
         assert_eq!(grid[(3, 2)], 7);
     }
+
+    #[test]
+    fn sparse_eq() {
+        let mut first: SparseGrid<i8> = SparseGrid::new();
+        let mut second: SparseGrid<i8> = SparseGrid::new();
+
+        first.set(3, 4, 1);
+        first.set(100, 2, 7);
+        second.set(100, 2, 7);
+        second.set(3, 4, 1);
+
+        assert_eq!(first, second);
+        assert_eq!(hash_of(&first), hash_of(&second));
+
+        second.set(3, 4, 2);
+        assert_ne!(first, second);
+        assert_ne!(hash_of(&first), hash_of(&second));
+    }
+
+    #[test]
+    fn sparse_eq_default() {
+        // A cell explicitly set to the default value is the same as one that
+        // isn't set...
+        let mut first: SparseGrid<i8> = SparseGrid::new();
+        let second: SparseGrid<i8> = SparseGrid::new();
+
+        first.set(1, 1, 0);
+        let _ = first.get_mut(2, 2);
+
+        assert_eq!(first, second);
+        assert_eq!(hash_of(&first), hash_of(&second));
+    }
 }
@@ -0,0 +1,137 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_stats {
+    use crate::grid::*;
+    use crate::life::*;
+    use crate::rule::*;
+    use crate::stats::*;
+    use crate::tests::common::*;
+    use crate::universe::*;
+
+    fn write_and_read(name: &str, all_stats: &[GenerationStats]) -> String {
+        let path = std::env::temp_dir().join(name);
+        let path = path.to_str().unwrap();
+
+        let mut writer = StatsWriter::from_path(path).unwrap();
+        for stats in all_stats {
+            writer.write(stats).unwrap();
+        }
+        writer.flush().unwrap();
+        drop(writer);
+
+        let contents = std::fs::read_to_string(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        contents
+    }
+
+    #[test]
+    fn stats_from_grids() {
+        let previous = create_grid(&["     ", "     ", " *** ", "     ", "     "]);
+        let current = LifeRule::default().step(&previous);
+
+        let stats = GenerationStats::from_grids(1, &previous, &current);
+
+        assert_eq!(stats.generation, 1);
+        assert_eq!(stats.population, 3);
+        assert_eq!(stats.births, 2);
+        assert_eq!(stats.deaths, 2);
+        assert_eq!(stats.changed, 4);
+        assert_eq!(stats.bounds, Some(BoundingBox::new(2, 1, 1, 3)));
+        assert_eq!(stats.density, 1.0);
+    }
+
+    #[test]
+    fn stats_dying_states() {
+        // A 'live' cell becoming 'dying' is a death, and a 'dying' cell
+        // decaying is a change...
+        let previous = create_grid(&["*o", "  "]);
+        let current = create_grid(&["o ", " *"]);
+
+        let stats = GenerationStats::from_grids(1, &previous, &current);
+
+        assert_eq!((stats.births, stats.deaths, stats.changed), (1, 1, 3));
+        assert_eq!(stats.population, 1);
+        assert_eq!(stats.density, 0.25);
+    }
+
+    #[test]
+    fn stats_from_grid() {
+        let stats = GenerationStats::from_grid(0, &SimpleGrid::init_life(4, 4));
+
+        assert_eq!((stats.population, stats.births, stats.deaths, stats.changed), (0, 0, 0, 0));
+        assert_eq!(stats.bounds, None);
+        assert_eq!(stats.density, 0.0);
+    }
+
+    #[test]
+    fn stats_from_universes() {
+        let previous = Universe::from_grid(&create_grid(&[" * ", "  *", "***"]));
+        let mut current = previous.clone();
+        for _ in 0..4 {
+            current.step(&LifeRule::default());
+        }
+
+        let stats = GenerationStats::from_universes(4, &previous, &current);
+
+        assert_eq!(stats.bounds, Some(BoundingBox::new(1, 1, 3, 3)));
+        assert_eq!(stats.population, 5);
+        assert_eq!((stats.births, stats.deaths), (4, 4));
+    }
+
+    #[test]
+    fn stats_format() {
+        assert_eq!(StatsFormat::from_path("out.csv"), StatsFormat::Csv);
+        assert_eq!(StatsFormat::from_path("out.JSONL"), StatsFormat::JsonLines);
+        assert_eq!(StatsFormat::from_path("out.json"), StatsFormat::JsonLines);
+        assert_eq!(StatsFormat::from_path("out"), StatsFormat::Csv);
+    }
+
+    #[test]
+    fn stats_write_csv() {
+        let grid = create_grid(&["  ", " *"]);
+        let all_stats = [GenerationStats::from_grid(0, &grid), GenerationStats::from_grid(1, &SimpleGrid::init_life(2, 2))];
+
+        let contents = write_and_read("test_stats_write.csv", &all_stats);
+
+        assert_eq!(contents, "generation,population,births,deaths,changed,x,y,width,height,density\n0,1,0,0,0,1,1,1,1,1\n1,0,0,0,0,,,,,0\n");
+    }
+
+    #[test]
+    fn stats_write_json_lines() {
+        let grid = create_grid(&["  ", " *"]);
+        let all_stats = [GenerationStats::from_grid(0, &grid), GenerationStats::from_grid(1, &SimpleGrid::init_life(2, 2))];
+
+        let contents = write_and_read("test_stats_write.jsonl", &all_stats);
+
+        assert_eq!(contents, "\
+{\"generation\":0,\"population\":1,\"births\":0,\"deaths\":0,\"changed\":0,\"bounding_box\":{\"x\":1,\"y\":1,\"width\":1,\"height\":1},\"density\":1}
+{\"generation\":1,\"population\":0,\"births\":0,\"deaths\":0,\"changed\":0,\"bounding_box\":null,\"density\":0}
+");
+    }
+}
@@ -0,0 +1,118 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_svg {
+    use crate::grid::*;
+    use crate::image::*;
+    use crate::life::*;
+    use crate::svg::*;
+    use crate::tests::common::*;
+
+    fn write(svg: &SvgRenderer, generation: usize, grids: &[LifeGridType], previous: Option<&LifeGridType>, origin: (isize, isize)) -> String {
+        let mut bytes = Vec::new();
+        svg.write_strip(generation, grids, previous, origin, &mut bytes).unwrap();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn svg_write() {
+        let grid = create_grid(&["*  ", "  *"]);
+
+        let mut bytes = Vec::new();
+        SvgRenderer::new().write(&grid, (0, 0), &mut bytes).unwrap();
+        let text = String::from_utf8(bytes).unwrap();
+
+        assert!(text.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"50\" height=\"40\""));
+        assert!(text.contains("<rect width=\"30\" height=\"20\" fill=\"#ffffff\"/>"));
+        assert!(text.contains("<rect x=\"0\" y=\"0\" width=\"10\" height=\"10\" fill=\"#000000\"/>"));
+        assert!(text.contains("<rect x=\"20\" y=\"10\" width=\"10\" height=\"10\" fill=\"#000000\"/>"));
+        assert_eq!(text.matches("fill=\"#000000\"").count(), 2);
+        assert!(!text.contains("<text"));
+        assert!(text.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn svg_palette_grid_lines() {
+        let grid = create_grid(&["* ", "  "]);
+        let mut svg = SvgRenderer::new();
+        svg.set_cell_size(4);
+        svg.set_palette(Palette::parse("#101010,red").unwrap());
+        svg.set_grid_lines(Some(GRID_LINE_COLOUR));
+
+        let text = write(&svg, 0, &[grid], None, (0, 0));
+
+        assert!(text.contains("<rect width=\"8\" height=\"8\" fill=\"#101010\"/>"));
+        assert!(text.contains("<rect x=\"0\" y=\"0\" width=\"4\" height=\"4\" fill=\"#ff0000\"/>"));
+        assert!(text.contains("<path d=\"M0 0V8M4 0V8M8 0V8M0 0H8M0 4H8M0 8H8\" stroke=\"#c0c0c0\""));
+    }
+
+    #[test]
+    fn svg_strip() {
+        let blinker = create_grid(&["   ", "***", "   "]);
+        let other = create_grid(&[" * ", " * ", " * "]);
+
+        let text = write(&SvgRenderer::new(), 4, &[blinker.clone(), other, blinker], None, (0, 0));
+
+        // Three panels of 30 pixels, 10 pixels apart, under captions...
+        assert!(text.contains("width=\"130\" height=\"68\""));
+        assert!(text.contains("<g transform=\"translate(90,10)\">"));
+        assert!(text.contains(">Generation 4</text>"));
+        assert!(text.contains(">Generation 6</text>"));
+        assert!(!text.contains("class=\"birth\""));
+    }
+
+    #[test]
+    fn svg_highlight() {
+        let before = create_grid(&["   ", "***", "   "]);
+        let after = create_grid(&[" * ", " * ", " * "]);
+        let mut svg = SvgRenderer::new();
+        svg.set_highlighting(true);
+
+        let text = write(&svg, 1, &[after], Some(&before), (0, 0));
+
+        assert!(text.contains("<rect class=\"birth\" x=\"10\" y=\"0\" width=\"10\" height=\"10\" fill=\"#00a000\"/>"));
+        assert!(text.contains("<rect class=\"death\" x=\"0\" y=\"10\" width=\"10\" height=\"10\" fill=\"#f09696\"/>"));
+        assert_eq!(text.matches("class=\"birth\"").count(), 2);
+        assert_eq!(text.matches("class=\"death\"").count(), 2);
+    }
+
+    #[test]
+    fn svg_labels() {
+        let grid = SimpleGrid::init_life(12, 3);
+        let mut svg = SvgRenderer::new();
+        svg.set_cell_size(5);
+        svg.set_labelled(true);
+
+        let text = write(&svg, 0, &[grid], None, (-3, 8));
+
+        // Every 5th coordinate, 20 pixels apart...
+        let labels: Vec<&str> = text.split("</text>").filter_map(|part| part.rsplit_once('>').map(|(_, label)| label)).filter(|label| !label.trim().is_empty()).collect();
+        assert_eq!(labels, vec!["0", "5", "10"]);
+        assert!(text.contains("text-anchor=\"end\""));
+    }
+}
@@ -0,0 +1,95 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_symmetry {
+    use crate::grid::*;
+    use crate::life::*;
+    use crate::rule::*;
+    use crate::symmetry::*;
+    use crate::tests::common::*;
+
+    fn group(rows: &[&str]) -> &'static str {
+        analyse(&create_grid(rows), &LifeRule::default(), 0).get_group()
+    }
+
+    #[test]
+    fn symmetry_groups() {
+        assert_eq!(group(&["**", "**"]), "D8");
+        assert_eq!(group(&["***"]), "D4");
+        assert_eq!(group(&["** ", "* *", " * "]), "D2");
+        assert_eq!(group(&[" **", "** "]), "C2");
+        assert_eq!(group(&[" *  ", " ***", "*** ", "  * "]), "C4");
+        assert_eq!(group(&[" * ", "  *", "***"]), "C1");
+    }
+
+    #[test]
+    fn symmetry_list() {
+        let report = analyse(&create_grid(&["     ", " *** ", "     "]), &LifeRule::default(), 0);
+
+        assert_eq!(report.symmetries, vec![Symmetry::Rotate180, Symmetry::FlipHorizontal, Symmetry::FlipVertical]);
+        assert_eq!(report.bounds, Some(BoundingBox::new(1, 1, 3, 1)));
+        assert_eq!(report.population, 3);
+    }
+
+    #[test]
+    fn symmetry_glide_blinker() {
+        // A blinker becomes its own quarter-turn after one generation...
+        let report = analyse(&create_grid(&["***"]), &LifeRule::default(), 10);
+
+        assert_eq!(report.glides[0], Glide { symmetry: Symmetry::Rotate90, generations: 1, dx: 1, dy: -1 });
+    }
+
+    #[test]
+    fn symmetry_glide_glider() {
+        let report = analyse(&create_grid(&[" * ", "  *", "***"]), &LifeRule::default(), 10);
+
+        assert_eq!(report.glides, vec![Glide { symmetry: Symmetry::Transpose, generations: 2, dx: 0, dy: 1 }]);
+        assert_eq!(report.glides[0].to_string(), "transpose after 2 generations, displaced by (0, 1)");
+    }
+
+    #[test]
+    fn symmetry_report_display() {
+        let report = analyse(&create_grid(&["**", "**"]), &LifeRule::default(), 10);
+
+        assert_eq!(report.to_string(), "\
+Bounding box: 2x2 at (0, 0)
+Population: 4
+Symmetry: D8 (rotate 90, rotate 180, rotate 270, flip horizontal, flip vertical, transpose, anti-transpose)
+Glide symmetries: none
+");
+    }
+
+    #[test]
+    fn symmetry_empty() {
+        let report = analyse(&SimpleGrid::init_life(3, 3), &LifeRule::default(), 10);
+
+        assert_eq!(report.bounds, None);
+        assert_eq!(report.get_group(), "D8");
+        assert!(report.glides.is_empty());
+    }
+}
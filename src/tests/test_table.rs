@@ -0,0 +1,185 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_table {
+    use crate::grid::*;
+    use crate::life::*;
+    use crate::neighbourhood::*;
+    use crate::rule::*;
+    use crate::table::*;
+    use crate::tests::common::*;
+
+    /// A von Neumann rule where a cell becomes 1 if it has a 1 to its north,
+    /// in any rotation.
+    static ROTATE_TABLE: &str = "\
+@RULE Spread
+@TABLE
+n_states:2
+neighborhood:vonNeumann
+symmetries:rotate4
+var a={0,1}
+var b={0,1}
+var c={0,1}
+0,1,a,b,c,1
+";
+
+    fn states(grid: &LifeGridType) -> Vec<String> {
+        (0..grid.get_height())
+            .map(|y| (0..grid.get_width()).map(|x| grid.get(x, y).get().to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn table_parse_wireworld() {
+        let rule = TableRule::wireworld();
+
+        assert_eq!(rule.get_name(), "WireWorld");
+        assert_eq!(rule.get_states(), 4);
+        assert_eq!(rule.to_string(), "WireWorld (4 states, Moore, permute)");
+    }
+
+    #[test]
+    fn table_builtin() {
+        assert!(TableRule::builtin("wireworld").is_some());
+        assert!(TableRule::builtin("nothing").is_none());
+        assert_eq!(parse_rule("WireWorld", Neighbourhood::default()).unwrap().get_states(), 4);
+    }
+
+    #[test]
+    fn table_rulestring() {
+        let mut table = TableRule::wireworld();
+        assert_eq!(table.get_rulestring(), "WireWorld");
+
+        table.set_path("rules/wireworld.rule");
+        assert_eq!(table.get_rulestring(), "rules/wireworld.rule");
+    }
+
+    #[test]
+    fn table_wireworld_transitions() {
+        let rule = TableRule::wireworld();
+
+        assert_eq!(rule.next_table_state(1, &[0, 0, 0, 0, 0, 0, 0, 0]), 2);
+        assert_eq!(rule.next_table_state(2, &[1, 1, 1, 0, 0, 0, 0, 0]), 3);
+        assert_eq!(rule.next_table_state(3, &[0, 0, 0, 0, 1, 0, 0, 0]), 1);
+        assert_eq!(rule.next_table_state(3, &[1, 0, 0, 3, 0, 2, 0, 1]), 1);
+        assert_eq!(rule.next_table_state(3, &[1, 1, 1, 0, 0, 0, 0, 0]), 3);
+        assert_eq!(rule.next_table_state(3, &[0, 0, 0, 0, 0, 0, 0, 0]), 3);
+        assert_eq!(rule.next_table_state(0, &[1, 1, 0, 0, 0, 0, 0, 0]), 0);
+    }
+
+    #[test]
+    fn table_wireworld_electron() {
+        let rule = TableRule::wireworld();
+        let grid = create_state_grid(&["00000000", "33213333", "00000000"]);
+
+        let next = rule.step(&grid);
+
+        assert_eq!(states(&next), vec!["00000000", "33321333", "00000000"]);
+    }
+
+    #[test]
+    fn table_wireworld_clock() {
+        // An electron on a loop of 10 cells returns to its start after 10
+        // generations, and not before...
+        let rule = TableRule::wireworld();
+        let clock = create_state_grid(&["033330", "300003", "032130"]);
+
+        let mut grid = rule.step(&clock);
+        for _ in 1..9 {
+            assert_ne!(states(&grid), states(&clock));
+            grid = rule.step(&grid);
+        }
+
+        assert_eq!(states(&rule.step(&grid)), states(&clock));
+    }
+
+    #[test]
+    fn table_rotate_symmetry() {
+        let rule = TableRule::parse(ROTATE_TABLE).unwrap();
+        let grid = create_state_grid(&["000", "010", "000"]);
+
+        assert_eq!(states(&rule.step(&grid)), vec!["010", "111", "010"]);
+    }
+
+    #[test]
+    fn table_no_symmetry() {
+        let table = ROTATE_TABLE.replace("rotate4", "none");
+        let rule = TableRule::parse(&table).unwrap();
+        let grid = create_state_grid(&["000", "010", "000"]);
+
+        // Only the cell south of the '1' has a '1' to its north...
+        assert_eq!(states(&rule.step(&grid)), vec!["000", "010", "010"]);
+    }
+
+    #[test]
+    fn table_bound_variables() {
+        // The same variable must have the same value - and may be the
+        // output...
+        let table = "n_states:3\nneighborhood:vonNeumann\nsymmetries:none\nvar a={1,2}\n0,a,0,a,0,a\n";
+        let rule = TableRule::parse(table).unwrap();
+
+        assert_eq!(rule.next_table_state(0, &[2, 0, 2, 0]), 2);
+        assert_eq!(rule.next_table_state(0, &[1, 0, 1, 0]), 1);
+        assert_eq!(rule.next_table_state(0, &[1, 0, 2, 0]), 0);
+    }
+
+    #[test]
+    fn table_compact_transitions() {
+        let table = "n_states:3\nneighborhood:vonNeumann\nsymmetries:none\n012002\n";
+        let rule = TableRule::parse(table).unwrap();
+
+        assert_eq!(rule.next_table_state(0, &[1, 2, 0, 0]), 2);
+    }
+
+    #[test]
+    fn table_inline_sets() {
+        let table = "n_states:3\nneighborhood:vonNeumann\nsymmetries:permute\n0,{1,2},{1,2},0,0,1\n";
+        let rule = TableRule::parse(table).unwrap();
+
+        assert_eq!(rule.next_table_state(0, &[0, 2, 0, 1]), 1);
+        assert_eq!(rule.next_table_state(0, &[0, 2, 0, 0]), 0);
+    }
+
+    #[test]
+    fn table_parse_bad() {
+        assert!(TableRule::parse("neighborhood:Moore\n").is_err());
+        assert!(TableRule::parse("n_states:2\nneighborhood:Triangular\n").is_err());
+        assert!(TableRule::parse("n_states:2\nsymmetries:rotate3\n").is_err());
+        assert!(TableRule::parse("n_states:2\nneighborhood:vonNeumann\n0,1,0,0\n").is_err());
+        assert!(TableRule::parse("n_states:2\nneighborhood:vonNeumann\n0,1,0,0,2,1\n").is_err());
+        assert!(TableRule::parse("n_states:2\nneighborhood:vonNeumann\n0,x,0,0,0,1\n").is_err());
+        assert!(TableRule::parse("n_states:2\nneighborhood:vonNeumann\nvar a={0,1}\n0,1,0,0,0,a\n").is_err());
+    }
+
+    #[test]
+    fn table_ignores_other_sections() {
+        let table = format!("{}\n@COLORS\n1 255 0 0\n", ROTATE_TABLE);
+
+        assert!(TableRule::parse(&table).is_ok());
+    }
+}
@@ -0,0 +1,60 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_terminal {
+    use crate::terminal::*;
+
+    #[test]
+    fn key_decode_chars() {
+        assert_eq!(Key::decode(b"q"), Some((Key::Char('q'), 1)));
+        assert_eq!(Key::decode(b" s"), Some((Key::Char(' '), 1)));
+        assert_eq!(Key::decode("é".as_bytes()), Some((Key::Char('é'), 2)));
+        assert_eq!(Key::decode(b""), None);
+    }
+
+    #[test]
+    fn key_decode_control() {
+        assert_eq!(Key::decode(b"\x03"), Some((Key::CtrlC, 1)));
+        assert_eq!(Key::decode(b"\r"), Some((Key::Enter, 1)));
+        assert_eq!(Key::decode(b"\x7f"), Some((Key::Backspace, 1)));
+        assert_eq!(Key::decode(b"\x1b"), Some((Key::Escape, 1)));
+    }
+
+    #[test]
+    fn key_decode_arrows() {
+        assert_eq!(Key::decode(b"\x1b[A"), Some((Key::Up, 3)));
+        assert_eq!(Key::decode(b"\x1b[B"), Some((Key::Down, 3)));
+        assert_eq!(Key::decode(b"\x1b[C"), Some((Key::Right, 3)));
+        assert_eq!(Key::decode(b"\x1b[Dx"), Some((Key::Left, 3)));
+    }
+
+    #[test]
+    fn move_cursor_sequence() {
+        assert_eq!(move_cursor(15, 1), "\x1b[15;1H");
+    }
+}
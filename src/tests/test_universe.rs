@@ -0,0 +1,135 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+#[cfg(test)]
+
+mod test_universe {
+    use crate::grid::*;
+    use crate::life::*;
+    use crate::ltl::*;
+    use crate::rule::*;
+    use crate::tests::common::*;
+    use crate::universe::*;
+
+    fn rows(grid: &LifeGridType) -> Vec<String> {
+        (0..grid.get_height())
+            .map(|y| (0..grid.get_width()).map(|x| grid.get(x, y).to_char()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn universe_from_grid() {
+        let grid = create_grid(&["     ", "  *  ", "   * ", "     "]);
+
+        let universe = Universe::from_grid(&grid);
+
+        assert_eq!(universe.get_origin(), (2, 1));
+        assert_eq!(rows(universe.get_grid()), vec!["* ", " *"]);
+        assert_eq!(universe.get_bounding_box(), Some(BoundingBox::new(2, 1, 2, 2)));
+        assert_eq!(universe.get_population(), 2);
+        assert_eq!(universe.get_state(3, 2), LifeState::LIVE);
+        assert_eq!(universe.get_state(-5, 2), LifeState::DEAD);
+    }
+
+    #[test]
+    fn universe_empty() {
+        let mut universe = Universe::from_grid(&SimpleGrid::init_life(3, 3));
+
+        universe.step(&LifeRule::default());
+
+        assert!(universe.is_empty());
+        assert_eq!(universe.get_bounding_box(), None);
+        assert_eq!(universe.get_population(), 0);
+    }
+
+    #[test]
+    fn universe_blinker_grows_beyond_grid() {
+        // A blinker on the edge of its grid would be cut off in a bounded
+        // grid...
+        let grid = create_grid(&["***"]);
+        let mut universe = Universe::from_grid(&grid);
+
+        universe.step(&LifeRule::default());
+
+        assert_eq!(universe.get_origin(), (1, -1));
+        assert_eq!(rows(universe.get_grid()), vec!["*", "*", "*"]);
+
+        universe.step(&LifeRule::default());
+
+        assert_eq!(universe.get_origin(), (0, 0));
+        assert_eq!(rows(universe.get_grid()), vec!["***"]);
+    }
+
+    #[test]
+    fn universe_glider_travels() {
+        let glider = create_grid(&[" * ", "  *", "***"]);
+        let mut universe = Universe::from_grid(&glider);
+
+        for _ in 0..40 {
+            universe.step(&LifeRule::default());
+        }
+
+        assert_eq!(universe.get_origin(), (10, 10));
+        assert_eq!(rows(universe.get_grid()), rows(&glider));
+        assert_eq!(rows(&universe.get_region(&BoundingBox::new(9, 10, 5, 3))), vec!["  *  ", "   * ", " *** "]);
+    }
+
+    #[test]
+    fn universe_dies_out() {
+        let mut universe = Universe::from_grid(&create_grid(&["**"]));
+
+        universe.step(&LifeRule::default());
+
+        assert!(universe.is_empty());
+    }
+
+    #[test]
+    fn universe_larger_radius() {
+        // With a range of 2, a lone cell gives births up to 2 cells away,
+        // and survives with no neighbours...
+        let rule = LargerThanLifeRule::parse("R2,C0,M0,S0..0,B1..1,NM").unwrap();
+        let mut universe = Universe::from_grid(&create_grid(&["*"]));
+
+        universe.step(&rule);
+
+        assert_eq!(universe.get_origin(), (-2, -2));
+        assert_eq!(universe.get_population(), 25);
+    }
+
+    #[test]
+    fn universe_remove() {
+        let mut universe = Universe::from_grid(&create_grid(&["**   *"]));
+
+        universe.remove(&[(5, 0), (9, 9)]);
+
+        assert_eq!(universe.get_origin(), (0, 0));
+        assert_eq!(rows(universe.get_grid()), vec!["**"]);
+
+        universe.remove(&[(0, 0), (1, 0)]);
+
+        assert!(universe.is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+/* ----------------------------------------------------------------------------
+
+    MIT License
+
+    Copyright (c) 2024 MW
+
+    Permission is hereby granted, free of charge, to any person obtaining a
+    copy of this software and associated documentation files (the "Software"),
+    to deal in the Software without restriction, including without limitation
+    the rights to use, copy, modify, merge, publish, distribute, sublicense,
+    and/or sell copies of the Software, and to permit persons to whom the
+    Software is furnished to do so, subject to the following conditions:
+
+    The above copyright notice and this permission notice shall be included in
+    all copies or substantial portions of the Software.
+
+    THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+    IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+    FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+    AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+    LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+    FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+    DEALINGS IN THE SOFTWARE.
+
+---------------------------------------------------------------------------- */
+
+use crate::grid::{BoundingBox, Grid, SizedGrid};
+use crate::life::{LifeGrid, LifeGridType, LifeState};
+use crate::rule::Rule;
+
+
+/// An unbounded 'life' universe.
+/// 
+/// The cells are held in a grid that just covers the cells that aren't
+/// 'dead', along with the position of the grid's top-left cell (its
+/// origin) in the universe. Before each step, the grid is given a margin
+/// wide enough for any births, and after it the grid is trimmed back, so a
+/// pattern may grow or travel without reaching an edge.
+/// 
+/// Rules where a cell with no 'live' neighbours is born (`B0`) would fill
+/// the universe, so only the cells within the margin are born.
+#[derive(Clone)]
+pub struct Universe {
+    grid: LifeGridType,
+    origin: (isize, isize)
+}
+
+impl Universe {
+    /// Initialises a universe containing the given grid, with the grid's
+    /// top-left cell at the universe's origin.
+    pub fn from_grid(grid: &LifeGridType) -> Self {
+        match grid.get_bounding_box() {
+            Some(bounds) => Self {
+                grid: grid.crop(&bounds, 0),
+                origin: (bounds.x, bounds.y)
+            },
+            None => Self::new()
+        }
+    }
+
+    /// Initialises an empty universe.
+    pub fn new() -> Self {
+        Self {
+            grid: LifeGridType::init_life(0, 0),
+            origin: (0, 0)
+        }
+    }
+
+    /// Gives the grid of the cells that aren't 'dead'.
+    /// 
+    /// See `get_origin()`.
+    pub fn get_grid(&self) -> &LifeGridType {
+        &self.grid
+    }
+
+    /// Gives the grid of the cells that aren't 'dead', consuming the
+    /// universe.
+    pub fn into_grid(self) -> LifeGridType {
+        self.grid
+    }
+
+    /// Gives the position in the universe of the top-left cell of the grid.
+    pub fn get_origin(&self) -> (isize, isize) {
+        self.origin
+    }
+
+    /// Gives the smallest region containing every cell that isn't 'dead',
+    /// or `None` if the universe is empty.
+    pub fn get_bounding_box(&self) -> Option<BoundingBox> {
+        if self.is_empty() {
+            None
+        }
+        else {
+            Some(BoundingBox::new(self.origin.0, self.origin.1, self.grid.get_width(), self.grid.get_height()))
+        }
+    }
+
+    /// Gives the number of 'live' cells.
+    pub fn get_population(&self) -> usize {
+        self.grid.get_population()
+    }
+
+    /// Indicates if every cell is 'dead'.
+    pub fn is_empty(&self) -> bool {
+        (self.grid.get_width() == 0) || (self.grid.get_height() == 0)
+    }
+
+    /// Gives the state of the cell at the given position.
+    pub fn get_state(&self, x: isize, y: isize) -> LifeState {
+        let (grid_x, grid_y) = (x - self.origin.0, y - self.origin.1);
+        if (grid_x < 0) || (grid_y < 0) || (grid_x as usize >= self.grid.get_width()) || (grid_y as usize >= self.grid.get_height()) {
+            LifeState::DEAD
+        }
+        else {
+            *self.grid.get(grid_x as usize, grid_y as usize)
+        }
+    }
+
+    /// Gives a grid of the given region of the universe.
+    pub fn get_region(&self, bounds: &BoundingBox) -> LifeGridType {
+        self.grid.crop(&bounds.translate(-self.origin.0, -self.origin.1), 0)
+    }
+
+    /// Sets the cells at the given positions to 'dead'.
+    pub fn remove(&mut self, cells: &[(isize, isize)]) {
+        for (x, y) in cells {
+            let (grid_x, grid_y) = (x - self.origin.0, y - self.origin.1);
+            if (grid_x >= 0) && (grid_y >= 0) && ((grid_x as usize) < self.grid.get_width()) && ((grid_y as usize) < self.grid.get_height()) {
+                self.grid.set_dead(grid_x as usize, grid_y as usize);
+            }
+        }
+
+        self.trim(self.origin);
+    }
+
+    /// Advances the universe by a generation of the given rule.
+    pub fn step(&mut self, rule: &dyn Rule) {
+        if self.is_empty() {
+            return;
+        }
+
+        // Give the grid a margin of the rule's radius, so that the grid
+        // includes every cell that could be born...
+        let margin = rule.get_radius();
+        let bounds = BoundingBox::new(0, 0, self.grid.get_width(), self.grid.get_height());
+        let padded = self.grid.crop(&bounds, margin);
+        let new_grid = rule.step(&padded);
+
+        // ...then trim it back to the cells that aren't 'dead'...
+        self.grid = new_grid;
+        self.trim((self.origin.0 - margin as isize, self.origin.1 - margin as isize));
+    }
+
+    /// Trims the grid, with its top-left cell at the given position, to the
+    /// cells that aren't 'dead'.
+    fn trim(&mut self, origin: (isize, isize)) {
+        *self = match self.grid.get_bounding_box() {
+            Some(bounds) => Self {
+                grid: self.grid.crop(&bounds, 0),
+                origin: (origin.0 + bounds.x, origin.1 + bounds.y)
+            },
+            None => Self::new()
+        };
+    }
+}
+
+impl Default for Universe {
+    fn default() -> Self {
+        Self::new()
+    }
+}